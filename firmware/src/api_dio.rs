@@ -6,6 +6,945 @@ pub struct PicohaDioRequest<'a> {
     pub pin_num: u32,
     #[femtopb(enumeration, tag = 3)]
     pub value: ::femtopb::enumeration::EnumValue<PinValue>,
+    #[femtopb(enumeration, tag = 4)]
+    pub pull: ::femtopb::enumeration::EnumValue<PinPull>,
+    #[femtopb(enumeration, tag = 5)]
+    pub drive_strength: ::femtopb::enumeration::EnumValue<PinDriveStrength>,
+    #[femtopb(enumeration, tag = 6)]
+    pub slew_rate: ::femtopb::enumeration::EnumValue<PinSlewRate>,
+    /// Copied back in the answer to match it with this request
+    #[femtopb(uint32, optional, tag = 7)]
+    pub request_id: ::core::option::Option<u32>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct PingRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinDirectionRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(enumeration, tag = 2)]
+    pub direction: ::femtopb::enumeration::EnumValue<Direction>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinValueRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(enumeration, tag = 2)]
+    pub level: ::femtopb::enumeration::EnumValue<Level>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPinDirectionRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPinValueRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinPullRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(enumeration, tag = 2)]
+    pub pull: ::femtopb::enumeration::EnumValue<PinPull>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPinPullRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinDriveRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(enumeration, tag = 2)]
+    pub drive_strength: ::femtopb::enumeration::EnumValue<PinDriveStrength>,
+    #[femtopb(enumeration, tag = 3)]
+    pub slew_rate: ::femtopb::enumeration::EnumValue<PinSlewRate>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPinDriveRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Change the level of several output pins at once, bit n of the masks is GPIO n
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPortValueRequest<'a> {
+    /// Pins to change
+    #[femtopb(uint32, tag = 1)]
+    pub mask: u32,
+    /// Level of each selected pin, 1 is high
+    #[femtopb(uint32, tag = 2)]
+    pub value: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Change the direction of several pins at once, bit n of the masks is GPIO n
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPortDirectionRequest<'a> {
+    /// Pins to change
+    #[femtopb(uint32, tag = 1)]
+    pub mask: u32,
+    /// Direction of each selected pin, 1 is output and 0 is input
+    #[femtopb(uint32, tag = 2)]
+    pub value: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPortStateRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Snapshot of all the pins, bit n of every mask is GPIO n
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct PortState<'a> {
+    /// Pins handled by the device
+    #[femtopb(uint32, tag = 1)]
+    pub available: u32,
+    /// Pins configured as input, push-pull output or open-drain output
+    #[femtopb(uint32, tag = 2)]
+    pub input: u32,
+    #[femtopb(uint32, tag = 3)]
+    pub output: u32,
+    #[femtopb(uint32, tag = 4)]
+    pub open_drain: u32,
+    /// Level on the pads, all sampled in the same cycle
+    #[femtopb(uint32, tag = 5)]
+    pub input_level: u32,
+    /// Level driven by the output latches
+    #[femtopb(uint32, tag = 6)]
+    pub output_latch: u32,
+    /// Pins driven by their PWM slice
+    #[femtopb(uint32, tag = 7)]
+    pub pwm: u32,
+    /// Pins driven by the pattern generator
+    #[femtopb(uint32, tag = 8)]
+    pub pattern: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Push an EdgeEvent on each selected edge of an input pin, EDGE_NONE stops the events
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SubscribeEdgesRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(enumeration, tag = 2)]
+    pub edge: ::femtopb::enumeration::EnumValue<Edge>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Only accept a new input level once it stayed unchanged for the debounce time
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinDebounceRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    /// 0 disables the filter
+    #[femtopb(uint32, tag = 2)]
+    pub debounce_us: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPinDebounceRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Count the selected edges of an input pin on the device, EDGE_NONE stops counting
+/// The count restarts from 0
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct ConfigureEdgeCounterRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(enumeration, tag = 2)]
+    pub edge: ::femtopb::enumeration::EnumValue<Edge>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct ReadEdgeCounterRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    /// Restart the count from 0 once read, no edge is lost in between
+    #[femtopb(bool, tag = 2)]
+    pub clear: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct ResetEdgeCounterRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Measure the signal on an input pin with its PWM slice
+/// Only odd pins, the B channel of a slice, can be measured
+/// Cannot be part of a batch, the requests are not served while measuring
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct MeasureFrequencyRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    /// Duration of each of the two measures, frequency then duty cycle, up to 100 ms
+    #[femtopb(uint32, tag = 2)]
+    pub gate_time_us: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct FrequencyMeasurement<'a> {
+    /// Rising edges counted during the gate time
+    #[femtopb(uint32, tag = 1)]
+    pub edge_count: u32,
+    #[femtopb(double, tag = 2)]
+    pub frequency_hz: f64,
+    /// 0 when no edge was counted
+    #[femtopb(double, tag = 3)]
+    pub period_us: f64,
+    /// Part of the time the input was high, from 0 to 1
+    #[femtopb(double, tag = 4)]
+    pub duty_cycle: f64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Drive a pin with its PWM slice, the pin goes back to SIO with SetPinDirectionRequest
+/// Pins n and n + 1 (n even) share a slice, so they must use the same frequency
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinPwmRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(uint32, tag = 2)]
+    pub frequency_hz: u32,
+    /// Part of the period the output is high, from 0 to 1
+    #[femtopb(double, tag = 3)]
+    pub duty_cycle: f64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Change the duty cycle of a pin already in PWM mode, the period is not restarted
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinPwmDutyRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(double, tag = 2)]
+    pub duty_cycle: f64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Output really generated, rounded to the slice resolution
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct PwmOutput<'a> {
+    #[femtopb(double, tag = 1)]
+    pub frequency_hz: f64,
+    #[femtopb(double, tag = 2)]
+    pub duty_cycle: f64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Pulses timed by the device on an output or open-drain pin, the first one starts at once
+/// The pin is left at the other level, a new direction or value cancels the pulses
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct PulseRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    /// Level of the pin during the pulses
+    #[femtopb(enumeration, tag = 2)]
+    pub active_level: ::femtopb::enumeration::EnumValue<Level>,
+    #[femtopb(uint32, tag = 3)]
+    pub duration_us: u32,
+    /// Number of pulses, 0 is a single pulse
+    #[femtopb(uint32, tag = 4)]
+    pub repeat_count: u32,
+    /// Time between the starts of two pulses, longer than the duration
+    #[femtopb(uint32, tag = 5)]
+    pub period_us: u32,
+    /// Answer when the last pulse ended instead of when the first one started
+    /// Ignored in a batch, the batch is answered at once
+    #[femtopb(bool, tag = 6)]
+    pub wait_completion: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Levels set on the output pins of the mask, at a time offset from the start of the sequence
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SequenceStep<'a> {
+    /// Not smaller than the offset of the previous step
+    #[femtopb(uint32, tag = 1)]
+    pub offset_us: u32,
+    #[femtopb(uint32, tag = 2)]
+    pub mask: u32,
+    #[femtopb(uint32, tag = 3)]
+    pub levels: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Store sequence steps in the device RAM, up to 128 steps
+/// Long sequences are loaded with several requests, the sequence must be stopped
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct LoadSequenceRequest<'a> {
+    #[femtopb(message, repeated, tag = 1)]
+    pub steps: ::femtopb::repeated::Repeated<'a, SequenceStep<'a>, ::femtopb::item_encoding::Message<'a, SequenceStep<'a>>>,
+    /// Add the steps after the ones already loaded instead of replacing them
+    #[femtopb(bool, tag = 2)]
+    pub append: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Play the loaded steps from a timer interrupt, on pins set as output
+/// Setting the value or the direction of one of these pins stops the sequence
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct StartSequenceRequest<'a> {
+    /// Restart the sequence every period, at least 100 us and the offset of the last step
+    /// 0 plays the sequence once
+    #[femtopb(uint32, tag = 1)]
+    pub loop_period_us: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Stop playing the sequence, the pins keep their current levels
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct StopSequenceRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetSequenceStateRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SequenceState<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub step_count: u32,
+    #[femtopb(bool, tag = 2)]
+    pub running: bool,
+    /// Times the last step was played since the sequence started
+    #[femtopb(uint32, tag = 3)]
+    pub completed_loops: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Sample a range of pins at a fixed rate into the device RAM, whatever their direction
+/// A new capture replaces the data of the previous one
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct StartCaptureRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub first_pin: u32,
+    #[femtopb(uint32, tag = 2)]
+    pub pin_count: u32,
+    /// Up to 25 MHz
+    #[femtopb(uint32, tag = 3)]
+    pub sample_rate_hz: u32,
+    /// Samples kept before the trigger, fewer when the trigger comes earlier
+    #[femtopb(uint32, tag = 4)]
+    pub pre_trigger_samples: u32,
+    /// Samples from the trigger one, at least 1
+    #[femtopb(uint32, tag = 5)]
+    pub post_trigger_samples: u32,
+    #[femtopb(enumeration, tag = 6)]
+    pub trigger: ::femtopb::enumeration::EnumValue<CaptureTrigger>,
+    /// Pattern on the sampled pins, bit n is pin first_pin + n
+    #[femtopb(uint32, tag = 7)]
+    pub trigger_mask: u32,
+    #[femtopb(uint32, tag = 8)]
+    pub trigger_value: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Abort the capture in progress, the data of a finished capture is kept
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct StopCaptureRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetCaptureStateRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Read the captured data from a byte offset, answered with a CaptureChunk
+/// Cannot be part of a batch, a few chunks would not fit in the batch answer
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct ReadCaptureRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub offset: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// The sample fields are set once the capture is done
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct CaptureState<'a> {
+    #[femtopb(enumeration, tag = 1)]
+    pub status: ::femtopb::enumeration::EnumValue<CaptureStatus>,
+    #[femtopb(uint32, tag = 2)]
+    pub first_pin: u32,
+    #[femtopb(uint32, tag = 3)]
+    pub pin_count: u32,
+    /// Bits per sample in the data, pin_count rounded up to a power of 2
+    /// Sample n of a 32-bit little-endian word is at bit n * sample_width
+    #[femtopb(uint32, tag = 4)]
+    pub sample_width: u32,
+    /// Real rate, rounded to the clock divider resolution
+    #[femtopb(double, tag = 5)]
+    pub sample_rate_hz: f64,
+    /// Index of the first valid sample in the first word of the data
+    #[femtopb(uint32, tag = 6)]
+    pub first_sample: u32,
+    #[femtopb(uint32, tag = 7)]
+    pub sample_count: u32,
+    /// Index of the trigger sample from the first valid sample
+    #[femtopb(uint32, tag = 8)]
+    pub trigger_sample: u32,
+    /// Bytes of data, whole 32-bit words
+    #[femtopb(uint32, tag = 9)]
+    pub data_size: u32,
+    /// Samples overwritten before being searched for the trigger, it may be missed
+    #[femtopb(bool, tag = 10)]
+    pub samples_skipped: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Part of the captured data, the next chunk starts at offset + the size of data
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct CaptureChunk<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub offset: u32,
+    #[femtopb(bytes, tag = 2)]
+    pub data: &'a [u8],
+    #[femtopb(uint32, tag = 3)]
+    pub data_size: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Store samples to play on a range of pins, in the layout of the captured data
+/// The data is made of whole 32-bit words, long patterns are loaded in several requests
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct LoadPatternRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub first_pin: u32,
+    #[femtopb(uint32, tag = 2)]
+    pub pin_count: u32,
+    #[femtopb(bytes, tag = 3)]
+    pub data: &'a [u8],
+    /// Add the data after the samples already loaded, for the same range of pins
+    #[femtopb(bool, tag = 4)]
+    pub append: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Play the loaded samples on pins set as output, the pins are driven by the pattern
+/// generator until the end of the pattern or a StopPatternRequest
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct StartPatternRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub sample_rate_hz: u32,
+    /// Play the pattern again from its first sample after the last one
+    #[femtopb(bool, tag = 2)]
+    pub looping: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Stop the pattern, the pins stay outputs at the level of the last sample
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct StopPatternRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPatternStateRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct PatternState<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub first_pin: u32,
+    #[femtopb(uint32, tag = 2)]
+    pub pin_count: u32,
+    /// Bits per sample in the data, pin_count rounded up to a power of 2
+    #[femtopb(uint32, tag = 3)]
+    pub sample_width: u32,
+    #[femtopb(uint32, tag = 4)]
+    pub sample_count: u32,
+    #[femtopb(bool, tag = 5)]
+    pub running: bool,
+    #[femtopb(bool, tag = 6)]
+    pub looping: bool,
+    /// Real rate of the last start, rounded to the clock divider resolution
+    #[femtopb(double, tag = 7)]
+    pub sample_rate_hz: f64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Wait for the levels of pins, answered when the condition is met or the timeout expires
+/// Other requests are served while waiting, it cannot be part of a batch
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct WaitForRequest<'a> {
+    /// Pins watched, bit n is GPIO n, a single pin is a mask of one bit
+    #[femtopb(uint32, tag = 1)]
+    pub mask: u32,
+    /// Expected levels of the pins of the mask
+    #[femtopb(uint32, tag = 2)]
+    pub value: u32,
+    #[femtopb(enumeration, tag = 3)]
+    pub condition: ::femtopb::enumeration::EnumValue<WaitCondition>,
+    #[femtopb(uint32, tag = 4)]
+    pub timeout_us: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Filled whether the condition is met or not
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct WaitResult<'a> {
+    /// Time from the request to the condition or the timeout
+    #[femtopb(uint32, tag = 1)]
+    pub elapsed_us: u32,
+    /// Levels of the pins of the mask when answered
+    #[femtopb(uint32, tag = 2)]
+    pub levels: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Read the device clock to estimate its offset and drift from the host clock
+/// With the host times t0 when sending and t3 when receiving the answer, the device clock
+/// is ahead by ((received_us - t0) + (timestamp_us - t3)) / 2
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SyncTimeRequest<'a> {
+    /// Host time of the request, copied back in the answer
+    #[femtopb(uint64, tag = 1)]
+    pub host_time_us: u64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct TimeSync<'a> {
+    #[femtopb(uint64, tag = 1)]
+    pub host_time_us: u64,
+    /// Device time when the request was received, the answer timestamp is when it is sent
+    #[femtopb(uint64, tag = 2)]
+    pub received_us: u64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SetPinSafeStateRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(enumeration, tag = 2)]
+    pub state: ::femtopb::enumeration::EnumValue<SafeState>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// The failsafe trips when no valid frame is received during the heartbeat timeout, counted
+/// from the end of the last request. The pins are then put in their safe state, which stops
+/// their pulses, sequence and pattern.
+/// The pins also go to their safe state when the session is closed, on a USB suspend, a bus
+/// reset or the DTR line going low, even with the failsafe disabled
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct ConfigureFailsafeRequest<'a> {
+    /// 0 disables the failsafe
+    #[femtopb(uint32, tag = 1)]
+    pub heartbeat_timeout_ms: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetFailsafeStateRequest<'a> {
+    /// Clear the tripped and session closed flags once read
+    #[femtopb(bool, tag = 1)]
+    pub clear: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct FailsafeState<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub heartbeat_timeout_ms: u32,
+    /// Set when the failsafe trips, until cleared by the host
+    #[femtopb(bool, tag = 2)]
+    pub tripped: bool,
+    /// Device time of the last trip
+    #[femtopb(uint64, tag = 3)]
+    pub tripped_at_us: u64,
+    /// Pins with a safe state, bit n is GPIO n
+    #[femtopb(uint32, tag = 4)]
+    pub safe_pins: u32,
+    /// Set when the session is closed, until cleared by the host, a close is not a trip
+    #[femtopb(bool, tag = 5)]
+    pub session_closed: bool,
+    /// Device time of the last session close
+    #[femtopb(uint64, tag = 6)]
+    pub session_closed_at_us: u64,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Save the direction, level, pull, drive and safe state of every pin as its power-on
+/// configuration, PWM and pattern pins are saved as outputs at their current level
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SaveConfigRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Read the power-on configuration saved for a pin
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetSavedConfigRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Erase the saved configuration, the pins start as inputs with pull-down again
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct EraseConfigRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Put every pin in its factory configuration now, input with pull-down and no safe state,
+/// the saved configuration is kept
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct RestoreDefaultsRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct SavedPinConfig<'a> {
+    /// False when no valid configuration is saved, the other fields are then the factory ones
+    #[femtopb(bool, tag = 1)]
+    pub saved: bool,
+    #[femtopb(uint32, tag = 2)]
+    pub version: u32,
+    #[femtopb(enumeration, tag = 3)]
+    pub direction: ::femtopb::enumeration::EnumValue<Direction>,
+    /// Output level, open-drain pins release the line when high
+    #[femtopb(enumeration, tag = 4)]
+    pub level: ::femtopb::enumeration::EnumValue<Level>,
+    #[femtopb(enumeration, tag = 5)]
+    pub pull: ::femtopb::enumeration::EnumValue<PinPull>,
+    #[femtopb(enumeration, tag = 6)]
+    pub drive_strength: ::femtopb::enumeration::EnumValue<PinDriveStrength>,
+    #[femtopb(enumeration, tag = 7)]
+    pub slew_rate: ::femtopb::enumeration::EnumValue<PinSlewRate>,
+    #[femtopb(enumeration, tag = 8)]
+    pub safe_state: ::femtopb::enumeration::EnumValue<SafeState>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Name a pin, an empty name removes it. A name has up to 16 letters, digits, '_', '-' or
+/// '.', and is used by a single pin. The names are written in the flash right away.
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct SetPinNameRequest<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(string, tag = 2)]
+    pub name: &'a str,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Read the names of all the named pins, cannot be part of a batch
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct GetPinNamesRequest<'a> {
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct PinName<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    #[femtopb(string, tag = 2)]
+    pub name: &'a str,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// One operation of a batch, same tags as the matching v2 payloads
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct BatchOperation<'a> {
+    /// Name of the pin targeted by the operation, replaces its pin_num
+    #[femtopb(string, tag = 1)]
+    pub pin_name: &'a str,
+    #[femtopb(oneof, tags = [17, 18, 19, 20, 21, 22, 23, 24, 26, 27, 28, 29, 30, 31, 32, 33, 34, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 47, 48, 49, 50, 53, 54, 55, 56, 57, 58, 59, 60])]
+    pub operation: ::core::option::Option<batch_operation::Operation<'a>>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Nested message and enum types in `BatchOperation`.
+pub mod batch_operation {
+    #[derive(Clone, PartialEq, ::femtopb::Oneof)]
+    #[non_exhaustive]
+    pub enum Operation<'a> {
+        #[femtopb(message, tag = 17)]
+        SetPinDirection(super::SetPinDirectionRequest<'a>),
+        #[femtopb(message, tag = 18)]
+        SetPinValue(super::SetPinValueRequest<'a>),
+        #[femtopb(message, tag = 19)]
+        GetPinDirection(super::GetPinDirectionRequest<'a>),
+        #[femtopb(message, tag = 20)]
+        GetPinValue(super::GetPinValueRequest<'a>),
+        #[femtopb(message, tag = 21)]
+        SetPinPull(super::SetPinPullRequest<'a>),
+        #[femtopb(message, tag = 22)]
+        GetPinPull(super::GetPinPullRequest<'a>),
+        #[femtopb(message, tag = 23)]
+        SetPinDrive(super::SetPinDriveRequest<'a>),
+        #[femtopb(message, tag = 24)]
+        GetPinDrive(super::GetPinDriveRequest<'a>),
+        #[femtopb(message, tag = 26)]
+        SetPortValue(super::SetPortValueRequest<'a>),
+        #[femtopb(message, tag = 27)]
+        SetPortDirection(super::SetPortDirectionRequest<'a>),
+        #[femtopb(message, tag = 28)]
+        GetPortState(super::GetPortStateRequest<'a>),
+        #[femtopb(message, tag = 29)]
+        SubscribeEdges(super::SubscribeEdgesRequest<'a>),
+        #[femtopb(message, tag = 30)]
+        SetPinDebounce(super::SetPinDebounceRequest<'a>),
+        #[femtopb(message, tag = 31)]
+        GetPinDebounce(super::GetPinDebounceRequest<'a>),
+        #[femtopb(message, tag = 32)]
+        ConfigureEdgeCounter(super::ConfigureEdgeCounterRequest<'a>),
+        #[femtopb(message, tag = 33)]
+        ReadEdgeCounter(super::ReadEdgeCounterRequest<'a>),
+        #[femtopb(message, tag = 34)]
+        ResetEdgeCounter(super::ResetEdgeCounterRequest<'a>),
+        #[femtopb(message, tag = 36)]
+        SetPinPwm(super::SetPinPwmRequest<'a>),
+        #[femtopb(message, tag = 37)]
+        SetPinPwmDuty(super::SetPinPwmDutyRequest<'a>),
+        #[femtopb(message, tag = 38)]
+        Pulse(super::PulseRequest<'a>),
+        #[femtopb(message, tag = 39)]
+        LoadSequence(super::LoadSequenceRequest<'a>),
+        #[femtopb(message, tag = 40)]
+        StartSequence(super::StartSequenceRequest<'a>),
+        #[femtopb(message, tag = 41)]
+        StopSequence(super::StopSequenceRequest<'a>),
+        #[femtopb(message, tag = 42)]
+        GetSequenceState(super::GetSequenceStateRequest<'a>),
+        #[femtopb(message, tag = 43)]
+        StartCapture(super::StartCaptureRequest<'a>),
+        #[femtopb(message, tag = 44)]
+        StopCapture(super::StopCaptureRequest<'a>),
+        #[femtopb(message, tag = 45)]
+        GetCaptureState(super::GetCaptureStateRequest<'a>),
+        #[femtopb(message, tag = 47)]
+        LoadPattern(super::LoadPatternRequest<'a>),
+        #[femtopb(message, tag = 48)]
+        StartPattern(super::StartPatternRequest<'a>),
+        #[femtopb(message, tag = 49)]
+        StopPattern(super::StopPatternRequest<'a>),
+        #[femtopb(message, tag = 50)]
+        GetPatternState(super::GetPatternStateRequest<'a>),
+        #[femtopb(message, tag = 53)]
+        SetPinSafeState(super::SetPinSafeStateRequest<'a>),
+        #[femtopb(message, tag = 54)]
+        ConfigureFailsafe(super::ConfigureFailsafeRequest<'a>),
+        #[femtopb(message, tag = 55)]
+        GetFailsafeState(super::GetFailsafeStateRequest<'a>),
+        #[femtopb(message, tag = 56)]
+        SaveConfig(super::SaveConfigRequest<'a>),
+        #[femtopb(message, tag = 57)]
+        GetSavedConfig(super::GetSavedConfigRequest<'a>),
+        #[femtopb(message, tag = 58)]
+        EraseConfig(super::EraseConfigRequest<'a>),
+        #[femtopb(message, tag = 59)]
+        RestoreDefaults(super::RestoreDefaultsRequest<'a>),
+        #[femtopb(message, tag = 60)]
+        SetPinName(super::SetPinNameRequest<'a>),
+        #[femtopb(phantom)]
+        _Phantom(::core::marker::PhantomData<&'a ()>),
+    }
+}
+/// Operations executed in order, answered with a single PicohaDioAnswer
+/// The batch stops with ERROR_CODE_ANSWER_TOO_LARGE before an operation whose result might not
+/// fit in the answer, the results of the operations already executed are kept
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct BatchRequest<'a> {
+    #[femtopb(message, repeated, tag = 1)]
+    pub operations: ::femtopb::repeated::Repeated<'a, BatchOperation<'a>, ::femtopb::item_encoding::Message<'a, BatchOperation<'a>>>,
+    /// Skip the remaining operations after the first failure
+    #[femtopb(bool, tag = 2)]
+    pub stop_on_failure: bool,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, PartialEq, ::femtopb::Message)]
+pub struct PicohaDioRequestV2<'a> {
+    /// Same tag as the v1 request_id, copied back in the answer
+    #[femtopb(uint32, optional, tag = 7)]
+    pub request_id: ::core::option::Option<u32>,
+    /// Name of the pin targeted by the request, replaces its pin_num
+    #[femtopb(string, tag = 8)]
+    pub pin_name: &'a str,
+    #[femtopb(oneof, tags = [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61])]
+    pub payload: ::core::option::Option<picoha_dio_request_v2::Payload<'a>>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Nested message and enum types in `PicohaDioRequestV2`.
+pub mod picoha_dio_request_v2 {
+    #[derive(Clone, PartialEq, ::femtopb::Oneof)]
+    #[non_exhaustive]
+    pub enum Payload<'a> {
+        #[femtopb(message, tag = 16)]
+        Ping(super::PingRequest<'a>),
+        #[femtopb(message, tag = 17)]
+        SetPinDirection(super::SetPinDirectionRequest<'a>),
+        #[femtopb(message, tag = 18)]
+        SetPinValue(super::SetPinValueRequest<'a>),
+        #[femtopb(message, tag = 19)]
+        GetPinDirection(super::GetPinDirectionRequest<'a>),
+        #[femtopb(message, tag = 20)]
+        GetPinValue(super::GetPinValueRequest<'a>),
+        #[femtopb(message, tag = 21)]
+        SetPinPull(super::SetPinPullRequest<'a>),
+        #[femtopb(message, tag = 22)]
+        GetPinPull(super::GetPinPullRequest<'a>),
+        #[femtopb(message, tag = 23)]
+        SetPinDrive(super::SetPinDriveRequest<'a>),
+        #[femtopb(message, tag = 24)]
+        GetPinDrive(super::GetPinDriveRequest<'a>),
+        #[femtopb(message, tag = 25)]
+        Batch(super::BatchRequest<'a>),
+        #[femtopb(message, tag = 26)]
+        SetPortValue(super::SetPortValueRequest<'a>),
+        #[femtopb(message, tag = 27)]
+        SetPortDirection(super::SetPortDirectionRequest<'a>),
+        #[femtopb(message, tag = 28)]
+        GetPortState(super::GetPortStateRequest<'a>),
+        #[femtopb(message, tag = 29)]
+        SubscribeEdges(super::SubscribeEdgesRequest<'a>),
+        #[femtopb(message, tag = 30)]
+        SetPinDebounce(super::SetPinDebounceRequest<'a>),
+        #[femtopb(message, tag = 31)]
+        GetPinDebounce(super::GetPinDebounceRequest<'a>),
+        #[femtopb(message, tag = 32)]
+        ConfigureEdgeCounter(super::ConfigureEdgeCounterRequest<'a>),
+        #[femtopb(message, tag = 33)]
+        ReadEdgeCounter(super::ReadEdgeCounterRequest<'a>),
+        #[femtopb(message, tag = 34)]
+        ResetEdgeCounter(super::ResetEdgeCounterRequest<'a>),
+        #[femtopb(message, tag = 35)]
+        MeasureFrequency(super::MeasureFrequencyRequest<'a>),
+        #[femtopb(message, tag = 36)]
+        SetPinPwm(super::SetPinPwmRequest<'a>),
+        #[femtopb(message, tag = 37)]
+        SetPinPwmDuty(super::SetPinPwmDutyRequest<'a>),
+        #[femtopb(message, tag = 38)]
+        Pulse(super::PulseRequest<'a>),
+        #[femtopb(message, tag = 39)]
+        LoadSequence(super::LoadSequenceRequest<'a>),
+        #[femtopb(message, tag = 40)]
+        StartSequence(super::StartSequenceRequest<'a>),
+        #[femtopb(message, tag = 41)]
+        StopSequence(super::StopSequenceRequest<'a>),
+        #[femtopb(message, tag = 42)]
+        GetSequenceState(super::GetSequenceStateRequest<'a>),
+        #[femtopb(message, tag = 43)]
+        StartCapture(super::StartCaptureRequest<'a>),
+        #[femtopb(message, tag = 44)]
+        StopCapture(super::StopCaptureRequest<'a>),
+        #[femtopb(message, tag = 45)]
+        GetCaptureState(super::GetCaptureStateRequest<'a>),
+        #[femtopb(message, tag = 46)]
+        ReadCapture(super::ReadCaptureRequest<'a>),
+        #[femtopb(message, tag = 47)]
+        LoadPattern(super::LoadPatternRequest<'a>),
+        #[femtopb(message, tag = 48)]
+        StartPattern(super::StartPatternRequest<'a>),
+        #[femtopb(message, tag = 49)]
+        StopPattern(super::StopPatternRequest<'a>),
+        #[femtopb(message, tag = 50)]
+        GetPatternState(super::GetPatternStateRequest<'a>),
+        #[femtopb(message, tag = 51)]
+        WaitFor(super::WaitForRequest<'a>),
+        #[femtopb(message, tag = 52)]
+        SyncTime(super::SyncTimeRequest<'a>),
+        #[femtopb(message, tag = 53)]
+        SetPinSafeState(super::SetPinSafeStateRequest<'a>),
+        #[femtopb(message, tag = 54)]
+        ConfigureFailsafe(super::ConfigureFailsafeRequest<'a>),
+        #[femtopb(message, tag = 55)]
+        GetFailsafeState(super::GetFailsafeStateRequest<'a>),
+        #[femtopb(message, tag = 56)]
+        SaveConfig(super::SaveConfigRequest<'a>),
+        #[femtopb(message, tag = 57)]
+        GetSavedConfig(super::GetSavedConfigRequest<'a>),
+        #[femtopb(message, tag = 58)]
+        EraseConfig(super::EraseConfigRequest<'a>),
+        #[femtopb(message, tag = 59)]
+        RestoreDefaults(super::RestoreDefaultsRequest<'a>),
+        #[femtopb(message, tag = 60)]
+        SetPinName(super::SetPinNameRequest<'a>),
+        #[femtopb(message, tag = 61)]
+        GetPinNames(super::GetPinNamesRequest<'a>),
+        #[femtopb(phantom)]
+        _Phantom(::core::marker::PhantomData<&'a ()>),
+    }
+}
+/// Result of one operation of a batch, values are filled like in PicohaDioAnswer
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct BatchOperationResult<'a> {
+    #[femtopb(enumeration, tag = 1)]
+    pub r#type: ::femtopb::enumeration::EnumValue<AnswerType>,
+    #[femtopb(enumeration, optional, tag = 2)]
+    pub error_code: ::core::option::Option<::femtopb::enumeration::EnumValue<ErrorCode>>,
+    #[femtopb(enumeration, optional, tag = 3)]
+    pub direction: ::core::option::Option<::femtopb::enumeration::EnumValue<Direction>>,
+    #[femtopb(enumeration, optional, tag = 4)]
+    pub level: ::core::option::Option<::femtopb::enumeration::EnumValue<Level>>,
+    #[femtopb(enumeration, optional, tag = 5)]
+    pub pull: ::core::option::Option<::femtopb::enumeration::EnumValue<PinPull>>,
+    #[femtopb(enumeration, optional, tag = 6)]
+    pub drive_strength: ::core::option::Option<::femtopb::enumeration::EnumValue<PinDriveStrength>>,
+    #[femtopb(enumeration, optional, tag = 7)]
+    pub slew_rate: ::core::option::Option<::femtopb::enumeration::EnumValue<PinSlewRate>>,
+    #[femtopb(message, optional, tag = 8)]
+    pub port_state: ::core::option::Option<PortState<'a>>,
+    #[femtopb(uint32, optional, tag = 9)]
+    pub debounce_us: ::core::option::Option<u32>,
+    #[femtopb(uint32, optional, tag = 10)]
+    pub edge_count: ::core::option::Option<u32>,
+    #[femtopb(enumeration, optional, tag = 11)]
+    pub counted_edge: ::core::option::Option<::femtopb::enumeration::EnumValue<Edge>>,
+    #[femtopb(message, optional, tag = 12)]
+    pub frequency: ::core::option::Option<FrequencyMeasurement<'a>>,
+    #[femtopb(message, optional, tag = 13)]
+    pub pwm: ::core::option::Option<PwmOutput<'a>>,
+    #[femtopb(message, optional, tag = 14)]
+    pub sequence: ::core::option::Option<SequenceState<'a>>,
+    #[femtopb(message, optional, tag = 15)]
+    pub capture: ::core::option::Option<CaptureState<'a>>,
+    #[femtopb(message, optional, tag = 17)]
+    pub pattern: ::core::option::Option<PatternState<'a>>,
+    #[femtopb(uint64, optional, tag = 18)]
+    pub timestamp_us: ::core::option::Option<u64>,
+    #[femtopb(message, optional, tag = 19)]
+    pub failsafe: ::core::option::Option<FailsafeState<'a>>,
+    #[femtopb(message, optional, tag = 20)]
+    pub saved_config: ::core::option::Option<SavedPinConfig<'a>>,
     #[femtopb(unknown_fields)]
     pub unknown_fields: femtopb::UnknownFields<'a>,
 }
@@ -17,9 +956,411 @@ pub struct PicohaDioAnswer<'a> {
     pub value: ::core::option::Option<::femtopb::enumeration::EnumValue<PinValue>>,
     #[femtopb(string, optional, tag = 3)]
     pub error_message: ::core::option::Option<&'a str>,
+    #[femtopb(enumeration, optional, tag = 4)]
+    pub pull: ::core::option::Option<::femtopb::enumeration::EnumValue<PinPull>>,
+    #[femtopb(enumeration, optional, tag = 5)]
+    pub drive_strength: ::core::option::Option<::femtopb::enumeration::EnumValue<PinDriveStrength>>,
+    #[femtopb(enumeration, optional, tag = 6)]
+    pub slew_rate: ::core::option::Option<::femtopb::enumeration::EnumValue<PinSlewRate>>,
+    #[femtopb(enumeration, optional, tag = 7)]
+    pub direction: ::core::option::Option<::femtopb::enumeration::EnumValue<Direction>>,
+    #[femtopb(enumeration, optional, tag = 8)]
+    pub level: ::core::option::Option<::femtopb::enumeration::EnumValue<Level>>,
+    #[femtopb(enumeration, optional, tag = 9)]
+    pub error_code: ::core::option::Option<::femtopb::enumeration::EnumValue<ErrorCode>>,
+    /// Echo of the request this answer belongs to
+    #[femtopb(uint32, optional, tag = 10)]
+    pub request_id: ::core::option::Option<u32>,
+    #[femtopb(enumeration, optional, tag = 11)]
+    pub request_type: ::core::option::Option<::femtopb::enumeration::EnumValue<RequestType>>,
+    #[femtopb(uint32, optional, tag = 12)]
+    pub pin_num: ::core::option::Option<u32>,
+    /// One result per executed operation of a batch, in order
+    #[femtopb(message, repeated, tag = 13)]
+    pub batch_results: ::femtopb::repeated::Repeated<'a, BatchOperationResult<'a>, ::femtopb::item_encoding::Message<'a, BatchOperationResult<'a>>>,
+    #[femtopb(message, optional, tag = 14)]
+    pub port_state: ::core::option::Option<PortState<'a>>,
+    #[femtopb(uint32, optional, tag = 15)]
+    pub debounce_us: ::core::option::Option<u32>,
+    /// Edge count of the pin and the edges it counts
+    #[femtopb(uint32, optional, tag = 16)]
+    pub edge_count: ::core::option::Option<u32>,
+    #[femtopb(enumeration, optional, tag = 17)]
+    pub counted_edge: ::core::option::Option<::femtopb::enumeration::EnumValue<Edge>>,
+    #[femtopb(message, optional, tag = 18)]
+    pub frequency: ::core::option::Option<FrequencyMeasurement<'a>>,
+    #[femtopb(message, optional, tag = 19)]
+    pub pwm: ::core::option::Option<PwmOutput<'a>>,
+    #[femtopb(message, optional, tag = 20)]
+    pub sequence: ::core::option::Option<SequenceState<'a>>,
+    #[femtopb(message, optional, tag = 21)]
+    pub capture: ::core::option::Option<CaptureState<'a>>,
+    #[femtopb(message, optional, tag = 22)]
+    pub capture_chunk: ::core::option::Option<CaptureChunk<'a>>,
+    #[femtopb(message, optional, tag = 23)]
+    pub pattern: ::core::option::Option<PatternState<'a>>,
+    #[femtopb(message, optional, tag = 24)]
+    pub wait: ::core::option::Option<WaitResult<'a>>,
+    /// Device time when the operation took effect, microseconds since boot
+    #[femtopb(uint64, optional, tag = 25)]
+    pub timestamp_us: ::core::option::Option<u64>,
+    #[femtopb(message, optional, tag = 26)]
+    pub time_sync: ::core::option::Option<TimeSync<'a>>,
+    #[femtopb(message, optional, tag = 27)]
+    pub failsafe: ::core::option::Option<FailsafeState<'a>>,
+    #[femtopb(message, optional, tag = 28)]
+    pub saved_config: ::core::option::Option<SavedPinConfig<'a>>,
+    /// Named pins, in pin number order
+    #[femtopb(message, repeated, tag = 29)]
+    pub pin_names: ::femtopb::repeated::Repeated<'a, PinName<'a>, ::femtopb::item_encoding::Message<'a, PinName<'a>>>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct EdgeEvent<'a> {
+    #[femtopb(uint32, tag = 1)]
+    pub pin_num: u32,
+    /// Level of the pin after the edge
+    #[femtopb(enumeration, tag = 2)]
+    pub level: ::femtopb::enumeration::EnumValue<Level>,
+    /// Device time of the edge, microseconds since boot
+    #[femtopb(uint64, tag = 3)]
+    pub timestamp_us: u64,
+    /// Events dropped just before this one because they were not sent in time
+    #[femtopb(uint32, tag = 4)]
+    pub lost_events: u32,
     #[femtopb(unknown_fields)]
     pub unknown_fields: femtopb::UnknownFields<'a>,
 }
+#[derive(Clone, Copy, PartialEq, ::femtopb::Message)]
+pub struct PicohaDioEvent<'a> {
+    #[femtopb(oneof, tags = [32])]
+    pub event: ::core::option::Option<picoha_dio_event::Event<'a>>,
+    #[femtopb(unknown_fields)]
+    pub unknown_fields: femtopb::UnknownFields<'a>,
+}
+/// Nested message and enum types in `PicohaDioEvent`.
+pub mod picoha_dio_event {
+    #[derive(Clone, Copy, PartialEq, ::femtopb::Oneof)]
+    #[non_exhaustive]
+    pub enum Event<'a> {
+        #[femtopb(message, tag = 32)]
+        Edge(super::EdgeEvent<'a>),
+        #[femtopb(phantom)]
+        _Phantom(::core::marker::PhantomData<&'a ()>),
+    }
+}
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum RequestType {
+    #[default]
+    Ping = 0,
+    SetPinDirection = 1,
+    SetPinValue = 2,
+    GetPinDirection = 3,
+    GetPinValue = 4,
+    SetPinPull = 5,
+    GetPinPull = 6,
+    SetPinDrive = 7,
+    GetPinDrive = 8,
+    /// v2 only, a v1 request cannot carry the operations
+    Batch = 9,
+    SetPortValue = 10,
+    SetPortDirection = 11,
+    GetPortState = 12,
+    SubscribeEdges = 13,
+    SetPinDebounce = 14,
+    GetPinDebounce = 15,
+    ConfigureEdgeCounter = 16,
+    ReadEdgeCounter = 17,
+    ResetEdgeCounter = 18,
+    MeasureFrequency = 19,
+    SetPinPwm = 20,
+    SetPinPwmDuty = 21,
+    Pulse = 22,
+    LoadSequence = 23,
+    StartSequence = 24,
+    StopSequence = 25,
+    GetSequenceState = 26,
+    StartCapture = 27,
+    StopCapture = 28,
+    GetCaptureState = 29,
+    ReadCapture = 30,
+    LoadPattern = 31,
+    StartPattern = 32,
+    StopPattern = 33,
+    GetPatternState = 34,
+    WaitFor = 35,
+    SyncTime = 36,
+    SetPinSafeState = 37,
+    ConfigureFailsafe = 38,
+    GetFailsafeState = 39,
+    SaveConfig = 40,
+    GetSavedConfig = 41,
+    EraseConfig = 42,
+    RestoreDefaults = 43,
+    SetPinName = 44,
+    GetPinNames = 45,
+}
+impl RequestType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Ping => "PING",
+            Self::SetPinDirection => "SET_PIN_DIRECTION",
+            Self::SetPinValue => "SET_PIN_VALUE",
+            Self::GetPinDirection => "GET_PIN_DIRECTION",
+            Self::GetPinValue => "GET_PIN_VALUE",
+            Self::SetPinPull => "SET_PIN_PULL",
+            Self::GetPinPull => "GET_PIN_PULL",
+            Self::SetPinDrive => "SET_PIN_DRIVE",
+            Self::GetPinDrive => "GET_PIN_DRIVE",
+            Self::Batch => "BATCH",
+            Self::SetPortValue => "SET_PORT_VALUE",
+            Self::SetPortDirection => "SET_PORT_DIRECTION",
+            Self::GetPortState => "GET_PORT_STATE",
+            Self::SubscribeEdges => "SUBSCRIBE_EDGES",
+            Self::SetPinDebounce => "SET_PIN_DEBOUNCE",
+            Self::GetPinDebounce => "GET_PIN_DEBOUNCE",
+            Self::ConfigureEdgeCounter => "CONFIGURE_EDGE_COUNTER",
+            Self::ReadEdgeCounter => "READ_EDGE_COUNTER",
+            Self::ResetEdgeCounter => "RESET_EDGE_COUNTER",
+            Self::MeasureFrequency => "MEASURE_FREQUENCY",
+            Self::SetPinPwm => "SET_PIN_PWM",
+            Self::SetPinPwmDuty => "SET_PIN_PWM_DUTY",
+            Self::Pulse => "PULSE",
+            Self::LoadSequence => "LOAD_SEQUENCE",
+            Self::StartSequence => "START_SEQUENCE",
+            Self::StopSequence => "STOP_SEQUENCE",
+            Self::GetSequenceState => "GET_SEQUENCE_STATE",
+            Self::StartCapture => "START_CAPTURE",
+            Self::StopCapture => "STOP_CAPTURE",
+            Self::GetCaptureState => "GET_CAPTURE_STATE",
+            Self::ReadCapture => "READ_CAPTURE",
+            Self::LoadPattern => "LOAD_PATTERN",
+            Self::StartPattern => "START_PATTERN",
+            Self::StopPattern => "STOP_PATTERN",
+            Self::GetPatternState => "GET_PATTERN_STATE",
+            Self::WaitFor => "WAIT_FOR",
+            Self::SyncTime => "SYNC_TIME",
+            Self::SetPinSafeState => "SET_PIN_SAFE_STATE",
+            Self::ConfigureFailsafe => "CONFIGURE_FAILSAFE",
+            Self::GetFailsafeState => "GET_FAILSAFE_STATE",
+            Self::SaveConfig => "SAVE_CONFIG",
+            Self::GetSavedConfig => "GET_SAVED_CONFIG",
+            Self::EraseConfig => "ERASE_CONFIG",
+            Self::RestoreDefaults => "RESTORE_DEFAULTS",
+            Self::SetPinName => "SET_PIN_NAME",
+            Self::GetPinNames => "GET_PIN_NAMES",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PING" => Some(Self::Ping),
+            "SET_PIN_DIRECTION" => Some(Self::SetPinDirection),
+            "SET_PIN_VALUE" => Some(Self::SetPinValue),
+            "GET_PIN_DIRECTION" => Some(Self::GetPinDirection),
+            "GET_PIN_VALUE" => Some(Self::GetPinValue),
+            "SET_PIN_PULL" => Some(Self::SetPinPull),
+            "GET_PIN_PULL" => Some(Self::GetPinPull),
+            "SET_PIN_DRIVE" => Some(Self::SetPinDrive),
+            "GET_PIN_DRIVE" => Some(Self::GetPinDrive),
+            "BATCH" => Some(Self::Batch),
+            "SET_PORT_VALUE" => Some(Self::SetPortValue),
+            "SET_PORT_DIRECTION" => Some(Self::SetPortDirection),
+            "GET_PORT_STATE" => Some(Self::GetPortState),
+            "SUBSCRIBE_EDGES" => Some(Self::SubscribeEdges),
+            "SET_PIN_DEBOUNCE" => Some(Self::SetPinDebounce),
+            "GET_PIN_DEBOUNCE" => Some(Self::GetPinDebounce),
+            "CONFIGURE_EDGE_COUNTER" => Some(Self::ConfigureEdgeCounter),
+            "READ_EDGE_COUNTER" => Some(Self::ReadEdgeCounter),
+            "RESET_EDGE_COUNTER" => Some(Self::ResetEdgeCounter),
+            "MEASURE_FREQUENCY" => Some(Self::MeasureFrequency),
+            "SET_PIN_PWM" => Some(Self::SetPinPwm),
+            "SET_PIN_PWM_DUTY" => Some(Self::SetPinPwmDuty),
+            "PULSE" => Some(Self::Pulse),
+            "LOAD_SEQUENCE" => Some(Self::LoadSequence),
+            "START_SEQUENCE" => Some(Self::StartSequence),
+            "STOP_SEQUENCE" => Some(Self::StopSequence),
+            "GET_SEQUENCE_STATE" => Some(Self::GetSequenceState),
+            "START_CAPTURE" => Some(Self::StartCapture),
+            "STOP_CAPTURE" => Some(Self::StopCapture),
+            "GET_CAPTURE_STATE" => Some(Self::GetCaptureState),
+            "READ_CAPTURE" => Some(Self::ReadCapture),
+            "LOAD_PATTERN" => Some(Self::LoadPattern),
+            "START_PATTERN" => Some(Self::StartPattern),
+            "STOP_PATTERN" => Some(Self::StopPattern),
+            "GET_PATTERN_STATE" => Some(Self::GetPatternState),
+            "WAIT_FOR" => Some(Self::WaitFor),
+            "SYNC_TIME" => Some(Self::SyncTime),
+            "SET_PIN_SAFE_STATE" => Some(Self::SetPinSafeState),
+            "CONFIGURE_FAILSAFE" => Some(Self::ConfigureFailsafe),
+            "GET_FAILSAFE_STATE" => Some(Self::GetFailsafeState),
+            "SAVE_CONFIG" => Some(Self::SaveConfig),
+            "GET_SAVED_CONFIG" => Some(Self::GetSavedConfig),
+            "ERASE_CONFIG" => Some(Self::EraseConfig),
+            "RESTORE_DEFAULTS" => Some(Self::RestoreDefaults),
+            "SET_PIN_NAME" => Some(Self::SetPinName),
+            "GET_PIN_NAMES" => Some(Self::GetPinNames),
+            _ => None,
+        }
+    }
+}
+/// Protocol v1 value, mixes levels and directions
+/// Replaced by Direction and Level in protocol v2
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum PinValue {
+    #[default]
+    Low = 0,
+    High = 1,
+    Input = 2,
+    Output = 3,
+    OpenDrain = 4,
+}
+impl PinValue {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Low => "LOW",
+            Self::High => "HIGH",
+            Self::Input => "INPUT",
+            Self::Output => "OUTPUT",
+            Self::OpenDrain => "OPEN_DRAIN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "LOW" => Some(Self::Low),
+            "HIGH" => Some(Self::High),
+            "INPUT" => Some(Self::Input),
+            "OUTPUT" => Some(Self::Output),
+            "OPEN_DRAIN" => Some(Self::OpenDrain),
+            _ => None,
+        }
+    }
+}
+/// Pull resistor applied on the pin pad
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum PinPull {
+    #[default]
+    PullNone = 0,
+    PullUp = 1,
+    PullDown = 2,
+    PullBusKeep = 3,
+}
+impl PinPull {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::PullNone => "PULL_NONE",
+            Self::PullUp => "PULL_UP",
+            Self::PullDown => "PULL_DOWN",
+            Self::PullBusKeep => "PULL_BUS_KEEP",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PULL_NONE" => Some(Self::PullNone),
+            "PULL_UP" => Some(Self::PullUp),
+            "PULL_DOWN" => Some(Self::PullDown),
+            "PULL_BUS_KEEP" => Some(Self::PullBusKeep),
+            _ => None,
+        }
+    }
+}
+/// Output drive strength of the pin pad
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum PinDriveStrength {
+    #[default]
+    Drive2ma = 0,
+    Drive4ma = 1,
+    Drive8ma = 2,
+    Drive12ma = 3,
+}
+impl PinDriveStrength {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Drive2ma => "DRIVE_2MA",
+            Self::Drive4ma => "DRIVE_4MA",
+            Self::Drive8ma => "DRIVE_8MA",
+            Self::Drive12ma => "DRIVE_12MA",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DRIVE_2MA" => Some(Self::Drive2ma),
+            "DRIVE_4MA" => Some(Self::Drive4ma),
+            "DRIVE_8MA" => Some(Self::Drive8ma),
+            "DRIVE_12MA" => Some(Self::Drive12ma),
+            _ => None,
+        }
+    }
+}
+/// Output slew rate of the pin pad
 #[derive(
     Clone,
     Copy,
@@ -33,42 +1374,31 @@ pub struct PicohaDioAnswer<'a> {
 )]
 #[repr(i32)]
 #[derive(Default)]
-pub enum RequestType {
+pub enum PinSlewRate {
     #[default]
-    Ping = 0,
-    SetPinDirection = 1,
-    SetPinValue = 2,
-    GetPinDirection = 3,
-    GetPinValue = 4,
+    SlewSlow = 0,
+    SlewFast = 1,
 }
-impl RequestType {
+impl PinSlewRate {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            Self::Ping => "PING",
-            Self::SetPinDirection => "SET_PIN_DIRECTION",
-            Self::SetPinValue => "SET_PIN_VALUE",
-            Self::GetPinDirection => "GET_PIN_DIRECTION",
-            Self::GetPinValue => "GET_PIN_VALUE",
+            Self::SlewSlow => "SLEW_SLOW",
+            Self::SlewFast => "SLEW_FAST",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "PING" => Some(Self::Ping),
-            "SET_PIN_DIRECTION" => Some(Self::SetPinDirection),
-            "SET_PIN_VALUE" => Some(Self::SetPinValue),
-            "GET_PIN_DIRECTION" => Some(Self::GetPinDirection),
-            "GET_PIN_VALUE" => Some(Self::GetPinValue),
+            "SLEW_SLOW" => Some(Self::SlewSlow),
+            "SLEW_FAST" => Some(Self::SlewFast),
             _ => None,
         }
     }
 }
-/// This structure should be splitted
-/// 1 for values and 1 for directions
 #[derive(
     Clone,
     Copy,
@@ -82,33 +1412,300 @@ impl RequestType {
 )]
 #[repr(i32)]
 #[derive(Default)]
-pub enum PinValue {
+pub enum Direction {
+    #[default]
+    Input = 0,
+    Output = 1,
+    OpenDrain = 2,
+    /// Only reported, a pin enters PWM mode with SetPinPwmRequest
+    Pwm = 3,
+    /// Only reported, the pins of a pattern enter this mode while it plays
+    Pattern = 4,
+}
+impl Direction {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Input => "DIRECTION_INPUT",
+            Self::Output => "DIRECTION_OUTPUT",
+            Self::OpenDrain => "DIRECTION_OPEN_DRAIN",
+            Self::Pwm => "DIRECTION_PWM",
+            Self::Pattern => "DIRECTION_PATTERN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DIRECTION_INPUT" => Some(Self::Input),
+            "DIRECTION_OUTPUT" => Some(Self::Output),
+            "DIRECTION_OPEN_DRAIN" => Some(Self::OpenDrain),
+            "DIRECTION_PWM" => Some(Self::Pwm),
+            "DIRECTION_PATTERN" => Some(Self::Pattern),
+            _ => None,
+        }
+    }
+}
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum Level {
     #[default]
     Low = 0,
     High = 1,
-    Input = 2,
-    Output = 3,
 }
-impl PinValue {
+impl Level {
     /// String value of the enum field names used in the ProtoBuf definition.
     ///
     /// The values are not transformed in any way and thus are considered stable
     /// (if the ProtoBuf definition does not change) and safe for programmatic use.
     pub fn as_str_name(&self) -> &'static str {
         match self {
-            Self::Low => "LOW",
-            Self::High => "HIGH",
-            Self::Input => "INPUT",
-            Self::Output => "OUTPUT",
+            Self::Low => "LEVEL_LOW",
+            Self::High => "LEVEL_HIGH",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
-            "LOW" => Some(Self::Low),
-            "HIGH" => Some(Self::High),
-            "INPUT" => Some(Self::Input),
-            "OUTPUT" => Some(Self::Output),
+            "LEVEL_LOW" => Some(Self::Low),
+            "LEVEL_HIGH" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+/// Edges reported by a pin
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum Edge {
+    #[default]
+    None = 0,
+    Rising = 1,
+    Falling = 2,
+    Both = 3,
+}
+impl Edge {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::None => "EDGE_NONE",
+            Self::Rising => "EDGE_RISING",
+            Self::Falling => "EDGE_FALLING",
+            Self::Both => "EDGE_BOTH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "EDGE_NONE" => Some(Self::None),
+            "EDGE_RISING" => Some(Self::Rising),
+            "EDGE_FALLING" => Some(Self::Falling),
+            "EDGE_BOTH" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum CaptureTrigger {
+    /// Trigger on the first sample
+    #[default]
+    Now = 0,
+    /// First sample matching the pattern
+    Level = 1,
+    /// First sample matching the pattern after a sample that did not
+    Edge = 2,
+}
+impl CaptureTrigger {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Now => "CAPTURE_TRIGGER_NOW",
+            Self::Level => "CAPTURE_TRIGGER_LEVEL",
+            Self::Edge => "CAPTURE_TRIGGER_EDGE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CAPTURE_TRIGGER_NOW" => Some(Self::Now),
+            "CAPTURE_TRIGGER_LEVEL" => Some(Self::Level),
+            "CAPTURE_TRIGGER_EDGE" => Some(Self::Edge),
+            _ => None,
+        }
+    }
+}
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum CaptureStatus {
+    #[default]
+    Idle = 0,
+    WaitingTrigger = 1,
+    Triggered = 2,
+    Done = 3,
+    /// Stopped by the host, or no trigger before 2^32 - 1 words
+    Aborted = 4,
+}
+impl CaptureStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Idle => "CAPTURE_STATUS_IDLE",
+            Self::WaitingTrigger => "CAPTURE_STATUS_WAITING_TRIGGER",
+            Self::Triggered => "CAPTURE_STATUS_TRIGGERED",
+            Self::Done => "CAPTURE_STATUS_DONE",
+            Self::Aborted => "CAPTURE_STATUS_ABORTED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CAPTURE_STATUS_IDLE" => Some(Self::Idle),
+            "CAPTURE_STATUS_WAITING_TRIGGER" => Some(Self::WaitingTrigger),
+            "CAPTURE_STATUS_TRIGGERED" => Some(Self::Triggered),
+            "CAPTURE_STATUS_DONE" => Some(Self::Done),
+            "CAPTURE_STATUS_ABORTED" => Some(Self::Aborted),
+            _ => None,
+        }
+    }
+}
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum WaitCondition {
+    /// The pins have the expected levels, right away if they already have them
+    #[default]
+    Level = 0,
+    /// The pins change into the expected levels, from levels that did not match
+    Edge = 1,
+}
+impl WaitCondition {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Level => "WAIT_CONDITION_LEVEL",
+            Self::Edge => "WAIT_CONDITION_EDGE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "WAIT_CONDITION_LEVEL" => Some(Self::Level),
+            "WAIT_CONDITION_EDGE" => Some(Self::Edge),
+            _ => None,
+        }
+    }
+}
+/// State a pin is put in when the failsafe trips
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum SafeState {
+    /// The pin is left as it is
+    #[default]
+    None = 0,
+    Input = 1,
+    /// Push-pull output at a level
+    Low = 2,
+    High = 3,
+}
+impl SafeState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::None => "SAFE_STATE_NONE",
+            Self::Input => "SAFE_STATE_INPUT",
+            Self::Low => "SAFE_STATE_LOW",
+            Self::High => "SAFE_STATE_HIGH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SAFE_STATE_NONE" => Some(Self::None),
+            "SAFE_STATE_INPUT" => Some(Self::Input),
+            "SAFE_STATE_LOW" => Some(Self::Low),
+            "SAFE_STATE_HIGH" => Some(Self::High),
             _ => None,
         }
     }
@@ -151,3 +1748,93 @@ impl AnswerType {
         }
     }
 }
+/// Reason of a FAILURE answer
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::femtopb::Enumeration
+)]
+#[repr(i32)]
+#[derive(Default)]
+pub enum ErrorCode {
+    #[default]
+    None = 0,
+    /// Pin number out of range or not handled by the device
+    InvalidPin = 1,
+    /// Pin used by the debug UART (firmware built with uart0_debug)
+    PinReserved = 2,
+    /// Operation not allowed with the current pin direction
+    WrongDirection = 3,
+    InvalidValue = 4,
+    UnknownRequestType = 5,
+    /// Frame received but not a valid request
+    DecodeFailure = 6,
+    /// Pin has no direction configured
+    PinNotConfigured = 7,
+    /// Pin cannot do this operation
+    NotSupported = 8,
+    /// Hardware block already used by another pin
+    ResourceBusy = 9,
+    /// Operation stopped by a later request before its end
+    Cancelled = 10,
+    /// Condition not met before the timeout
+    Timeout = 11,
+    /// Flash not written as expected
+    StorageFailure = 12,
+    /// No pin has the name given in the request
+    UnknownPinName = 13,
+    /// Results of the batch too large for the answer
+    AnswerTooLarge = 14,
+}
+impl ErrorCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::None => "ERROR_CODE_NONE",
+            Self::InvalidPin => "ERROR_CODE_INVALID_PIN",
+            Self::PinReserved => "ERROR_CODE_PIN_RESERVED",
+            Self::WrongDirection => "ERROR_CODE_WRONG_DIRECTION",
+            Self::InvalidValue => "ERROR_CODE_INVALID_VALUE",
+            Self::UnknownRequestType => "ERROR_CODE_UNKNOWN_REQUEST_TYPE",
+            Self::DecodeFailure => "ERROR_CODE_DECODE_FAILURE",
+            Self::PinNotConfigured => "ERROR_CODE_PIN_NOT_CONFIGURED",
+            Self::NotSupported => "ERROR_CODE_NOT_SUPPORTED",
+            Self::ResourceBusy => "ERROR_CODE_RESOURCE_BUSY",
+            Self::Cancelled => "ERROR_CODE_CANCELLED",
+            Self::Timeout => "ERROR_CODE_TIMEOUT",
+            Self::StorageFailure => "ERROR_CODE_STORAGE_FAILURE",
+            Self::UnknownPinName => "ERROR_CODE_UNKNOWN_PIN_NAME",
+            Self::AnswerTooLarge => "ERROR_CODE_ANSWER_TOO_LARGE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ERROR_CODE_NONE" => Some(Self::None),
+            "ERROR_CODE_INVALID_PIN" => Some(Self::InvalidPin),
+            "ERROR_CODE_PIN_RESERVED" => Some(Self::PinReserved),
+            "ERROR_CODE_WRONG_DIRECTION" => Some(Self::WrongDirection),
+            "ERROR_CODE_INVALID_VALUE" => Some(Self::InvalidValue),
+            "ERROR_CODE_UNKNOWN_REQUEST_TYPE" => Some(Self::UnknownRequestType),
+            "ERROR_CODE_DECODE_FAILURE" => Some(Self::DecodeFailure),
+            "ERROR_CODE_PIN_NOT_CONFIGURED" => Some(Self::PinNotConfigured),
+            "ERROR_CODE_NOT_SUPPORTED" => Some(Self::NotSupported),
+            "ERROR_CODE_RESOURCE_BUSY" => Some(Self::ResourceBusy),
+            "ERROR_CODE_CANCELLED" => Some(Self::Cancelled),
+            "ERROR_CODE_TIMEOUT" => Some(Self::Timeout),
+            "ERROR_CODE_STORAGE_FAILURE" => Some(Self::StorageFailure),
+            "ERROR_CODE_UNKNOWN_PIN_NAME" => Some(Self::UnknownPinName),
+            "ERROR_CODE_ANSWER_TOO_LARGE" => Some(Self::AnswerTooLarge),
+            _ => None,
+        }
+    }
+}
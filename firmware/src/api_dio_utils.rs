@@ -1,6 +1,14 @@
 // Print debug support
-use crate::api_dio::{PicohaDioAnswer, PicohaDioRequest};
+use crate::api_dio::batch_operation::Operation;
+use crate::api_dio::picoha_dio_request_v2::Payload;
+use crate::api_dio::{
+    Direction, ErrorCode, GetPinDirectionRequest, GetPinDriveRequest, GetPinPullRequest,
+    GetPinValueRequest, Level, PicohaDioAnswer, PicohaDioRequest, PicohaDioRequestV2, PinValue,
+    PingRequest, RequestType, SetPinDirectionRequest, SetPinDriveRequest, SetPinPullRequest,
+    SetPinValueRequest,
+};
 use core::fmt::{self};
+use femtopb::EnumValue;
 
 // #[cfg(any(feature = "uart0_debug"))]
 // use crate::print_debug_message;
@@ -11,8 +19,67 @@ impl fmt::Debug for PicohaDioRequest<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "PicohaDioRequest {{ r#type: {:?}, pin_num: {:?}, value: {:?} }}",
-            self.r#type, self.pin_num, self.value
+            "PicohaDioRequest {{ request_id: {:?}, r#type: {:?}, pin_num: {:?}, value: {:?}, pull: {:?} }}",
+            self.request_id, self.r#type, self.pin_num, self.value, self.pull
+        )
+    }
+}
+
+impl fmt::Debug for PicohaDioRequestV2<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let payload = match &self.payload {
+            Some(Payload::Ping(_)) => "PING",
+            Some(Payload::SetPinDirection(_)) => "SET_PIN_DIRECTION",
+            Some(Payload::SetPinValue(_)) => "SET_PIN_VALUE",
+            Some(Payload::GetPinDirection(_)) => "GET_PIN_DIRECTION",
+            Some(Payload::GetPinValue(_)) => "GET_PIN_VALUE",
+            Some(Payload::SetPinPull(_)) => "SET_PIN_PULL",
+            Some(Payload::GetPinPull(_)) => "GET_PIN_PULL",
+            Some(Payload::SetPinDrive(_)) => "SET_PIN_DRIVE",
+            Some(Payload::GetPinDrive(_)) => "GET_PIN_DRIVE",
+            Some(Payload::Batch(_)) => "BATCH",
+            Some(Payload::SetPortValue(_)) => "SET_PORT_VALUE",
+            Some(Payload::SetPortDirection(_)) => "SET_PORT_DIRECTION",
+            Some(Payload::GetPortState(_)) => "GET_PORT_STATE",
+            Some(Payload::SubscribeEdges(_)) => "SUBSCRIBE_EDGES",
+            Some(Payload::SetPinDebounce(_)) => "SET_PIN_DEBOUNCE",
+            Some(Payload::GetPinDebounce(_)) => "GET_PIN_DEBOUNCE",
+            Some(Payload::ConfigureEdgeCounter(_)) => "CONFIGURE_EDGE_COUNTER",
+            Some(Payload::ReadEdgeCounter(_)) => "READ_EDGE_COUNTER",
+            Some(Payload::ResetEdgeCounter(_)) => "RESET_EDGE_COUNTER",
+            Some(Payload::MeasureFrequency(_)) => "MEASURE_FREQUENCY",
+            Some(Payload::SetPinPwm(_)) => "SET_PIN_PWM",
+            Some(Payload::SetPinPwmDuty(_)) => "SET_PIN_PWM_DUTY",
+            Some(Payload::Pulse(_)) => "PULSE",
+            Some(Payload::LoadSequence(_)) => "LOAD_SEQUENCE",
+            Some(Payload::StartSequence(_)) => "START_SEQUENCE",
+            Some(Payload::StopSequence(_)) => "STOP_SEQUENCE",
+            Some(Payload::GetSequenceState(_)) => "GET_SEQUENCE_STATE",
+            Some(Payload::StartCapture(_)) => "START_CAPTURE",
+            Some(Payload::StopCapture(_)) => "STOP_CAPTURE",
+            Some(Payload::GetCaptureState(_)) => "GET_CAPTURE_STATE",
+            Some(Payload::ReadCapture(_)) => "READ_CAPTURE",
+            Some(Payload::LoadPattern(_)) => "LOAD_PATTERN",
+            Some(Payload::StartPattern(_)) => "START_PATTERN",
+            Some(Payload::StopPattern(_)) => "STOP_PATTERN",
+            Some(Payload::GetPatternState(_)) => "GET_PATTERN_STATE",
+            Some(Payload::WaitFor(_)) => "WAIT_FOR",
+            Some(Payload::SyncTime(_)) => "SYNC_TIME",
+            Some(Payload::SetPinSafeState(_)) => "SET_PIN_SAFE_STATE",
+            Some(Payload::ConfigureFailsafe(_)) => "CONFIGURE_FAILSAFE",
+            Some(Payload::GetFailsafeState(_)) => "GET_FAILSAFE_STATE",
+            Some(Payload::SaveConfig(_)) => "SAVE_CONFIG",
+            Some(Payload::GetSavedConfig(_)) => "GET_SAVED_CONFIG",
+            Some(Payload::EraseConfig(_)) => "ERASE_CONFIG",
+            Some(Payload::RestoreDefaults(_)) => "RESTORE_DEFAULTS",
+            Some(Payload::SetPinName(_)) => "SET_PIN_NAME",
+            Some(Payload::GetPinNames(_)) => "GET_PIN_NAMES",
+            Some(Payload::_Phantom(_)) | None => "NONE",
+        };
+        write!(
+            f,
+            "PicohaDioRequestV2 {{ request_id: {:?}, pin_name: {:?}, payload: {} }}",
+            self.request_id, self.pin_name, payload
         )
     }
 }
@@ -21,8 +88,346 @@ impl fmt::Debug for PicohaDioAnswer<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "PicohaDioAnswer {{ r#type: {:?}, value: {:?} }}",
-            self.r#type, self.value
+            "PicohaDioAnswer {{ request_id: {:?}, r#type: {:?}, error_code: {:?}, value: {:?}, pull: {:?}, direction: {:?}, level: {:?} }}",
+            self.request_id, self.r#type, self.error_code, self.value, self.pull, self.direction, self.level
         )
     }
 }
+
+/// Convert a protocol v1 request into the matching protocol v2 payload
+///
+/// The v1 PinValue mixes levels and directions, so a v1 request carrying a direction
+/// where a level is expected (or the other way around) is rejected here.
+///
+pub fn request_v1_to_payload<'a>(request: &PicohaDioRequest<'a>) -> Result<Payload<'a>, ErrorCode> {
+    let pin_num = request.pin_num;
+    let r#type = match request.r#type {
+        EnumValue::Known(t) => t,
+        EnumValue::Unknown(_) => return Err(ErrorCode::UnknownRequestType),
+    };
+
+    match r#type {
+        RequestType::Ping => Ok(Payload::Ping(PingRequest::default())),
+        RequestType::SetPinDirection => {
+            let direction = match request.value {
+                EnumValue::Known(PinValue::Input) => Direction::Input,
+                EnumValue::Known(PinValue::Output) => Direction::Output,
+                EnumValue::Known(PinValue::OpenDrain) => Direction::OpenDrain,
+                _ => return Err(ErrorCode::InvalidValue),
+            };
+            Ok(Payload::SetPinDirection(SetPinDirectionRequest {
+                pin_num,
+                direction: EnumValue::Known(direction),
+                ..Default::default()
+            }))
+        }
+        RequestType::SetPinValue => {
+            let level = match request.value {
+                EnumValue::Known(PinValue::Low) => Level::Low,
+                EnumValue::Known(PinValue::High) => Level::High,
+                _ => return Err(ErrorCode::InvalidValue),
+            };
+            Ok(Payload::SetPinValue(SetPinValueRequest {
+                pin_num,
+                level: EnumValue::Known(level),
+                ..Default::default()
+            }))
+        }
+        RequestType::GetPinDirection => Ok(Payload::GetPinDirection(GetPinDirectionRequest {
+            pin_num,
+            ..Default::default()
+        })),
+        RequestType::GetPinValue => Ok(Payload::GetPinValue(GetPinValueRequest {
+            pin_num,
+            ..Default::default()
+        })),
+        RequestType::SetPinPull => Ok(Payload::SetPinPull(SetPinPullRequest {
+            pin_num,
+            pull: request.pull,
+            ..Default::default()
+        })),
+        RequestType::GetPinPull => Ok(Payload::GetPinPull(GetPinPullRequest {
+            pin_num,
+            ..Default::default()
+        })),
+        RequestType::SetPinDrive => Ok(Payload::SetPinDrive(SetPinDriveRequest {
+            pin_num,
+            drive_strength: request.drive_strength,
+            slew_rate: request.slew_rate,
+            ..Default::default()
+        })),
+        RequestType::GetPinDrive => Ok(Payload::GetPinDrive(GetPinDriveRequest {
+            pin_num,
+            ..Default::default()
+        })),
+        // v2 only requests
+        RequestType::Batch
+        | RequestType::SetPortValue
+        | RequestType::SetPortDirection
+        | RequestType::GetPortState
+        | RequestType::SubscribeEdges
+        | RequestType::SetPinDebounce
+        | RequestType::GetPinDebounce
+        | RequestType::ConfigureEdgeCounter
+        | RequestType::ReadEdgeCounter
+        | RequestType::ResetEdgeCounter
+        | RequestType::MeasureFrequency
+        | RequestType::SetPinPwm
+        | RequestType::SetPinPwmDuty
+        | RequestType::Pulse
+        | RequestType::LoadSequence
+        | RequestType::StartSequence
+        | RequestType::StopSequence
+        | RequestType::GetSequenceState
+        | RequestType::StartCapture
+        | RequestType::StopCapture
+        | RequestType::GetCaptureState
+        | RequestType::ReadCapture
+        | RequestType::LoadPattern
+        | RequestType::StartPattern
+        | RequestType::StopPattern
+        | RequestType::GetPatternState
+        | RequestType::WaitFor
+        | RequestType::SyncTime
+        | RequestType::SetPinSafeState
+        | RequestType::ConfigureFailsafe
+        | RequestType::GetFailsafeState
+        | RequestType::SaveConfig
+        | RequestType::GetSavedConfig
+        | RequestType::EraseConfig
+        | RequestType::RestoreDefaults
+        | RequestType::SetPinName
+        | RequestType::GetPinNames => Err(ErrorCode::UnknownRequestType),
+    }
+}
+
+/// Human readable message sent along with an error code
+///
+pub fn error_code_message(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::None => "No error",
+        ErrorCode::InvalidPin => "Pin not available",
+        ErrorCode::PinReserved => "Pin reserved for debug uart",
+        ErrorCode::WrongDirection => "Wrong pin direction",
+        ErrorCode::InvalidValue => "Invalid value",
+        ErrorCode::UnknownRequestType => "Unknown request type",
+        ErrorCode::DecodeFailure => "Unable to decode request",
+        ErrorCode::PinNotConfigured => "Pin not configured",
+        ErrorCode::NotSupported => "Operation not supported by the pin",
+        ErrorCode::ResourceBusy => "Hardware resource used by another pin",
+        ErrorCode::Cancelled => "Operation cancelled by a later request",
+        ErrorCode::Timeout => "Condition not met before the timeout",
+        ErrorCode::StorageFailure => "Flash not written as expected",
+        ErrorCode::UnknownPinName => "No pin has this name",
+        ErrorCode::AnswerTooLarge => "Results of the batch too large for the answer",
+    }
+}
+
+/// Request type of a payload, the v1 RequestType covers every v2 payload
+///
+pub fn payload_request_type(payload: &Payload) -> Option<RequestType> {
+    match payload {
+        Payload::Ping(_) => Some(RequestType::Ping),
+        Payload::SetPinDirection(_) => Some(RequestType::SetPinDirection),
+        Payload::SetPinValue(_) => Some(RequestType::SetPinValue),
+        Payload::GetPinDirection(_) => Some(RequestType::GetPinDirection),
+        Payload::GetPinValue(_) => Some(RequestType::GetPinValue),
+        Payload::SetPinPull(_) => Some(RequestType::SetPinPull),
+        Payload::GetPinPull(_) => Some(RequestType::GetPinPull),
+        Payload::SetPinDrive(_) => Some(RequestType::SetPinDrive),
+        Payload::GetPinDrive(_) => Some(RequestType::GetPinDrive),
+        Payload::Batch(_) => Some(RequestType::Batch),
+        Payload::SetPortValue(_) => Some(RequestType::SetPortValue),
+        Payload::SetPortDirection(_) => Some(RequestType::SetPortDirection),
+        Payload::GetPortState(_) => Some(RequestType::GetPortState),
+        Payload::SubscribeEdges(_) => Some(RequestType::SubscribeEdges),
+        Payload::SetPinDebounce(_) => Some(RequestType::SetPinDebounce),
+        Payload::GetPinDebounce(_) => Some(RequestType::GetPinDebounce),
+        Payload::ConfigureEdgeCounter(_) => Some(RequestType::ConfigureEdgeCounter),
+        Payload::ReadEdgeCounter(_) => Some(RequestType::ReadEdgeCounter),
+        Payload::ResetEdgeCounter(_) => Some(RequestType::ResetEdgeCounter),
+        Payload::MeasureFrequency(_) => Some(RequestType::MeasureFrequency),
+        Payload::SetPinPwm(_) => Some(RequestType::SetPinPwm),
+        Payload::SetPinPwmDuty(_) => Some(RequestType::SetPinPwmDuty),
+        Payload::Pulse(_) => Some(RequestType::Pulse),
+        Payload::LoadSequence(_) => Some(RequestType::LoadSequence),
+        Payload::StartSequence(_) => Some(RequestType::StartSequence),
+        Payload::StopSequence(_) => Some(RequestType::StopSequence),
+        Payload::GetSequenceState(_) => Some(RequestType::GetSequenceState),
+        Payload::StartCapture(_) => Some(RequestType::StartCapture),
+        Payload::StopCapture(_) => Some(RequestType::StopCapture),
+        Payload::GetCaptureState(_) => Some(RequestType::GetCaptureState),
+        Payload::ReadCapture(_) => Some(RequestType::ReadCapture),
+        Payload::LoadPattern(_) => Some(RequestType::LoadPattern),
+        Payload::StartPattern(_) => Some(RequestType::StartPattern),
+        Payload::StopPattern(_) => Some(RequestType::StopPattern),
+        Payload::GetPatternState(_) => Some(RequestType::GetPatternState),
+        Payload::WaitFor(_) => Some(RequestType::WaitFor),
+        Payload::SyncTime(_) => Some(RequestType::SyncTime),
+        Payload::SetPinSafeState(_) => Some(RequestType::SetPinSafeState),
+        Payload::ConfigureFailsafe(_) => Some(RequestType::ConfigureFailsafe),
+        Payload::GetFailsafeState(_) => Some(RequestType::GetFailsafeState),
+        Payload::SaveConfig(_) => Some(RequestType::SaveConfig),
+        Payload::GetSavedConfig(_) => Some(RequestType::GetSavedConfig),
+        Payload::EraseConfig(_) => Some(RequestType::EraseConfig),
+        Payload::RestoreDefaults(_) => Some(RequestType::RestoreDefaults),
+        Payload::SetPinName(_) => Some(RequestType::SetPinName),
+        Payload::GetPinNames(_) => Some(RequestType::GetPinNames),
+        Payload::_Phantom(_) => None,
+    }
+}
+
+/// Pin targeted by a payload, if any
+///
+pub fn payload_pin_num(payload: &Payload) -> Option<u32> {
+    match payload {
+        Payload::SetPinDirection(r) => Some(r.pin_num),
+        Payload::SetPinValue(r) => Some(r.pin_num),
+        Payload::GetPinDirection(r) => Some(r.pin_num),
+        Payload::GetPinValue(r) => Some(r.pin_num),
+        Payload::SetPinPull(r) => Some(r.pin_num),
+        Payload::GetPinPull(r) => Some(r.pin_num),
+        Payload::SetPinDrive(r) => Some(r.pin_num),
+        Payload::GetPinDrive(r) => Some(r.pin_num),
+        Payload::SubscribeEdges(r) => Some(r.pin_num),
+        Payload::SetPinDebounce(r) => Some(r.pin_num),
+        Payload::GetPinDebounce(r) => Some(r.pin_num),
+        Payload::ConfigureEdgeCounter(r) => Some(r.pin_num),
+        Payload::ReadEdgeCounter(r) => Some(r.pin_num),
+        Payload::ResetEdgeCounter(r) => Some(r.pin_num),
+        Payload::MeasureFrequency(r) => Some(r.pin_num),
+        Payload::SetPinPwm(r) => Some(r.pin_num),
+        Payload::SetPinPwmDuty(r) => Some(r.pin_num),
+        Payload::Pulse(r) => Some(r.pin_num),
+        Payload::SetPinSafeState(r) => Some(r.pin_num),
+        Payload::GetSavedConfig(r) => Some(r.pin_num),
+        Payload::SetPinName(r) => Some(r.pin_num),
+        Payload::Ping(_)
+        | Payload::Batch(_)
+        | Payload::SetPortValue(_)
+        | Payload::SetPortDirection(_)
+        | Payload::GetPortState(_)
+        | Payload::LoadSequence(_)
+        | Payload::StartSequence(_)
+        | Payload::StopSequence(_)
+        | Payload::GetSequenceState(_)
+        | Payload::StartCapture(_)
+        | Payload::StopCapture(_)
+        | Payload::GetCaptureState(_)
+        | Payload::ReadCapture(_)
+        | Payload::LoadPattern(_)
+        | Payload::StartPattern(_)
+        | Payload::StopPattern(_)
+        | Payload::GetPatternState(_)
+        | Payload::WaitFor(_)
+        | Payload::SyncTime(_)
+        | Payload::ConfigureFailsafe(_)
+        | Payload::GetFailsafeState(_)
+        | Payload::SaveConfig(_)
+        | Payload::EraseConfig(_)
+        | Payload::RestoreDefaults(_)
+        | Payload::GetPinNames(_)
+        | Payload::_Phantom(_) => None,
+    }
+}
+
+/// Pin number field of a payload, to target the pin given by its name
+///
+pub fn payload_pin_num_mut<'a>(payload: &'a mut Payload<'_>) -> Option<&'a mut u32> {
+    match payload {
+        Payload::SetPinDirection(r) => Some(&mut r.pin_num),
+        Payload::SetPinValue(r) => Some(&mut r.pin_num),
+        Payload::GetPinDirection(r) => Some(&mut r.pin_num),
+        Payload::GetPinValue(r) => Some(&mut r.pin_num),
+        Payload::SetPinPull(r) => Some(&mut r.pin_num),
+        Payload::GetPinPull(r) => Some(&mut r.pin_num),
+        Payload::SetPinDrive(r) => Some(&mut r.pin_num),
+        Payload::GetPinDrive(r) => Some(&mut r.pin_num),
+        Payload::SubscribeEdges(r) => Some(&mut r.pin_num),
+        Payload::SetPinDebounce(r) => Some(&mut r.pin_num),
+        Payload::GetPinDebounce(r) => Some(&mut r.pin_num),
+        Payload::ConfigureEdgeCounter(r) => Some(&mut r.pin_num),
+        Payload::ReadEdgeCounter(r) => Some(&mut r.pin_num),
+        Payload::ResetEdgeCounter(r) => Some(&mut r.pin_num),
+        Payload::MeasureFrequency(r) => Some(&mut r.pin_num),
+        Payload::SetPinPwm(r) => Some(&mut r.pin_num),
+        Payload::SetPinPwmDuty(r) => Some(&mut r.pin_num),
+        Payload::Pulse(r) => Some(&mut r.pin_num),
+        Payload::SetPinSafeState(r) => Some(&mut r.pin_num),
+        Payload::GetSavedConfig(r) => Some(&mut r.pin_num),
+        Payload::SetPinName(r) => Some(&mut r.pin_num),
+        Payload::Ping(_)
+        | Payload::Batch(_)
+        | Payload::SetPortValue(_)
+        | Payload::SetPortDirection(_)
+        | Payload::GetPortState(_)
+        | Payload::LoadSequence(_)
+        | Payload::StartSequence(_)
+        | Payload::StopSequence(_)
+        | Payload::GetSequenceState(_)
+        | Payload::StartCapture(_)
+        | Payload::StopCapture(_)
+        | Payload::GetCaptureState(_)
+        | Payload::ReadCapture(_)
+        | Payload::LoadPattern(_)
+        | Payload::StartPattern(_)
+        | Payload::StopPattern(_)
+        | Payload::GetPatternState(_)
+        | Payload::WaitFor(_)
+        | Payload::SyncTime(_)
+        | Payload::ConfigureFailsafe(_)
+        | Payload::GetFailsafeState(_)
+        | Payload::SaveConfig(_)
+        | Payload::EraseConfig(_)
+        | Payload::RestoreDefaults(_)
+        | Payload::GetPinNames(_)
+        | Payload::_Phantom(_) => None,
+    }
+}
+
+/// Convert a batch operation into the matching v2 payload
+///
+pub fn batch_operation_to_payload(operation: Operation<'_>) -> Option<Payload<'_>> {
+    match operation {
+        Operation::SetPinDirection(r) => Some(Payload::SetPinDirection(r)),
+        Operation::SetPinValue(r) => Some(Payload::SetPinValue(r)),
+        Operation::GetPinDirection(r) => Some(Payload::GetPinDirection(r)),
+        Operation::GetPinValue(r) => Some(Payload::GetPinValue(r)),
+        Operation::SetPinPull(r) => Some(Payload::SetPinPull(r)),
+        Operation::GetPinPull(r) => Some(Payload::GetPinPull(r)),
+        Operation::SetPinDrive(r) => Some(Payload::SetPinDrive(r)),
+        Operation::GetPinDrive(r) => Some(Payload::GetPinDrive(r)),
+        Operation::SetPortValue(r) => Some(Payload::SetPortValue(r)),
+        Operation::SetPortDirection(r) => Some(Payload::SetPortDirection(r)),
+        Operation::GetPortState(r) => Some(Payload::GetPortState(r)),
+        Operation::SubscribeEdges(r) => Some(Payload::SubscribeEdges(r)),
+        Operation::SetPinDebounce(r) => Some(Payload::SetPinDebounce(r)),
+        Operation::GetPinDebounce(r) => Some(Payload::GetPinDebounce(r)),
+        Operation::ConfigureEdgeCounter(r) => Some(Payload::ConfigureEdgeCounter(r)),
+        Operation::ReadEdgeCounter(r) => Some(Payload::ReadEdgeCounter(r)),
+        Operation::ResetEdgeCounter(r) => Some(Payload::ResetEdgeCounter(r)),
+        Operation::SetPinPwm(r) => Some(Payload::SetPinPwm(r)),
+        Operation::SetPinPwmDuty(r) => Some(Payload::SetPinPwmDuty(r)),
+        Operation::Pulse(r) => Some(Payload::Pulse(r)),
+        Operation::LoadSequence(r) => Some(Payload::LoadSequence(r)),
+        Operation::StartSequence(r) => Some(Payload::StartSequence(r)),
+        Operation::StopSequence(r) => Some(Payload::StopSequence(r)),
+        Operation::GetSequenceState(r) => Some(Payload::GetSequenceState(r)),
+        Operation::StartCapture(r) => Some(Payload::StartCapture(r)),
+        Operation::StopCapture(r) => Some(Payload::StopCapture(r)),
+        Operation::GetCaptureState(r) => Some(Payload::GetCaptureState(r)),
+        Operation::LoadPattern(r) => Some(Payload::LoadPattern(r)),
+        Operation::StartPattern(r) => Some(Payload::StartPattern(r)),
+        Operation::StopPattern(r) => Some(Payload::StopPattern(r)),
+        Operation::GetPatternState(r) => Some(Payload::GetPatternState(r)),
+        Operation::SetPinSafeState(r) => Some(Payload::SetPinSafeState(r)),
+        Operation::ConfigureFailsafe(r) => Some(Payload::ConfigureFailsafe(r)),
+        Operation::GetFailsafeState(r) => Some(Payload::GetFailsafeState(r)),
+        Operation::SaveConfig(r) => Some(Payload::SaveConfig(r)),
+        Operation::GetSavedConfig(r) => Some(Payload::GetSavedConfig(r)),
+        Operation::EraseConfig(r) => Some(Payload::EraseConfig(r)),
+        Operation::RestoreDefaults(r) => Some(Payload::RestoreDefaults(r)),
+        Operation::SetPinName(r) => Some(Payload::SetPinName(r)),
+        Operation::_Phantom(_) => None,
+    }
+}
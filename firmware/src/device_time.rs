@@ -0,0 +1,22 @@
+use rp2040_hal::Timer;
+
+/// Timer used as the device time base, the HAL timer counts microseconds since boot
+static mut DEVICE_TIMER: Option<Timer> = None;
+
+/// Store the timer used as the device time base
+///
+pub fn device_time_init(timer: Timer) {
+    unsafe {
+        DEVICE_TIMER = Some(timer);
+    }
+}
+
+/// Microseconds elapsed since boot, 0 before the time base is initialized
+///
+pub fn now_us() -> u64 {
+    // The timer is a copy of a handle on a read-only counter, safe from any context
+    match unsafe { DEVICE_TIMER } {
+        Some(timer) => timer.get_counter().ticks(),
+        None => 0,
+    }
+}
@@ -0,0 +1,470 @@
+use rp2040_hal::pac;
+
+use crate::dio_pio::{self, sample_width};
+
+/// Words of the capture ring buffer, 32 KB
+const RING_WORDS: usize = 8192;
+
+/// Size of the ring buffer in bytes as a power of 2, the DMA wraps its write address on it
+const RING_SIZE_BITS: u8 = 15;
+
+/// Words the DMA may still write once the end of a capture is decided, the RX FIFO and
+/// the polling delay
+const STOP_MARGIN_WORDS: usize = 16;
+
+/// Fastest sample rate, the trigger is searched by the main loop
+const MAX_SAMPLE_RATE_HZ: u32 = 25_000_000;
+
+/// Largest number of words searched for the trigger per poll, requests are not delayed longer
+const MAX_CHECKED_WORDS_PER_POLL: u64 = 1024;
+
+/// Largest chunk of captured data sent in one answer
+const CHUNK_BYTES: usize = 256;
+
+/// PIO0 state machine sampling the pins
+const CAPTURE_SM: usize = 0;
+
+/// DMA channel moving the samples into the ring buffer
+const CAPTURE_DMA_CHANNEL: usize = 0;
+
+/// DMA request of the RX FIFO of the capture state machine
+const DREQ_PIO0_RX0: u8 = 4;
+
+/// Transfers of a capture, the DMA stops after 2^32 - 1 words without trigger
+const CAPTURE_TRANSFERS: u32 = u32::MAX;
+
+/// `in pins, <bit count>`, a bit count of 32 is encoded as 0
+const PIO_IN_PINS: u16 = 0x4000;
+
+/// `jmp 0`
+const PIO_JMP_0: u32 = 0x0000;
+
+/// Ring buffer aligned on its size, as required by the DMA ring mode
+#[repr(C, align(32768))]
+struct RingBuffer([u32; RING_WORDS]);
+
+/// Written by the DMA while capturing, read only once the DMA is stopped or behind it
+static mut RING_BUFFER: RingBuffer = RingBuffer([0; RING_WORDS]);
+
+/// Condition ending the wait of a capture
+#[derive(Clone, Copy, PartialEq)]
+pub enum Trigger {
+    // Trigger on the first sample
+    Now,
+    // First sample matching the pattern
+    Level,
+    // First sample matching the pattern after a sample that did not
+    Edge,
+}
+
+/// Capture requested by the host
+#[derive(Clone, Copy)]
+pub struct CaptureSetting {
+    pub first_pin: usize,
+    pub pin_count: usize,
+    pub sample_rate_hz: u32,
+    pub pre_trigger_samples: u32,
+    // Samples from the trigger one, at least 1
+    pub post_trigger_samples: u32,
+    pub trigger: Trigger,
+    // Bit n is pin first_pin + n
+    pub trigger_mask: u32,
+    pub trigger_value: u32,
+}
+
+/// Progress of a capture
+#[derive(Clone, Copy, PartialEq)]
+pub enum CaptureStatus {
+    Idle,
+    WaitingTrigger,
+    Triggered,
+    Done,
+    Aborted,
+}
+
+/// Capture state reported to the host, the sample fields are valid once done
+pub struct CaptureInfo {
+    pub status: CaptureStatus,
+    pub first_pin: usize,
+    pub pin_count: usize,
+    // Bits per sample in the data, pin_count rounded up to a power of 2
+    pub sample_width: u32,
+    pub sample_rate_hz: f64,
+    // Index of the first valid sample in the first word of the data
+    pub first_sample: u32,
+    pub sample_count: u32,
+    // Index of the trigger sample from the first valid sample
+    pub trigger_sample: u32,
+    pub data_size: usize,
+    // Samples overwritten before being searched for the trigger
+    pub samples_skipped: bool,
+}
+
+/// Take the PIO and DMA blocks out of reset
+///
+pub fn dio_capture_init(_pio: pac::PIO0, resets: &mut pac::RESETS, sys_clock_hz: u32) {
+    resets.reset().modify(|_, w| w.pio0().clear_bit());
+    while resets.reset_done().read().pio0().bit_is_clear() {}
+    dio_pio::dio_pio_init(resets, sys_clock_hz);
+}
+
+/// Words written by the DMA since the capture started
+///
+fn words_written() -> u64 {
+    let dma = unsafe { &*pac::DMA::ptr() };
+    (CAPTURE_TRANSFERS - dma.ch(CAPTURE_DMA_CHANNEL).ch_trans_count().read().bits()) as u64
+}
+
+/// Read a word of the ring buffer, by absolute word index
+///
+fn ring_word(word: u64) -> u32 {
+    unsafe {
+        let ring = core::ptr::addr_of!(RING_BUFFER.0) as *const u32;
+        core::ptr::read_volatile(ring.add(word as usize % RING_WORDS))
+    }
+}
+
+/// Stop the state machine then the DMA channel
+///
+fn stop_hardware() {
+    let pio = unsafe { &*pac::PIO0::ptr() };
+    let dma = unsafe { &*pac::DMA::ptr() };
+
+    pio.ctrl().modify(|r, w| unsafe {
+        w.sm_enable()
+            .bits(r.sm_enable().bits() & !(1 << CAPTURE_SM))
+    });
+    dma.chan_abort()
+        .write(|w| unsafe { w.chan_abort().bits(1 << CAPTURE_DMA_CHANNEL) });
+    while dma.chan_abort().read().chan_abort().bits() != 0 {}
+}
+
+/// Start the state machine sampling the pins and the DMA filling the ring buffer
+///
+fn start_hardware(setting: &CaptureSetting, div_256ths: u32) {
+    let pio = unsafe { &*pac::PIO0::ptr() };
+    let dma = unsafe { &*pac::DMA::ptr() };
+    let sm = pio.sm(CAPTURE_SM);
+
+    // A single instruction program, each execution pushes one sample in the ISR
+    let bit_count = sample_width(setting.pin_count) as u16 & 0x1F;
+    pio.instr_mem(0)
+        .write(|w| unsafe { w.bits((PIO_IN_PINS | bit_count) as u32) });
+    sm.sm_clkdiv().write(|w| unsafe {
+        w.int()
+            .bits((div_256ths >> 8) as u16)
+            .frac()
+            .bits((div_256ths & 0xFF) as u8)
+    });
+    sm.sm_execctrl()
+        .write(|w| unsafe { w.wrap_top().bits(0).wrap_bottom().bits(0) });
+
+    // Joining the FIFOs also drops the samples of a previous capture
+    sm.sm_shiftctrl().write(|w| unsafe {
+        w.autopush()
+            .set_bit()
+            .push_thresh()
+            .bits(0)
+            .in_shiftdir()
+            .set_bit()
+            .fjoin_rx()
+            .set_bit()
+    });
+    sm.sm_pinctrl()
+        .write(|w| unsafe { w.in_base().bits(setting.first_pin as u8) });
+    sm.sm_instr().write(|w| unsafe { w.bits(PIO_JMP_0) });
+    pio.ctrl().modify(|_, w| unsafe {
+        w.sm_restart()
+            .bits(1 << CAPTURE_SM)
+            .clkdiv_restart()
+            .bits(1 << CAPTURE_SM)
+    });
+
+    let ch = dma.ch(CAPTURE_DMA_CHANNEL);
+    ch.ch_read_addr()
+        .write(|w| unsafe { w.bits(pio.rxf(CAPTURE_SM).as_ptr() as u32) });
+    ch.ch_write_addr()
+        .write(|w| unsafe { w.bits(core::ptr::addr_of!(RING_BUFFER.0) as u32) });
+    ch.ch_trans_count()
+        .write(|w| unsafe { w.bits(CAPTURE_TRANSFERS) });
+    ch.ch_ctrl_trig().write(|w| unsafe {
+        w.data_size()
+            .size_word()
+            .incr_read()
+            .clear_bit()
+            .incr_write()
+            .set_bit()
+            .ring_size()
+            .bits(RING_SIZE_BITS)
+            .ring_sel()
+            .set_bit()
+            .chain_to()
+            .bits(CAPTURE_DMA_CHANNEL as u8)
+            .treq_sel()
+            .bits(DREQ_PIO0_RX0)
+            .high_priority()
+            .set_bit()
+            .en()
+            .set_bit()
+    });
+
+    pio.ctrl()
+        .modify(|r, w| unsafe { w.sm_enable().bits(r.sm_enable().bits() | (1 << CAPTURE_SM)) });
+}
+
+/// Logic analyzer sampling a range of pins into the ring buffer
+pub struct DioCapture {
+    status: CaptureStatus,
+    setting: Option<CaptureSetting>,
+    sample_rate_hz: f64,
+    // Words already searched for the trigger
+    checked_words: u64,
+    // Previous sample matched the trigger pattern
+    last_match: bool,
+    samples_skipped: bool,
+    // Absolute index of the trigger sample
+    trigger_at: u64,
+    // Word following the last sample of the capture
+    end_word: u64,
+    // Absolute index of the first valid sample, once done
+    first_valid: u64,
+}
+
+impl DioCapture {
+    /// Create an idle capture
+    ///
+    pub fn new() -> Self {
+        DioCapture {
+            status: CaptureStatus::Idle,
+            setting: None,
+            sample_rate_hz: 0.0,
+            checked_words: 0,
+            last_match: false,
+            samples_skipped: false,
+            trigger_at: 0,
+            end_word: 0,
+            first_valid: 0,
+        }
+    }
+
+    /// Check if the capture is sampling
+    ///
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self.status,
+            CaptureStatus::WaitingTrigger | CaptureStatus::Triggered
+        )
+    }
+
+    /// Start a capture, the data of the previous one is lost
+    ///
+    /// Returns false when the rate, the depths or the trigger pattern cannot be used
+    ///
+    pub fn start(&mut self, setting: CaptureSetting) -> bool {
+        let width = sample_width(setting.pin_count);
+        let samples_per_word = (32 / width) as u64;
+        let pin_bits = match setting.pin_count {
+            32 => u32::MAX,
+            n => (1 << n) - 1,
+        };
+
+        //
+        // The ring buffer holds both depths, plus the words written while stopping
+        let depth = setting.pre_trigger_samples as u64 + setting.post_trigger_samples as u64;
+        let ring_samples = (RING_WORDS - STOP_MARGIN_WORDS) as u64 * samples_per_word;
+        if setting.post_trigger_samples == 0 || depth > ring_samples {
+            return false;
+        }
+        if setting.trigger_mask & !pin_bits != 0
+            || setting.trigger_value & !setting.trigger_mask != 0
+        {
+            return false;
+        }
+
+        if setting.sample_rate_hz > MAX_SAMPLE_RATE_HZ {
+            return false;
+        }
+        let Some((div_256ths, sample_rate_hz)) = dio_pio::clock_divider(setting.sample_rate_hz)
+        else {
+            return false;
+        };
+
+        stop_hardware();
+        *self = DioCapture {
+            status: CaptureStatus::WaitingTrigger,
+            setting: Some(setting),
+            sample_rate_hz,
+            // An edge needs a sample out of the pattern first
+            last_match: true,
+            ..DioCapture::new()
+        };
+        if setting.trigger == Trigger::Now {
+            self.set_triggered(0);
+        }
+        start_hardware(&setting, div_256ths);
+        true
+    }
+
+    /// Abort the capture in progress, a finished capture keeps its data
+    ///
+    pub fn stop(&mut self) {
+        if self.is_running() {
+            stop_hardware();
+            self.status = CaptureStatus::Aborted;
+        }
+    }
+
+    /// Search the new samples for the trigger and end the capture once the post-trigger
+    /// samples are written
+    ///
+    pub fn poll(&mut self) {
+        let Some(setting) = self.setting else {
+            return;
+        };
+        if !self.is_running() {
+            return;
+        }
+        let written = words_written();
+
+        if self.status == CaptureStatus::WaitingTrigger {
+            self.search_trigger(&setting, written);
+        }
+
+        let dma = unsafe { &*pac::DMA::ptr() };
+        let busy = dma
+            .ch(CAPTURE_DMA_CHANNEL)
+            .ch_ctrl_trig()
+            .read()
+            .busy()
+            .bit_is_set();
+        if self.status == CaptureStatus::Triggered && written >= self.end_word {
+            self.finish(&setting);
+        } else if !busy {
+            // All the transfers are done without trigger
+            stop_hardware();
+            self.status = CaptureStatus::Aborted;
+        }
+    }
+
+    /// Search the trigger in the words written since the last search
+    ///
+    fn search_trigger(&mut self, setting: &CaptureSetting, written: u64) {
+        let width = sample_width(setting.pin_count);
+        let samples_per_word = 32 / width;
+        let sample_bits = match width {
+            32 => u32::MAX,
+            w => (1 << w) - 1,
+        };
+
+        // Words about to be overwritten cannot be used anymore
+        let oldest = written.saturating_sub((RING_WORDS - STOP_MARGIN_WORDS) as u64);
+        if self.checked_words < oldest {
+            self.checked_words = oldest;
+            self.samples_skipped = true;
+        }
+
+        let last = written.min(self.checked_words + MAX_CHECKED_WORDS_PER_POLL);
+        for word_index in self.checked_words..last {
+            let word = ring_word(word_index);
+            for n in 0..samples_per_word {
+                let sample = (word >> (n * width)) & sample_bits;
+                let matched = sample & setting.trigger_mask == setting.trigger_value;
+                let triggered = match setting.trigger {
+                    Trigger::Edge => matched && !self.last_match,
+                    _ => matched,
+                };
+                self.last_match = matched;
+                if triggered {
+                    self.set_triggered(word_index * samples_per_word as u64 + n as u64);
+                    return;
+                }
+            }
+        }
+        self.checked_words = last;
+    }
+
+    /// Record the trigger sample and the end of the capture
+    ///
+    fn set_triggered(&mut self, trigger_at: u64) {
+        let Some(setting) = self.setting else {
+            return;
+        };
+        let samples_per_word = (32 / sample_width(setting.pin_count)) as u64;
+        let end_sample = trigger_at + setting.post_trigger_samples as u64;
+        self.trigger_at = trigger_at;
+        self.end_word = end_sample.div_ceil(samples_per_word);
+        self.status = CaptureStatus::Triggered;
+    }
+
+    /// Stop the hardware and locate the samples kept in the ring buffer
+    ///
+    fn finish(&mut self, setting: &CaptureSetting) {
+        stop_hardware();
+        let samples_per_word = (32 / sample_width(setting.pin_count)) as u64;
+
+        // A late poll lets the DMA overwrite the oldest pre-trigger samples
+        let oldest_word = words_written().saturating_sub(RING_WORDS as u64);
+        self.first_valid = self
+            .trigger_at
+            .saturating_sub(setting.pre_trigger_samples as u64)
+            .max(oldest_word * samples_per_word);
+        self.status = CaptureStatus::Done;
+    }
+
+    /// Get the state of the capture
+    ///
+    pub fn info(&self) -> CaptureInfo {
+        let mut info = CaptureInfo {
+            status: self.status,
+            first_pin: 0,
+            pin_count: 0,
+            sample_width: 0,
+            sample_rate_hz: self.sample_rate_hz,
+            first_sample: 0,
+            sample_count: 0,
+            trigger_sample: 0,
+            data_size: 0,
+            samples_skipped: self.samples_skipped,
+        };
+        if let Some(setting) = self.setting {
+            info.first_pin = setting.first_pin;
+            info.pin_count = setting.pin_count;
+            info.sample_width = sample_width(setting.pin_count);
+            if self.status == CaptureStatus::Done {
+                let samples_per_word = (32 / info.sample_width) as u64;
+                let first_word = self.first_valid / samples_per_word;
+                let end_sample = self.trigger_at + setting.post_trigger_samples as u64;
+                info.first_sample = (self.first_valid - first_word * samples_per_word) as u32;
+                info.sample_count = (end_sample - self.first_valid) as u32;
+                info.trigger_sample = (self.trigger_at - self.first_valid) as u32;
+                info.data_size = (self.end_word - first_word) as usize * 4;
+            }
+        }
+        info
+    }
+
+    /// Chunk of the captured data from a byte offset, None when the capture is not done or
+    /// the offset is past the data
+    ///
+    /// Chunks stop at the end of the ring buffer, the next one continues from its start
+    ///
+    pub fn chunk(&self, offset: usize) -> Option<&'static [u8]> {
+        let info = self.info();
+        if self.status != CaptureStatus::Done || offset % 4 != 0 || offset >= info.data_size {
+            return None;
+        }
+
+        let samples_per_word = (32 / info.sample_width) as u64;
+        let word = (self.first_valid / samples_per_word) as usize + offset / 4;
+        let ring_index = word % RING_WORDS;
+        let len = (info.data_size - offset)
+            .min(CHUNK_BYTES)
+            .min((RING_WORDS - ring_index) * 4);
+
+        // The DMA is stopped, the buffer does not change until the next capture
+        Some(unsafe {
+            let ring = core::ptr::addr_of!(RING_BUFFER.0) as *const u8;
+            core::slice::from_raw_parts(ring.add(ring_index * 4), len)
+        })
+    }
+}
@@ -0,0 +1,332 @@
+use rp2040_hal::gpio::{DynPullType, OutputDriveStrength, OutputSlewRate};
+
+use crate::dio_failsafe::SafeState;
+use crate::BANK0_PINS;
+
+/// Start of the flash in the XIP address space
+const XIP_BASE: u32 = 0x1000_0000;
+
+/// Size of the flash of the Pico
+const FLASH_SIZE: u32 = 2048 * 1024;
+
+/// Smallest erasable block of the flash
+const SECTOR_SIZE: u32 = 4096;
+
+/// Smallest programmable block of the flash
+const PAGE_SIZE: usize = 256;
+
+/// Offset of the configuration in the flash, the last sector, kept out of the firmware by
+/// memory.x
+const CONFIG_OFFSET: u32 = FLASH_SIZE - SECTOR_SIZE;
+
+/// Offset of the pin names in the flash, the sector before the configuration
+const NAMES_OFFSET: u32 = CONFIG_OFFSET - SECTOR_SIZE;
+
+/// Marks a sector holding a configuration, "PDIO"
+const CONFIG_MAGIC: u32 = 0x4F49_4450;
+
+/// Layout of the stored configuration, a configuration of another version is ignored
+pub const CONFIG_VERSION: u8 = 1;
+
+/// Marks a sector holding pin names, "PDNM"
+const NAMES_MAGIC: u32 = 0x4D4E_4450;
+
+/// Layout of the stored pin names
+const NAMES_VERSION: u8 = 1;
+
+/// Longest pin name, in bytes
+pub const MAX_NAME_LEN: usize = 16;
+
+/// Bytes covered by the CRC of the names, each name is padded with zeros
+const NAMES_SIZE: usize = HEADER_SIZE + BANK0_PINS * MAX_NAME_LEN;
+
+/// Pages programmed for the names and their CRC
+const NAMES_PAGES: usize = (NAMES_SIZE + 4).div_ceil(PAGE_SIZE);
+
+/// Magic, version, pin count and 2 reserved bytes
+const HEADER_SIZE: usize = 8;
+
+/// Mode, level, pull, drive strength, slew rate and safe state
+const PIN_RECORD_SIZE: usize = 6;
+
+/// Bytes covered by the CRC, followed by the CRC itself
+const CONFIG_SIZE: usize = HEADER_SIZE + BANK0_PINS * PIN_RECORD_SIZE;
+
+/// How a pin drives its line
+#[derive(Clone, Copy, PartialEq)]
+pub enum PinMode {
+    Input,
+    Output,
+    OpenDrain,
+}
+
+/// Configuration of a pin applied at power-on
+#[derive(Clone, Copy)]
+pub struct PinConfig {
+    pub mode: PinMode,
+    // Output level, released line for open-drain pins
+    pub high: bool,
+    pub pull: DynPullType,
+    pub strength: OutputDriveStrength,
+    pub slew_rate: OutputSlewRate,
+    pub safe_state: SafeState,
+}
+
+impl PinConfig {
+    /// Configuration of a pin without saved configuration
+    pub const FACTORY: PinConfig = PinConfig {
+        mode: PinMode::Input,
+        high: false,
+        pull: DynPullType::Down,
+        strength: OutputDriveStrength::FourMilliAmps,
+        slew_rate: OutputSlewRate::Slow,
+        safe_state: SafeState::None,
+    };
+
+    /// Write the pin record
+    ///
+    fn encode(&self, record: &mut [u8]) {
+        record[0] = match self.mode {
+            PinMode::Input => 0,
+            PinMode::Output => 1,
+            PinMode::OpenDrain => 2,
+        };
+        record[1] = self.high as u8;
+        record[2] = match self.pull {
+            DynPullType::None => 0,
+            DynPullType::Up => 1,
+            DynPullType::Down => 2,
+            DynPullType::BusKeep => 3,
+        };
+        record[3] = match self.strength {
+            OutputDriveStrength::TwoMilliAmps => 0,
+            OutputDriveStrength::FourMilliAmps => 1,
+            OutputDriveStrength::EightMilliAmps => 2,
+            OutputDriveStrength::TwelveMilliAmps => 3,
+        };
+        record[4] = match self.slew_rate {
+            OutputSlewRate::Slow => 0,
+            OutputSlewRate::Fast => 1,
+        };
+        record[5] = match self.safe_state {
+            SafeState::None => 0,
+            SafeState::Input => 1,
+            SafeState::Low => 2,
+            SafeState::High => 3,
+        };
+    }
+
+    /// Read a pin record, None when a value is out of range
+    ///
+    fn decode(record: &[u8]) -> Option<PinConfig> {
+        Some(PinConfig {
+            mode: match record[0] {
+                0 => PinMode::Input,
+                1 => PinMode::Output,
+                2 => PinMode::OpenDrain,
+                _ => return None,
+            },
+            high: match record[1] {
+                0 => false,
+                1 => true,
+                _ => return None,
+            },
+            pull: match record[2] {
+                0 => DynPullType::None,
+                1 => DynPullType::Up,
+                2 => DynPullType::Down,
+                3 => DynPullType::BusKeep,
+                _ => return None,
+            },
+            strength: match record[3] {
+                0 => OutputDriveStrength::TwoMilliAmps,
+                1 => OutputDriveStrength::FourMilliAmps,
+                2 => OutputDriveStrength::EightMilliAmps,
+                3 => OutputDriveStrength::TwelveMilliAmps,
+                _ => return None,
+            },
+            slew_rate: match record[4] {
+                0 => OutputSlewRate::Slow,
+                1 => OutputSlewRate::Fast,
+                _ => return None,
+            },
+            safe_state: match record[5] {
+                0 => SafeState::None,
+                1 => SafeState::Input,
+                2 => SafeState::Low,
+                3 => SafeState::High,
+                _ => return None,
+            },
+        })
+    }
+}
+
+/// Configuration of every pin, indexed by GPIO number
+pub type PinConfigs = [PinConfig; BANK0_PINS];
+
+/// Configuration of every pin without saved configuration
+pub const FACTORY_CONFIGS: PinConfigs = [PinConfig::FACTORY; BANK0_PINS];
+
+/// Name of a pin, empty when the pin has no name
+pub type PinName = heapless::String<MAX_NAME_LEN>;
+
+/// Check that a name can be stored and used in requests: letters, digits, '_', '-' and '.'
+///
+pub fn is_valid_name(name: &str) -> bool {
+    name.len() <= MAX_NAME_LEN
+        && name
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-' || c == b'.')
+}
+
+/// CRC-32 of the IEEE 802.3, as computed by zlib
+///
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xEDB8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+/// Content of the configuration sector, read through the XIP cache
+///
+fn stored_page() -> &'static [u8; PAGE_SIZE] {
+    unsafe { &*((XIP_BASE + CONFIG_OFFSET) as *const [u8; PAGE_SIZE]) }
+}
+
+/// Header of a stored block
+///
+fn write_header(block: &mut [u8], magic: u32, version: u8) {
+    block[0..4].copy_from_slice(&magic.to_le_bytes());
+    block[4] = version;
+    block[5] = BANK0_PINS as u8;
+    block[6] = 0;
+    block[7] = 0;
+}
+
+/// Check the header and the CRC following the data of a stored block
+///
+fn is_valid_block(block: &[u8], size: usize, magic: u32, version: u8) -> bool {
+    let stored_magic = u32::from_le_bytes([block[0], block[1], block[2], block[3]]);
+    if stored_magic != magic || block[4] != version || block[5] as usize != BANK0_PINS {
+        return false;
+    }
+    let crc = &block[size..size + 4];
+    u32::from_le_bytes([crc[0], crc[1], crc[2], crc[3]]) == crc32(&block[..size])
+}
+
+/// Read the saved configuration
+///
+/// Returns None when the sector is erased, corrupt, or holds another version
+///
+pub fn load() -> Option<PinConfigs> {
+    let page = stored_page();
+    if !is_valid_block(page, CONFIG_SIZE, CONFIG_MAGIC, CONFIG_VERSION) {
+        return None;
+    }
+
+    let mut configs = FACTORY_CONFIGS;
+    for (n, record) in page[HEADER_SIZE..CONFIG_SIZE]
+        .chunks_exact(PIN_RECORD_SIZE)
+        .enumerate()
+    {
+        configs[n] = PinConfig::decode(record)?;
+    }
+    Some(configs)
+}
+
+/// Write a configuration in the flash, then check it reads back
+///
+/// The interrupts are disabled while the flash is written, for some tens of milliseconds
+///
+pub fn save(configs: &PinConfigs) -> bool {
+    let mut page = [0xFFu8; PAGE_SIZE];
+    write_header(&mut page, CONFIG_MAGIC, CONFIG_VERSION);
+    for (config, record) in configs
+        .iter()
+        .zip(page[HEADER_SIZE..CONFIG_SIZE].chunks_exact_mut(PIN_RECORD_SIZE))
+    {
+        config.encode(record);
+    }
+    let crc = crc32(&page[..CONFIG_SIZE]);
+    page[CONFIG_SIZE..CONFIG_SIZE + 4].copy_from_slice(&crc.to_le_bytes());
+
+    // Nothing may run from the flash while it is written
+    cortex_m::interrupt::free(|_| unsafe {
+        rp2040_flash::flash::flash_range_erase(CONFIG_OFFSET, SECTOR_SIZE, true);
+        rp2040_flash::flash::flash_range_program(CONFIG_OFFSET, &page, true);
+    });
+    stored_page() == &page
+}
+
+/// Erase the saved configuration, the pins start with their factory configuration again
+///
+pub fn erase() -> bool {
+    cortex_m::interrupt::free(|_| unsafe {
+        rp2040_flash::flash::flash_range_erase(CONFIG_OFFSET, SECTOR_SIZE, true);
+    });
+    stored_page().iter().all(|byte| *byte == 0xFF)
+}
+
+/// Content of the names sector, read through the XIP cache
+///
+fn stored_names() -> &'static [u8; NAMES_PAGES * PAGE_SIZE] {
+    unsafe { &*((XIP_BASE + NAMES_OFFSET) as *const [u8; NAMES_PAGES * PAGE_SIZE]) }
+}
+
+/// Read the saved pin names, indexed by GPIO number
+///
+/// Returns false and leaves the names empty when the sector is erased or corrupt
+///
+pub fn load_names(names: &mut [PinName]) -> bool {
+    let block = stored_names();
+    if !is_valid_block(block, NAMES_SIZE, NAMES_MAGIC, NAMES_VERSION) {
+        return false;
+    }
+    for (name, stored) in names
+        .iter_mut()
+        .zip(block[HEADER_SIZE..NAMES_SIZE].chunks_exact(MAX_NAME_LEN))
+    {
+        let len = stored.iter().position(|c| *c == 0).unwrap_or(MAX_NAME_LEN);
+        name.clear();
+        // A name that cannot be used in requests is dropped
+        if let Ok(text) = core::str::from_utf8(&stored[..len]) {
+            if is_valid_name(text) {
+                name.push_str(text).ok();
+            }
+        }
+    }
+    true
+}
+
+/// Write the pin names in the flash, then check they read back
+///
+/// The interrupts are disabled while the flash is written, for some tens of milliseconds
+///
+pub fn save_names(names: &[PinName]) -> bool {
+    let mut block = [0xFFu8; NAMES_PAGES * PAGE_SIZE];
+    write_header(&mut block, NAMES_MAGIC, NAMES_VERSION);
+    block[HEADER_SIZE..NAMES_SIZE].fill(0);
+    for (name, stored) in names
+        .iter()
+        .zip(block[HEADER_SIZE..NAMES_SIZE].chunks_exact_mut(MAX_NAME_LEN))
+    {
+        stored[..name.len()].copy_from_slice(name.as_bytes());
+    }
+    let crc = crc32(&block[..NAMES_SIZE]);
+    block[NAMES_SIZE..NAMES_SIZE + 4].copy_from_slice(&crc.to_le_bytes());
+
+    // Nothing may run from the flash while it is written
+    cortex_m::interrupt::free(|_| unsafe {
+        rp2040_flash::flash::flash_range_erase(NAMES_OFFSET, SECTOR_SIZE, true);
+        rp2040_flash::flash::flash_range_program(NAMES_OFFSET, &block, true);
+    });
+    stored_names() == &block
+}
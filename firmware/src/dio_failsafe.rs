@@ -0,0 +1,151 @@
+use crate::BANK0_PINS;
+
+/// State a pin is put in when the failsafe trips
+#[derive(Clone, Copy, PartialEq)]
+pub enum SafeState {
+    // The pin is left as it is
+    None,
+    Input,
+    Low,
+    High,
+}
+
+/// Heartbeat watchdog putting the pins in a safe state when the host goes silent
+pub struct Failsafe {
+    safe_states: [SafeState; BANK0_PINS],
+    // 0 when disabled
+    heartbeat_timeout_ms: u32,
+    // Device time of the last valid frame
+    last_frame_at: u64,
+    // The safe states were applied for the current silence
+    applied: bool,
+    // Latched until cleared by the host
+    tripped: bool,
+    tripped_at_us: u64,
+    // The host closed its session, latched until cleared by the host
+    session_closed: bool,
+    session_closed_at_us: u64,
+}
+
+impl Failsafe {
+    /// Create a disabled failsafe without safe states
+    ///
+    pub fn new() -> Self {
+        Failsafe {
+            safe_states: [SafeState::None; BANK0_PINS],
+            heartbeat_timeout_ms: 0,
+            last_frame_at: 0,
+            applied: false,
+            tripped: false,
+            tripped_at_us: 0,
+            session_closed: false,
+            session_closed_at_us: 0,
+        }
+    }
+
+    /// Set the state of a pin when the failsafe trips
+    ///
+    pub fn set_safe_state(&mut self, pin_num: usize, state: SafeState) {
+        self.safe_states[pin_num] = state;
+    }
+
+    /// State of a pin when the failsafe trips
+    ///
+    pub fn safe_state(&self, pin_num: usize) -> SafeState {
+        self.safe_states[pin_num]
+    }
+
+    /// Pins with a safe state, bit n is GPIO n
+    ///
+    pub fn safe_pins(&self) -> u32 {
+        self.safe_states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| **state != SafeState::None)
+            .fold(0, |mask, (n, _)| mask | (1 << n))
+    }
+
+    /// Set the heartbeat timeout, 0 disables the failsafe, the silence starts now
+    ///
+    pub fn configure(&mut self, heartbeat_timeout_ms: u32, now: u64) {
+        self.heartbeat_timeout_ms = heartbeat_timeout_ms;
+        self.frame_received(now);
+    }
+
+    /// Heartbeat timeout, 0 when disabled
+    ///
+    pub fn heartbeat_timeout_ms(&self) -> u32 {
+        self.heartbeat_timeout_ms
+    }
+
+    /// Restart the silence on a valid frame from the host
+    ///
+    pub fn frame_received(&mut self, now: u64) {
+        self.last_frame_at = now;
+        self.applied = false;
+    }
+
+    /// Check if the host has been silent longer than the heartbeat timeout
+    ///
+    /// Returns true once per silence, when the safe states must be applied
+    ///
+    pub fn check(&mut self, now: u64) -> bool {
+        if self.heartbeat_timeout_ms == 0 || self.applied {
+            return false;
+        }
+        if now - self.last_frame_at < self.heartbeat_timeout_ms as u64 * 1000 {
+            return false;
+        }
+        self.trip(now);
+        true
+    }
+
+    /// Latch the tripped flag, the safe states are applied for the current silence
+    ///
+    pub fn trip(&mut self, now: u64) {
+        self.applied = true;
+        self.tripped = true;
+        self.tripped_at_us = now;
+    }
+
+    /// Check if the failsafe tripped since the flag was last cleared
+    ///
+    pub fn tripped(&self) -> bool {
+        self.tripped
+    }
+
+    /// Device time of the last trip
+    ///
+    pub fn tripped_at_us(&self) -> u64 {
+        self.tripped_at_us
+    }
+
+    /// Latch the session closed flag, the safe states are applied for the current silence
+    ///
+    /// A closed session is not a trip, the host did not go silent
+    ///
+    pub fn close_session(&mut self, now: u64) {
+        self.applied = true;
+        self.session_closed = true;
+        self.session_closed_at_us = now;
+    }
+
+    /// Check if the host closed its session since the flag was last cleared
+    ///
+    pub fn session_closed(&self) -> bool {
+        self.session_closed
+    }
+
+    /// Device time of the last session close
+    ///
+    pub fn session_closed_at_us(&self) -> u64 {
+        self.session_closed_at_us
+    }
+
+    /// Clear the tripped and session closed flags, the device times are kept
+    ///
+    pub fn clear(&mut self) {
+        self.tripped = false;
+        self.session_closed = false;
+    }
+}
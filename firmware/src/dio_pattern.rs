@@ -0,0 +1,326 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use rp2040_hal::pac;
+
+use crate::dio_pio::{self, sample_width};
+
+/// Words of the pattern buffer, 16 KB
+const PATTERN_WORDS: usize = 4096;
+
+/// PIO1 state machine writing the samples on the pins
+const PATTERN_SM: usize = 0;
+
+/// DMA channel moving the words of the pattern into the TX FIFO
+const DATA_DMA_CHANNEL: usize = 1;
+
+/// DMA channel restarting the data channel at the start of the pattern, when looping
+const LOOP_DMA_CHANNEL: usize = 2;
+
+/// DMA request of the TX FIFO of the pattern state machine
+const DREQ_PIO1_TX0: u8 = 8;
+
+/// DMA request value of a channel transferring without pacing
+const TREQ_PERMANENT: u8 = 0x3F;
+
+/// `out pins, <bit count>`, a bit count of 32 is encoded as 0
+const PIO_OUT_PINS: u16 = 0x6000;
+
+/// `jmp 0`
+const PIO_JMP_0: u32 = 0x0000;
+
+/// Read by the DMA only while playing, written only while stopped
+static mut PATTERN_BUFFER: [u32; PATTERN_WORDS] = [0; PATTERN_WORDS];
+
+/// Address of the pattern buffer, copied by the loop channel into the data channel
+static PATTERN_START: AtomicU32 = AtomicU32::new(0);
+
+/// Pattern state reported to the host
+pub struct PatternInfo {
+    pub first_pin: usize,
+    pub pin_count: usize,
+    // Bits per sample in the data, pin_count rounded up to a power of 2
+    pub sample_width: u32,
+    pub sample_count: u32,
+    pub running: bool,
+    pub looping: bool,
+    pub sample_rate_hz: f64,
+}
+
+/// Take the PIO and DMA blocks out of reset
+///
+pub fn dio_pattern_init(_pio: pac::PIO1, resets: &mut pac::RESETS, sys_clock_hz: u32) {
+    resets.reset().modify(|_, w| w.pio1().clear_bit());
+    while resets.reset_done().read().pio1().bit_is_clear() {}
+    dio_pio::dio_pio_init(resets, sys_clock_hz);
+}
+
+/// Stop the DMA channels then the state machine, the pins keep the last sample written
+///
+fn stop_hardware() {
+    let pio = unsafe { &*pac::PIO1::ptr() };
+    let dma = unsafe { &*pac::DMA::ptr() };
+
+    dma.chan_abort().write(|w| unsafe {
+        w.chan_abort()
+            .bits((1 << DATA_DMA_CHANNEL) | (1 << LOOP_DMA_CHANNEL))
+    });
+    while dma.chan_abort().read().chan_abort().bits() != 0 {}
+    pio.ctrl().modify(|r, w| unsafe {
+        w.sm_enable()
+            .bits(r.sm_enable().bits() & !(1 << PATTERN_SM))
+    });
+}
+
+/// Check if the state machine wrote the last sample and waits for more data
+///
+fn hardware_done() -> bool {
+    let pio = unsafe { &*pac::PIO1::ptr() };
+    let dma = unsafe { &*pac::DMA::ptr() };
+    let busy = dma
+        .ch(DATA_DMA_CHANNEL)
+        .ch_ctrl_trig()
+        .read()
+        .busy()
+        .bit_is_set();
+    let stalled = pio.fdebug().read().txstall().bits() & (1 << PATTERN_SM) != 0;
+    !busy && stalled
+}
+
+/// Start the state machine writing the pins and the DMA feeding it the pattern
+///
+fn start_hardware(
+    first_pin: usize,
+    pin_count: usize,
+    words: usize,
+    div_256ths: u32,
+    looping: bool,
+) {
+    let pio = unsafe { &*pac::PIO1::ptr() };
+    let dma = unsafe { &*pac::DMA::ptr() };
+    let sm = pio.sm(PATTERN_SM);
+
+    // A single instruction program, each execution writes one sample from the OSR
+    let bit_count = sample_width(pin_count) as u16 & 0x1F;
+    pio.instr_mem(0)
+        .write(|w| unsafe { w.bits((PIO_OUT_PINS | bit_count) as u32) });
+    sm.sm_clkdiv().write(|w| unsafe {
+        w.int()
+            .bits((div_256ths >> 8) as u16)
+            .frac()
+            .bits((div_256ths & 0xFF) as u8)
+    });
+    sm.sm_execctrl()
+        .write(|w| unsafe { w.wrap_top().bits(0).wrap_bottom().bits(0) });
+
+    // Joining the FIFOs also drops the words of a previous playback
+    sm.sm_shiftctrl().write(|w| unsafe {
+        w.autopull()
+            .set_bit()
+            .pull_thresh()
+            .bits(0)
+            .out_shiftdir()
+            .set_bit()
+            .fjoin_tx()
+            .set_bit()
+    });
+    sm.sm_pinctrl().write(|w| unsafe {
+        w.out_base()
+            .bits(first_pin as u8)
+            .out_count()
+            .bits(pin_count as u8)
+    });
+    sm.sm_instr().write(|w| unsafe { w.bits(PIO_JMP_0) });
+    pio.fdebug()
+        .write(|w| unsafe { w.txstall().bits(1 << PATTERN_SM) });
+    pio.ctrl().modify(|_, w| unsafe {
+        w.sm_restart()
+            .bits(1 << PATTERN_SM)
+            .clkdiv_restart()
+            .bits(1 << PATTERN_SM)
+    });
+
+    //
+    // The loop channel writes the start address in the trigger register of the data
+    // channel, which then transfers the pattern again with its reloaded count
+    let start = core::ptr::addr_of!(PATTERN_BUFFER) as u32;
+    PATTERN_START.store(start, Ordering::Relaxed);
+    let data = dma.ch(DATA_DMA_CHANNEL);
+    let restart = dma.ch(LOOP_DMA_CHANNEL);
+    restart
+        .ch_read_addr()
+        .write(|w| unsafe { w.bits(PATTERN_START.as_ptr() as u32) });
+    restart
+        .ch_write_addr()
+        .write(|w| unsafe { w.bits(data.ch_al3_read_addr_trig().as_ptr() as u32) });
+    restart.ch_trans_count().write(|w| unsafe { w.bits(1) });
+    // The alias of the control register does not trigger the channel, it only has raw
+    // bits: enabled, word transfers, chained to itself and not paced
+    restart.ch_al1_ctrl().write(|w| unsafe {
+        w.bits(1 | (2 << 2) | ((LOOP_DMA_CHANNEL as u32) << 11) | ((TREQ_PERMANENT as u32) << 15))
+    });
+
+    // A channel chained to itself does not chain
+    let chain_to = match looping {
+        true => LOOP_DMA_CHANNEL,
+        false => DATA_DMA_CHANNEL,
+    };
+    data.ch_read_addr().write(|w| unsafe { w.bits(start) });
+    data.ch_write_addr()
+        .write(|w| unsafe { w.bits(pio.txf(PATTERN_SM).as_ptr() as u32) });
+    data.ch_trans_count()
+        .write(|w| unsafe { w.bits(words as u32) });
+    data.ch_ctrl_trig().write(|w| unsafe {
+        w.data_size()
+            .size_word()
+            .incr_read()
+            .set_bit()
+            .incr_write()
+            .clear_bit()
+            .chain_to()
+            .bits(chain_to as u8)
+            .treq_sel()
+            .bits(DREQ_PIO1_TX0)
+            .high_priority()
+            .set_bit()
+            .en()
+            .set_bit()
+    });
+
+    pio.ctrl()
+        .modify(|r, w| unsafe { w.sm_enable().bits(r.sm_enable().bits() | (1 << PATTERN_SM)) });
+}
+
+/// Pattern generator replaying a loaded buffer on a range of pins
+pub struct DioPattern {
+    first_pin: usize,
+    pin_count: usize,
+    // Words loaded in the pattern buffer
+    words: usize,
+    sample_rate_hz: f64,
+    looping: bool,
+    running: bool,
+}
+
+impl DioPattern {
+    /// Create a generator without pattern
+    ///
+    pub fn new() -> Self {
+        DioPattern {
+            first_pin: 0,
+            pin_count: 0,
+            words: 0,
+            sample_rate_hz: 0.0,
+            looping: false,
+            running: false,
+        }
+    }
+
+    /// Check if the pattern is playing
+    ///
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Pins written by the pattern, bit n is GPIO n
+    ///
+    pub fn pins(&self) -> u32 {
+        (((1u64 << self.pin_count) - 1) << self.first_pin) as u32
+    }
+
+    /// Load the samples of a range of pins, after the samples already loaded when appending
+    ///
+    /// Returns false when the data is not made of whole words, does not fit, or is
+    /// appended to a pattern of another range. Nothing is loaded while playing.
+    ///
+    pub fn load(&mut self, first_pin: usize, pin_count: usize, data: &[u8], append: bool) -> bool {
+        if self.running || data.len() % 4 != 0 {
+            return false;
+        }
+        let words = match append {
+            true if (first_pin, pin_count) != (self.first_pin, self.pin_count) => return false,
+            true => self.words,
+            false => 0,
+        };
+        if words + data.len() / 4 > PATTERN_WORDS {
+            return false;
+        }
+
+        // The DMA is stopped, the buffer is not read until the next playback
+        let buffer = unsafe { &mut *core::ptr::addr_of_mut!(PATTERN_BUFFER) };
+        for (n, bytes) in data.chunks_exact(4).enumerate() {
+            buffer[words + n] = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        self.first_pin = first_pin;
+        self.pin_count = pin_count;
+        self.words = words + data.len() / 4;
+        true
+    }
+
+    /// Start playing the loaded pattern, once or looping, the first sample is written right away
+    ///
+    /// Returns false when no sample is loaded or the rate cannot be used
+    ///
+    pub fn start(&mut self, sample_rate_hz: u32, looping: bool) -> bool {
+        if self.running || self.words == 0 {
+            return false;
+        }
+        let Some((div_256ths, real_rate_hz)) = dio_pio::clock_divider(sample_rate_hz) else {
+            return false;
+        };
+
+        self.sample_rate_hz = real_rate_hz;
+        self.looping = looping;
+        self.running = true;
+        start_hardware(
+            self.first_pin,
+            self.pin_count,
+            self.words,
+            div_256ths,
+            looping,
+        );
+        true
+    }
+
+    /// Stop playing, the pins keep the last sample written
+    ///
+    pub fn stop(&mut self) {
+        if self.running {
+            stop_hardware();
+            self.running = false;
+        }
+    }
+
+    /// Check if a playback played once ended, and stop the hardware then
+    ///
+    /// Returns true when the playback just ended
+    ///
+    pub fn poll(&mut self) -> bool {
+        if !self.running || self.looping || !hardware_done() {
+            return false;
+        }
+        self.stop();
+        true
+    }
+
+    /// Get the state of the pattern
+    ///
+    pub fn info(&self) -> PatternInfo {
+        let sample_width = match self.pin_count {
+            0 => 0,
+            n => sample_width(n),
+        };
+        let sample_count = match sample_width {
+            0 => 0,
+            w => self.words as u32 * (32 / w),
+        };
+        PatternInfo {
+            first_pin: self.first_pin,
+            pin_count: self.pin_count,
+            sample_width,
+            sample_count,
+            running: self.running,
+            looping: self.looping,
+            sample_rate_hz: self.sample_rate_hz,
+        }
+    }
+}
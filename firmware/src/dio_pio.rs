@@ -0,0 +1,40 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use rp2040_hal::pac;
+
+/// System clock frequency, the state machines run from it
+static SYS_CLOCK_HZ: AtomicU32 = AtomicU32::new(0);
+
+/// Take the DMA block out of reset, shared by the capture and the pattern generator
+///
+pub fn dio_pio_init(resets: &mut pac::RESETS, sys_clock_hz: u32) {
+    resets.reset().modify(|_, w| w.dma().clear_bit());
+    while resets.reset_done().read().dma().bit_is_clear() {}
+    SYS_CLOCK_HZ.store(sys_clock_hz, Ordering::Relaxed);
+}
+
+/// Bits per sample for a number of pins, the samples of a word must not overlap 2 words
+///
+pub fn sample_width(pin_count: usize) -> u32 {
+    (pin_count as u32).next_power_of_two()
+}
+
+/// Clock divider of a state machine taking one sample per cycle, in 16.8 fixed point
+///
+/// Returns the divider and the real rate, None when the divider is not from 1 to 65536
+///
+pub fn clock_divider(sample_rate_hz: u32) -> Option<(u32, f64)> {
+    if sample_rate_hz == 0 {
+        return None;
+    }
+    let sys_clock_hz = SYS_CLOCK_HZ.load(Ordering::Relaxed) as u64;
+    let rate = sample_rate_hz as u64;
+    let div_256ths = (sys_clock_hz * 256 + rate / 2) / rate;
+    if !(256..=0xFFFF_FF).contains(&div_256ths) {
+        return None;
+    }
+    Some((
+        div_256ths as u32,
+        sys_clock_hz as f64 * 256.0 / div_256ths as f64,
+    ))
+}
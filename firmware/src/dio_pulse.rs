@@ -0,0 +1,207 @@
+use core::cell::RefCell;
+
+use cortex_m::interrupt::Mutex;
+use rp2040_hal::pac::{self, interrupt};
+use rp2040_hal::timer::{Alarm, Alarm1, Instant};
+
+use crate::device_time;
+use crate::BANK0_PINS;
+
+/// How a pin drives its line during the pulses
+#[derive(Clone, Copy)]
+pub enum PulseDrive {
+    // SIO output latch
+    PushPull,
+    // Output enable of a pin with its latch low, released is high
+    OpenDrain,
+}
+
+/// Pulses to generate on a pin
+#[derive(Clone, Copy)]
+pub struct PulseTrain {
+    pub drive: PulseDrive,
+    pub active_high: bool,
+    pub duration_us: u32,
+    pub period_us: u32,
+    pub count: u32,
+}
+
+/// Pulse train in progress on a pin
+#[derive(Clone, Copy)]
+struct RunningTrain {
+    train: PulseTrain,
+    // Pulses left, including the current one
+    remaining: u32,
+    // Start of the current or last pulse
+    started_at: u64,
+    active: bool,
+}
+
+impl RunningTrain {
+    /// Device time of the next level change
+    ///
+    fn next_change(&self) -> u64 {
+        match self.active {
+            true => self.started_at + self.train.duration_us as u64,
+            false => self.started_at + self.train.period_us as u64,
+        }
+    }
+}
+
+/// Pulse state of all the pins, shared with the alarm interrupt
+struct PinPulses {
+    trains: [Option<RunningTrain>; BANK0_PINS],
+    // Pins whose last train ended after all its pulses
+    completed: u32,
+    alarm: Option<Alarm1>,
+}
+
+impl PinPulses {
+    /// Schedule the alarm on the next level change of all the trains
+    ///
+    fn schedule_alarm(&mut self) {
+        let next = self.trains.iter().flatten().map(|t| t.next_change()).min();
+        if let (Some(alarm), Some(next)) = (self.alarm.as_mut(), next) {
+            // A change already due fires the interrupt right away
+            alarm.schedule_at(Instant::from_ticks(next)).ok();
+        }
+    }
+}
+
+static PIN_PULSES: Mutex<RefCell<PinPulses>> = Mutex::new(RefCell::new(PinPulses {
+    trains: [None; BANK0_PINS],
+    completed: 0,
+    alarm: None,
+}));
+
+/// Drive the line of a pin at a level
+///
+fn drive_level(pin_num: usize, drive: PulseDrive, high: bool) {
+    let mask = 1 << pin_num;
+    match drive {
+        PulseDrive::PushPull => {
+            let sio = unsafe { &*pac::SIO::ptr() };
+            match high {
+                true => sio.gpio_out_set().write(|w| unsafe { w.bits(mask) }),
+                false => sio.gpio_out_clr().write(|w| unsafe { w.bits(mask) }),
+            }
+        }
+        PulseDrive::OpenDrain => {
+            let io_bank0 = unsafe { &*pac::IO_BANK0::ptr() };
+            io_bank0
+                .gpio(pin_num)
+                .gpio_ctrl()
+                .modify(|_, w| match high {
+                    true => w.oeover().disable(),
+                    false => w.oeover().enable(),
+                });
+        }
+    }
+}
+
+/// Store the alarm timing the pulses and enable its interrupt
+///
+pub fn dio_pulse_init(mut alarm: Alarm1) {
+    alarm.enable_interrupt();
+    cortex_m::interrupt::free(|cs| {
+        PIN_PULSES.borrow(cs).borrow_mut().alarm = Some(alarm);
+    });
+    unsafe {
+        pac::NVIC::unmask(pac::Interrupt::TIMER_IRQ_1);
+    }
+}
+
+/// Start a pulse train on a pin, the first pulse starts right away
+///
+/// Returns false when a train is already running on the pin
+///
+pub fn start(pin_num: usize, train: PulseTrain) -> bool {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_pulses = PIN_PULSES.borrow(cs).borrow_mut();
+        if pin_pulses.trains[pin_num].is_some() {
+            return false;
+        }
+        pin_pulses.completed &= !(1 << pin_num);
+
+        drive_level(pin_num, train.drive, train.active_high);
+        pin_pulses.trains[pin_num] = Some(RunningTrain {
+            train,
+            remaining: train.count,
+            started_at: device_time::now_us(),
+            active: true,
+        });
+        pin_pulses.schedule_alarm();
+        true
+    })
+}
+
+/// Check if a pulse train is running on a pin
+///
+pub fn is_running(pin_num: usize) -> bool {
+    cortex_m::interrupt::free(|cs| PIN_PULSES.borrow(cs).borrow().trains[pin_num].is_some())
+}
+
+/// Check if the last train of a pin ended after all its pulses, and forget it
+///
+pub fn take_completed(pin_num: usize) -> bool {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_pulses = PIN_PULSES.borrow(cs).borrow_mut();
+        let completed = pin_pulses.completed & (1 << pin_num) != 0;
+        pin_pulses.completed &= !(1 << pin_num);
+        completed
+    })
+}
+
+/// Stop the pulse train of a pin, a pulse in progress is cut to the inactive level
+///
+pub fn cancel(pin_num: usize) {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_pulses = PIN_PULSES.borrow(cs).borrow_mut();
+        if let Some(running) = pin_pulses.trains[pin_num].take() {
+            if running.active {
+                drive_level(pin_num, running.train.drive, !running.train.active_high);
+            }
+        }
+    });
+}
+
+#[interrupt]
+fn TIMER_IRQ_1() {
+    let now = device_time::now_us();
+
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_pulses = PIN_PULSES.borrow(cs).borrow_mut();
+        if let Some(alarm) = pin_pulses.alarm.as_mut() {
+            alarm.clear_interrupt();
+        }
+
+        for pin_num in 0..BANK0_PINS {
+            let Some(mut running) = pin_pulses.trains[pin_num] else {
+                continue;
+            };
+            if running.next_change() > now {
+                continue;
+            }
+
+            // Changes are scheduled from the pulse start, a late interrupt does not drift
+            let train = running.train;
+            if running.active {
+                drive_level(pin_num, train.drive, !train.active_high);
+                running.active = false;
+                running.remaining -= 1;
+                if running.remaining == 0 {
+                    pin_pulses.trains[pin_num] = None;
+                    pin_pulses.completed |= 1 << pin_num;
+                    continue;
+                }
+            } else {
+                drive_level(pin_num, train.drive, train.active_high);
+                running.started_at += train.period_us as u64;
+                running.active = true;
+            }
+            pin_pulses.trains[pin_num] = Some(running);
+        }
+
+        pin_pulses.schedule_alarm();
+    });
+}
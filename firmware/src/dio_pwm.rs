@@ -0,0 +1,211 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use rp2040_hal::pac;
+use rp2040_hal::pac::pwm::ch::csr::DIVMODE_A;
+
+use crate::device_time;
+
+/// Number of PWM slices, GPIO n is driven by slice (n / 2) % 8
+const PWM_SLICES: usize = 8;
+
+/// Largest wrap value of a slice, 100 % duty needs a compare value of TOP + 1 in 16 bits
+const MAX_TOP: u64 = 0xFFFE;
+
+/// Largest clock divider, 8.4 fixed point
+const MAX_DIV_16THS: u64 = 0xFFF;
+
+/// System clock frequency, the slices count its cycles
+static SYS_CLOCK_HZ: AtomicU32 = AtomicU32::new(0);
+
+/// Result of a frequency measure
+pub struct Measurement {
+    pub edge_count: u32,
+    pub frequency_hz: f64,
+    pub period_us: f64,
+    pub duty_cycle: f64,
+}
+
+/// Clock divider and wrap value of a slice generating a frequency
+#[derive(Clone, Copy, PartialEq)]
+pub struct SliceSetting {
+    // Divider in 16ths of system clock cycles
+    div_16ths: u16,
+    top: u16,
+}
+
+/// Take the PWM block out of reset
+///
+pub fn dio_pwm_init(_pwm: pac::PWM, resets: &mut pac::RESETS, sys_clock_hz: u32) {
+    resets.reset().modify(|_, w| w.pwm().clear_bit());
+    while resets.reset_done().read().pwm().bit_is_clear() {}
+    SYS_CLOCK_HZ.store(sys_clock_hz, Ordering::Relaxed);
+}
+
+/// PWM slice connected to a pin
+///
+pub fn slice_of(pin_num: usize) -> usize {
+    (pin_num / 2) % PWM_SLICES
+}
+
+/// Check if a pin can be measured, only the B channel of a slice is an input
+///
+pub fn can_measure(pin_num: usize) -> bool {
+    pin_num % 2 == 1
+}
+
+/// Count on the B input of a slice during the gate time
+///
+/// Returns the count and the real duration of the gate in microseconds
+///
+fn count_during(slice: usize, mode: DIVMODE_A, gate_time_us: u32) -> (u64, u64) {
+    let pwm = unsafe { &*pac::PWM::ptr() };
+    let ch = pwm.ch(slice);
+
+    ch.csr().write(|w| w.divmode().variant(mode));
+    ch.div()
+        .write(|w| unsafe { w.int().bits(1).frac().bits(0) });
+    ch.top().write(|w| unsafe { w.top().bits(u16::MAX) });
+    ch.ctr().write(|w| unsafe { w.ctr().bits(0) });
+
+    // The counter wraps every 65536 counts, the wraps are counted while the gate is open
+    let mut wraps = 0u64;
+    let mut last = 0u16;
+    let start = device_time::now_us();
+    ch.csr().modify(|_, w| w.en().set_bit());
+    loop {
+        let ctr = ch.ctr().read().ctr().bits();
+        if ctr < last {
+            wraps += 1;
+        }
+        last = ctr;
+        if device_time::now_us() - start >= gate_time_us as u64 {
+            break;
+        }
+    }
+    ch.csr().modify(|_, w| w.en().clear_bit());
+    let elapsed_us = device_time::now_us() - start;
+
+    let ctr = ch.ctr().read().ctr().bits();
+    if ctr < last {
+        wraps += 1;
+    }
+    ((wraps << 16) + ctr as u64, elapsed_us)
+}
+
+/// Measure frequency then duty cycle on a pin, each during the gate time
+///
+/// The pin must already be connected to its PWM slice
+///
+pub fn measure(pin_num: usize, gate_time_us: u32) -> Measurement {
+    let slice = slice_of(pin_num);
+
+    // Rising edges of the input
+    let (edges, frequency_gate_us) = count_during(slice, DIVMODE_A::RISE, gate_time_us);
+
+    // System clock cycles while the input is high
+    let (high_cycles, duty_gate_us) = count_during(slice, DIVMODE_A::LEVEL, gate_time_us);
+
+    let frequency_hz = edges as f64 * 1_000_000.0 / frequency_gate_us as f64;
+    let gate_cycles =
+        duty_gate_us as f64 * SYS_CLOCK_HZ.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+    Measurement {
+        edge_count: edges as u32,
+        frequency_hz,
+        period_us: match edges {
+            0 => 0.0,
+            _ => 1_000_000.0 / frequency_hz,
+        },
+        duty_cycle: (high_cycles as f64 / gate_cycles).min(1.0),
+    }
+}
+
+/// Find the slice setting closest to a frequency, None when out of the slice range
+///
+pub fn slice_setting(frequency_hz: u32) -> Option<SliceSetting> {
+    if frequency_hz == 0 {
+        return None;
+    }
+    let clock_16ths = SYS_CLOCK_HZ.load(Ordering::Relaxed) as u64 * 16;
+    let frequency_hz = frequency_hz as u64;
+
+    // Smallest divider keeping the wrap value in range gives the best duty resolution
+    let div_16ths = clock_16ths.div_ceil(frequency_hz * (MAX_TOP + 1)).max(16);
+    let wrap = (clock_16ths + div_16ths * frequency_hz / 2) / (div_16ths * frequency_hz);
+    if div_16ths > MAX_DIV_16THS || wrap < 2 {
+        return None;
+    }
+    Some(SliceSetting {
+        div_16ths: div_16ths as u16,
+        top: (wrap - 1) as u16,
+    })
+}
+
+/// Setting currently used by a slice
+///
+pub fn current_setting(slice: usize) -> SliceSetting {
+    let pwm = unsafe { &*pac::PWM::ptr() };
+    let ch = pwm.ch(slice);
+    let div = ch.div().read();
+    SliceSetting {
+        div_16ths: ((div.int().bits() as u16) << 4) | div.frac().bits() as u16,
+        top: ch.top().read().top().bits(),
+    }
+}
+
+/// Frequency generated by a slice
+///
+pub fn output_frequency(slice: usize) -> f64 {
+    let setting = current_setting(slice);
+    SYS_CLOCK_HZ.load(Ordering::Relaxed) as f64 * 16.0
+        / (setting.div_16ths as f64 * (setting.top as f64 + 1.0))
+}
+
+/// Start generating a signal on the channel of a pin
+///
+/// The other channel of the slice keeps running, it must accept the same setting
+///
+pub fn start_output(pin_num: usize, setting: SliceSetting, duty_cycle: f64) -> f64 {
+    let pwm = unsafe { &*pac::PWM::ptr() };
+    let ch = pwm.ch(slice_of(pin_num));
+
+    ch.div().write(|w| unsafe {
+        w.int()
+            .bits((setting.div_16ths >> 4) as u8)
+            .frac()
+            .bits((setting.div_16ths & 0xF) as u8)
+    });
+    ch.top().write(|w| unsafe { w.top().bits(setting.top) });
+    let duty_cycle = set_duty(pin_num, duty_cycle);
+    ch.csr().modify(|_, w| w.divmode().div().en().set_bit());
+    duty_cycle
+}
+
+/// Change the duty cycle on the channel of a pin, from the next period
+///
+/// Returns the duty cycle really generated
+///
+pub fn set_duty(pin_num: usize, duty_cycle: f64) -> f64 {
+    let pwm = unsafe { &*pac::PWM::ptr() };
+    let ch = pwm.ch(slice_of(pin_num));
+
+    let period = ch.top().read().top().bits() as f64 + 1.0;
+    let level = (duty_cycle * period + 0.5) as u16;
+    if pin_num % 2 == 0 {
+        ch.cc().modify(|_, w| unsafe { w.a().bits(level) });
+    } else {
+        ch.cc().modify(|_, w| unsafe { w.b().bits(level) });
+    }
+    level as f64 / period
+}
+
+/// Stop the signal on the channel of a pin, and the whole slice when no other pin uses it
+///
+pub fn stop_output(pin_num: usize, stop_slice: bool) {
+    set_duty(pin_num, 0.0);
+    if stop_slice {
+        let pwm = unsafe { &*pac::PWM::ptr() };
+        pwm.ch(slice_of(pin_num))
+            .csr()
+            .modify(|_, w| w.en().clear_bit());
+    }
+}
@@ -1,9 +1,33 @@
 // Print debug support
 use crate::api_dio_utils;
+use crate::device_time;
+use crate::dio_capture::{self, CaptureSetting, DioCapture, Trigger};
+use crate::dio_config_store::{self, PinConfig, PinMode};
+use crate::dio_failsafe::{self, Failsafe};
+use crate::dio_pattern::DioPattern;
+use crate::dio_pulse::{self, PulseDrive, PulseTrain};
+use crate::dio_pwm;
+use crate::dio_sequence::{self, Step};
+use crate::edge_events::{self, EdgeSelection};
 #[cfg(any(feature = "uart0_debug"))]
 use crate::uart_debug::uart_debug_print;
 use crate::{
-    api_dio::{PicohaDioAnswer, PicohaDioRequest},
+    api_dio::{
+        picoha_dio_event::Event, picoha_dio_request_v2::Payload, BatchOperationResult,
+        BatchRequest, CaptureChunk, CaptureState, CaptureStatus, CaptureTrigger,
+        ConfigureEdgeCounterRequest, ConfigureFailsafeRequest, Direction, Edge, EdgeEvent,
+        ErrorCode, FailsafeState, FrequencyMeasurement, GetFailsafeStateRequest,
+        GetPinDebounceRequest, GetPinDirectionRequest, GetPinDriveRequest, GetPinPullRequest,
+        GetPinValueRequest, GetSavedConfigRequest, Level, LoadPatternRequest, LoadSequenceRequest,
+        MeasureFrequencyRequest, PatternState, PicohaDioAnswer, PicohaDioEvent, PicohaDioRequest,
+        PicohaDioRequestV2, PortState, PulseRequest, PwmOutput, ReadCaptureRequest,
+        ReadEdgeCounterRequest, RequestType, ResetEdgeCounterRequest, SafeState, SavedPinConfig,
+        SequenceState, SetPinDebounceRequest, SetPinDirectionRequest, SetPinDriveRequest,
+        SetPinNameRequest, SetPinPullRequest, SetPinPwmDutyRequest, SetPinPwmRequest,
+        SetPinSafeStateRequest, SetPinValueRequest, SetPortDirectionRequest, SetPortValueRequest,
+        StartCaptureRequest, StartPatternRequest, StartSequenceRequest, SubscribeEdgesRequest,
+        SyncTimeRequest, TimeSync, WaitCondition, WaitForRequest, WaitResult,
+    },
     print_debug_message,
 };
 use core::fmt::Write;
@@ -14,11 +38,53 @@ use rp2040_hal::gpio::new_pin;
 use femtopb::Message;
 
 use rp2040_hal::gpio::DynPinId;
+use rp2040_hal::gpio::{OutputDriveStrength, OutputEnableOverride, OutputSlewRate};
 // USB Communications Class Device support
 use usbd_serial::SerialPort;
 
 const MAX_PINS: usize = 23;
 
+/// Maximum number of operations in a batch request
+const MAX_BATCH_OPERATIONS: usize = 48;
+
+/// Room kept in a batch answer before each operation, for the largest result, with a capture
+/// state, and the echo and timestamp added when the answer is sent
+const BATCH_ANSWER_MARGIN: usize = 128;
+
+/// Maximum number of edge events sent per main loop iteration, requests are not delayed longer
+const MAX_EVENTS_PER_POLL: usize = 16;
+
+/// Maximum debounce time of a pin
+const MAX_DEBOUNCE_US: u32 = 1_000_000;
+
+/// Maximum gate time of a frequency measure, the USB is not served while measuring, so the
+/// two gates stay far below the timeout of the host
+const MAX_GATE_TIME_US: u32 = 100_000;
+
+/// Maximum number of wait requests waiting at the same time
+const MAX_PENDING_WAITS: usize = 4;
+
+/// Largest encoded answer, a batch answer must fit in it
+const MAX_ANSWER_SIZE: usize = 1024;
+
+/// Largest SLIP frame, when every byte of the largest answer is escaped, with its END markers
+pub const MAX_FRAME_SIZE: usize = 2 * MAX_ANSWER_SIZE + 2;
+
+/// Largest encoded event
+const MAX_EVENT_SIZE: usize = 64;
+
+/// Largest SLIP frame of an event
+const MAX_EVENT_FRAME_SIZE: usize = 2 * MAX_EVENT_SIZE + 2;
+
+/// Frames waiting for room in the USB serial port, events leave room for an answer
+const TX_BACKLOG_SIZE: usize = 2 * MAX_FRAME_SIZE;
+
+/// USB serial port used to talk with the host
+///
+/// The write buffer holds a whole frame, batch answers are larger than the default buffer
+pub type DioSerialPort<'a> =
+    SerialPort<'a, rp2040_hal::usb::UsbBus, [u8; 128], [u8; MAX_FRAME_SIZE]>;
+
 type PinO = rp2040_hal::gpio::Pin<
     rp2040_hal::gpio::DynPinId,
     rp2040_hal::gpio::FunctionSio<rp2040_hal::gpio::SioOutput>,
@@ -31,21 +97,84 @@ type PinI = rp2040_hal::gpio::Pin<
     rp2040_hal::gpio::DynPullType,
 >;
 const PINI_NONE: Option<PinI> = None;
+type PinPwm = rp2040_hal::gpio::Pin<
+    rp2040_hal::gpio::DynPinId,
+    rp2040_hal::gpio::FunctionPwm,
+    rp2040_hal::gpio::DynPullType,
+>;
+const PINPWM_NONE: Option<PinPwm> = None;
+type PinPattern = rp2040_hal::gpio::Pin<
+    rp2040_hal::gpio::DynPinId,
+    rp2040_hal::gpio::FunctionPio1,
+    rp2040_hal::gpio::DynPullType,
+>;
+const PINPATTERN_NONE: Option<PinPattern> = None;
 
 enum PinDirection {
     input,
     output,
+    open_drain,
+    pwm,
+    pattern,
 }
 enum PinValue {
     low,
     high,
 }
 
+/// Identification of the request being processed, copied back in its answer
+#[derive(Clone, Copy, Default)]
+struct RequestEcho {
+    request_id: Option<u32>,
+    request_type: Option<RequestType>,
+    pin_num: Option<u32>,
+}
+
+/// Wait request answered once its pins match or its timeout expires
+#[derive(Clone, Copy)]
+struct PendingWait {
+    echo: RequestEcho,
+    mask: u32,
+    value: u32,
+    // Wait for the pins to change into the value instead of just having it
+    edge: bool,
+    // Last levels did not match, so the next match is an edge
+    armed: bool,
+    started_at: u64,
+    timeout_us: u32,
+}
+
 /// Application Digital I/O
 pub struct DioRequestProcessor {
     pins_id: [Option<DynPinId>; MAX_PINS],
     pins_o: [Option<PinO>; MAX_PINS],
     pins_i: [Option<PinI>; MAX_PINS],
+    // Open-drain pins, the output latch stays low and the output enable is toggled
+    pins_od: [Option<PinO>; MAX_PINS],
+    // Pins driven by their PWM slice instead of SIO
+    pins_pwm: [Option<PinPwm>; MAX_PINS],
+    // Pins driven by the pattern generator while it plays
+    pins_pattern: [Option<PinPattern>; MAX_PINS],
+    // Pull type of each pin, kept across direction changes
+    pins_pull: [rp2040_hal::gpio::DynPullType; MAX_PINS],
+    // Request currently processed
+    echo: RequestEcho,
+    // Device time when the request currently processed was received
+    received_at: u64,
+    // Pulse requests answered when their pulses end
+    pending_pulses: [Option<RequestEcho>; MAX_PINS],
+    // Wait requests answered when their condition is met
+    pending_waits: [Option<PendingWait>; MAX_PENDING_WAITS],
+    // Logic analyzer on a range of pins
+    capture: DioCapture,
+    // Pattern generator on a range of pins
+    pattern: DioPattern,
+    // Safe states applied when the host goes silent
+    failsafe: Failsafe,
+    // Names given to the pins by the host, empty for unnamed pins
+    pin_names: [dio_config_store::PinName; MAX_PINS],
+    // Whole frames not yet taken by the serial port, which may take part of a frame
+    tx_backlog: heapless::Vec<u8, TX_BACKLOG_SIZE>,
 }
 
 impl DioRequestProcessor {
@@ -56,6 +185,19 @@ impl DioRequestProcessor {
             pins_id: pins_id,
             pins_o: [PINO_NONE; MAX_PINS],
             pins_i: [PINI_NONE; MAX_PINS],
+            pins_od: [PINO_NONE; MAX_PINS],
+            pins_pwm: [PINPWM_NONE; MAX_PINS],
+            pins_pattern: [PINPATTERN_NONE; MAX_PINS],
+            pins_pull: [rp2040_hal::gpio::DynPullType::Down; MAX_PINS],
+            echo: RequestEcho::default(),
+            received_at: 0,
+            pending_pulses: [None; MAX_PINS],
+            pending_waits: [None; MAX_PENDING_WAITS],
+            capture: DioCapture::new(),
+            pattern: DioPattern::new(),
+            failsafe: Failsafe::new(),
+            pin_names: core::array::from_fn(|_| dio_config_store::PinName::new()),
+            tx_backlog: heapless::Vec::new(),
         }
     }
 
@@ -64,7 +206,134 @@ impl DioRequestProcessor {
     ///
     pub fn init_all_pins_as_input(&mut self) {
         for n in 0..MAX_PINS {
-            self.set_pin_as_input(n);
+            // Unavailable pins are just skipped
+            self.set_pin_as_input(n).ok();
+        }
+    }
+
+    /// Apply the power-on configuration saved in the flash, if any
+    ///
+    pub fn apply_saved_config(&mut self) {
+        let Some(configs) = dio_config_store::load() else {
+            print_debug_message!(b"+ no saved configuration\r\n");
+            return;
+        };
+        for pin_num in 0..MAX_PINS {
+            if self.pins_id[pin_num].is_some() {
+                self.apply_pin_config(pin_num, &configs[pin_num]).ok();
+            }
+        }
+    }
+
+    /// Load the pin names saved in the flash, if any
+    ///
+    pub fn load_pin_names(&mut self) {
+        if !dio_config_store::load_names(&mut self.pin_names) {
+            print_debug_message!(b"+ no saved pin names\r\n");
+        }
+    }
+
+    /// Find the pin of a name
+    ///
+    fn pin_by_name(&self, name: &str) -> Result<usize, ErrorCode> {
+        self.pin_names
+            .iter()
+            .position(|pin_name| !pin_name.is_empty() && pin_name.as_str() == name)
+            .ok_or_else(|| {
+                print_debug_message!("\t!!!no pin named {:?}", name);
+                ErrorCode::UnknownPinName
+            })
+    }
+
+    /// Make a payload target the pin of a name, nothing to do without name
+    ///
+    fn resolve_pin_name(&self, pin_name: &str, payload: &mut Payload) -> Result<(), ErrorCode> {
+        if pin_name.is_empty() {
+            return Ok(());
+        }
+        let pin = self.pin_by_name(pin_name)?;
+        // A name given to a request that does not target a pin is a mistake of the host
+        let pin_num = api_dio_utils::payload_pin_num_mut(payload).ok_or(ErrorCode::InvalidValue)?;
+        *pin_num = pin as u32;
+        Ok(())
+    }
+
+    /// Put a pin in a configuration, outputs start at their level without glitch
+    ///
+    fn apply_pin_config(&mut self, pin_num: usize, config: &PinConfig) -> Result<(), ErrorCode> {
+        self.failsafe.set_safe_state(pin_num, config.safe_state);
+        self.set_pin_pull(pin_num, config.pull);
+        match config.mode {
+            PinMode::Input => self.set_pin_as_input(pin_num),
+            PinMode::Output => {
+                // The output latch holds the level before the pin drives it
+                let sio = unsafe { &*rp2040_hal::pac::SIO::ptr() };
+                match config.high {
+                    true => sio
+                        .gpio_out_set()
+                        .write(|w| unsafe { w.bits(1 << pin_num) }),
+                    false => sio
+                        .gpio_out_clr()
+                        .write(|w| unsafe { w.bits(1 << pin_num) }),
+                };
+                self.set_pin_as_output(pin_num)?;
+                self.set_pin_drive(pin_num, config.strength, config.slew_rate)
+            }
+            PinMode::OpenDrain => {
+                self.set_pin_as_open_drain(pin_num)?;
+                self.set_pin_drive(pin_num, config.strength, config.slew_rate)?;
+                match config.high {
+                    true => Ok(()),
+                    false => self.set_pin_low(pin_num),
+                }
+            }
+        }
+    }
+
+    /// Get the current configuration of a pin, to save it
+    ///
+    fn get_pin_config(&mut self, pin_num: usize) -> PinConfig {
+        let mode = match self.get_internal_pin_direction(pin_num) {
+            Some(PinDirection::input) | None => PinMode::Input,
+            Some(PinDirection::open_drain) => PinMode::OpenDrain,
+            Some(_) => PinMode::Output,
+        };
+        let high = match mode {
+            PinMode::Input => false,
+            // The line may be pulled low by someone else, the pin only releases it
+            PinMode::OpenDrain => self.pins_od[pin_num]
+                .as_ref()
+                .map(|pin| pin.get_output_enable_override() != OutputEnableOverride::Enable)
+                .unwrap_or(true),
+            PinMode::Output => matches!(self.get_internal_pin_value(pin_num), Some(PinValue::high)),
+        };
+        let (strength, slew_rate) = self
+            .get_pin_drive(pin_num)
+            .unwrap_or((PinConfig::FACTORY.strength, PinConfig::FACTORY.slew_rate));
+        PinConfig {
+            mode,
+            high,
+            pull: self.pins_pull[pin_num],
+            strength,
+            slew_rate,
+            safe_state: self.failsafe.safe_state(pin_num),
+        }
+    }
+
+    /// Check that a pin number from a request can be used
+    ///
+    fn check_pin(&self, pin_num: u32) -> Result<usize, ErrorCode> {
+        let pin = pin_num as usize;
+        match self.pins_id.get(pin) {
+            Some(Some(_)) => Ok(pin),
+            Some(None) if cfg!(feature = "uart0_debug") && pin < 2 => {
+                print_debug_message!("\t!!!pin {:?} reserved", pin);
+                Err(ErrorCode::PinReserved)
+            }
+            _ => {
+                print_debug_message!("\t!!!pin {:?} not available", pin);
+                Err(ErrorCode::InvalidPin)
+            }
         }
     }
 
@@ -84,6 +353,21 @@ impl DioRequestProcessor {
             return Some(PinDirection::input);
         }
 
+        // if pin is in the open-drain array, it is configured as open-drain
+        if self.pins_od[pin].is_some() {
+            return Some(PinDirection::open_drain);
+        }
+
+        // if pin is in the pwm array, it is driven by its PWM slice
+        if self.pins_pwm[pin].is_some() {
+            return Some(PinDirection::pwm);
+        }
+
+        // if pin is in the pattern array, it is driven by the pattern generator
+        if self.pins_pattern[pin].is_some() {
+            return Some(PinDirection::pattern);
+        }
+
         // else not configured yet
         // print_debug_message!(b"? not configured\r\n");
         None
@@ -96,6 +380,16 @@ impl DioRequestProcessor {
         print_debug_message!("? check pin {:?}\r\n", pin);
 
         let dir = self.get_internal_pin_direction(pin);
+
+        // Debounced pins report their settled level
+        if let Some(PinDirection::input) | Some(PinDirection::open_drain) = dir {
+            match edge_events::debounced_level(pin) {
+                Some(true) => return Some(PinValue::high),
+                Some(false) => return Some(PinValue::low),
+                None => {}
+            }
+        }
+
         match dir {
             Some(d) => match d {
                 PinDirection::input => {
@@ -119,6 +413,39 @@ impl DioRequestProcessor {
                         }
                     }
                 }
+                PinDirection::open_drain => {
+                    // Read the real line level, the pin may be pulled low by someone else
+                    let pin_obj = &self.pins_od[pin];
+                    if let Some(pin_obj) = pin_obj {
+                        match pin_obj.as_input().is_high() {
+                            Ok(true) => return Some(PinValue::high),
+                            Ok(false) => return Some(PinValue::low),
+                            Err(_) => {} // Infalible
+                        }
+                    }
+                }
+                PinDirection::pwm => {
+                    // Level on the pad at this instant of the period
+                    let pin_obj = &self.pins_pwm[pin];
+                    if let Some(pin_obj) = pin_obj {
+                        match pin_obj.as_input().is_high() {
+                            Ok(true) => return Some(PinValue::high),
+                            Ok(false) => return Some(PinValue::low),
+                            Err(_) => {} // Infalible
+                        }
+                    }
+                }
+                PinDirection::pattern => {
+                    // Level on the pad at this sample of the pattern
+                    let pin_obj = &self.pins_pattern[pin];
+                    if let Some(pin_obj) = pin_obj {
+                        match pin_obj.as_input().is_high() {
+                            Ok(true) => return Some(PinValue::high),
+                            Ok(false) => return Some(PinValue::low),
+                            Err(_) => {} // Infalible
+                        }
+                    }
+                }
             },
             None => {
                 return None;
@@ -130,221 +457,978 @@ impl DioRequestProcessor {
         None
     }
 
+    /// Other pins in PWM mode sharing the slice of a pin
+    ///
+    fn pwm_pins_on_slice(&self, pin_num: usize) -> impl Iterator<Item = usize> + '_ {
+        let slice = dio_pwm::slice_of(pin_num);
+        (0..MAX_PINS).filter(move |n| {
+            *n != pin_num && self.pins_pwm[*n].is_some() && dio_pwm::slice_of(*n) == slice
+        })
+    }
+
+    /// Drive a pin with its PWM slice
+    ///
+    /// Returns the duty cycle really generated
+    ///
+    fn set_pin_as_pwm(
+        &mut self,
+        pin_num: usize,
+        frequency_hz: u32,
+        duty_cycle: f64,
+    ) -> Result<f64, ErrorCode> {
+        print_debug_message!("\tset pin {:?} as pwm", pin_num);
+        if !(0.0..=1.0).contains(&duty_cycle) {
+            return Err(ErrorCode::InvalidValue);
+        }
+        let setting = dio_pwm::slice_setting(frequency_hz).ok_or(ErrorCode::InvalidValue)?;
+
+        //
+        // A pin on the same channel would copy the signal, a pin on the other channel
+        // keeps running and only accepts the same period
+        for other in self.pwm_pins_on_slice(pin_num) {
+            if other % 2 == pin_num % 2
+                || dio_pwm::current_setting(dio_pwm::slice_of(other)) != setting
+            {
+                print_debug_message!("      * pwm slice used by pin {:?}", other);
+                return Err(ErrorCode::ResourceBusy);
+            }
+        }
+
+        let dyn_id = self.pins_id[pin_num].ok_or(ErrorCode::InvalidPin)?;
+        self.release_pattern(pin_num);
+        let duty_cycle = dio_pwm::start_output(pin_num, setting, duty_cycle);
+        let mut pin = unsafe { new_pin(dyn_id) }
+            .try_into_function::<rp2040_hal::gpio::FunctionPwm>()
+            .map_err(|_| {
+                print_debug_message!("      * error converting pin {:?} to pwm", pin_num);
+                ErrorCode::InvalidPin
+            })?;
+
+        //
+        // Remove pin from the sio arrays, the slice drives it now
+        self.pins_i[pin_num] = None;
+        self.pins_o[pin_num] = None;
+        self.pins_od[pin_num] = None;
+        edge_events::release_pin(pin_num);
+        stop_timed_outputs(pin_num);
+
+        pin.set_output_enable_override(OutputEnableOverride::Normal);
+        pin.set_pull_type(self.pins_pull[pin_num]);
+        self.pins_pwm[pin_num] = Some(pin);
+        Ok(duty_cycle)
+    }
+
+    /// Give a pin in PWM mode back, its slice stops when no other pin uses it
+    ///
+    fn release_pwm(&mut self, pin_num: usize) {
+        if self.pins_pwm[pin_num].take().is_some() {
+            let slice_used = self.pwm_pins_on_slice(pin_num).next().is_some();
+            dio_pwm::stop_output(pin_num, !slice_used);
+        }
+    }
+
+    /// Stop the pattern when it drives a pin, the host takes the pin back
+    ///
+    fn release_pattern(&mut self, pin_num: usize) {
+        if self.pins_pattern[pin_num].is_some() {
+            self.stop_pattern();
+        }
+    }
+
+    /// Check that a pin reads its line instead of driving it, input or open-drain
+    ///
+    fn check_pin_not_driven(&self, pin_num: usize) -> Result<(), ErrorCode> {
+        match self.get_internal_pin_direction(pin_num) {
+            Some(PinDirection::input) | Some(PinDirection::open_drain) => Ok(()),
+            Some(PinDirection::output) | Some(PinDirection::pwm) | Some(PinDirection::pattern) => {
+                Err(ErrorCode::WrongDirection)
+            }
+            None => Err(ErrorCode::PinNotConfigured),
+        }
+    }
+
     /// Set a pin as output
     ///
-    fn set_pin_as_output(&mut self, pin_num: usize) {
+    fn set_pin_as_output(&mut self, pin_num: usize) -> Result<(), ErrorCode> {
         print_debug_message!("\tset pin {:?} as output", pin_num);
+        self.release_pattern(pin_num);
         self.pins_id[pin_num as usize]
-            .map(|dyn_id| unsafe {
+            .ok_or_else(|| {
+                print_debug_message!("      * pin {:?} not available", pin_num);
+                ErrorCode::InvalidPin
+            })
+            .and_then(|dyn_id| unsafe {
                 let pin = new_pin(dyn_id);
                 pin.try_into_function::<rp2040_hal::gpio::FunctionSioOutput>()
-                    .and_then(|pin_out| {
+                    .map(|mut pin_out| {
                         //
-                        // Remove pin from ouput array if it is there
+                        // Remove pin from input, open-drain and pwm arrays if it is there
                         self.pins_i[pin_num] = None;
+                        self.pins_od[pin_num] = None;
+                        self.release_pwm(pin_num);
+                        stop_timed_outputs(pin_num);
 
+                        // A driven pin only reports its own writes
+                        edge_events::release_pin(pin_num);
+
+                        pin_out.set_output_enable_override(OutputEnableOverride::Normal);
                         self.pins_o[pin_num as usize] = Some(pin_out);
-                        Ok(())
                     })
-                    // Ignore the error, just a warning
                     .map_err(|_| {
                         print_debug_message!(
                             "      * error converting pin {:?} to output",
                             pin_num
                         );
+                        ErrorCode::InvalidPin
                     })
-                    .ok();
             })
-            // Ignore the error, just a warning
-            .ok_or_else(|| {
-                print_debug_message!("      * pin {:?} not available", pin_num);
-            })
-            .ok();
     }
 
     /// Set a pin as input
     ///
-    fn set_pin_as_input(&mut self, pin_num: usize) {
+    fn set_pin_as_input(&mut self, pin_num: usize) -> Result<(), ErrorCode> {
         //
         // Debug log
         print_debug_message!("\tset pin {:?} as input", pin_num);
+        self.release_pattern(pin_num);
 
         //
         // Set the pin as input
         self.pins_id[pin_num as usize]
-            .map(|dyn_id| unsafe {
+            .ok_or_else(|| {
+                print_debug_message!("      * pin {:?} not available", pin_num);
+                ErrorCode::InvalidPin
+            })
+            .and_then(|dyn_id| unsafe {
                 let pin = new_pin(dyn_id);
                 pin.try_into_function::<rp2040_hal::gpio::FunctionSioInput>()
-                    .and_then(|mut pin_in| {
+                    .map(|mut pin_in| {
                         //
-                        // Remove pin from ouput array if it is there
+                        // Remove pin from ouput, open-drain and pwm arrays if it is there
                         self.pins_o[pin_num] = None;
+                        self.pins_od[pin_num] = None;
+                        self.release_pwm(pin_num);
+                        stop_timed_outputs(pin_num);
 
-                        // pin_in.set_pull_type(rp2040_hal::gpio::DynPullType::None);
-                        pin_in.set_pull_type(rp2040_hal::gpio::DynPullType::Down);
+                        pin_in.set_output_enable_override(OutputEnableOverride::Normal);
+                        pin_in.set_pull_type(self.pins_pull[pin_num]);
                         self.pins_i[pin_num as usize] = Some(pin_in);
-                        Ok(())
                     })
-                    // Ignore the error, just a warning
                     .map_err(|_| {
                         print_debug_message!("      * error converting pin {:?} to input", pin_num);
+                        ErrorCode::InvalidPin
                     })
-                    .ok();
             })
-            // Ignore the error, just a warning
+    }
+
+    /// Set a pin as open-drain output
+    ///
+    /// The pin starts released, so the line is high unless something else pulls it down
+    ///
+    fn set_pin_as_open_drain(&mut self, pin_num: usize) -> Result<(), ErrorCode> {
+        //
+        // Debug log
+        print_debug_message!("\tset pin {:?} as open-drain", pin_num);
+        self.release_pattern(pin_num);
+
+        //
+        // Set the pin as output with the latch low, the output enable override drives the line
+        self.pins_id[pin_num as usize]
             .ok_or_else(|| {
                 print_debug_message!("      * pin {:?} not available", pin_num);
+                ErrorCode::InvalidPin
             })
-            .ok();
+            .and_then(|dyn_id| unsafe {
+                let mut pin = new_pin(dyn_id);
+                pin.set_output_enable_override(OutputEnableOverride::Disable);
+                pin.try_into_function::<rp2040_hal::gpio::FunctionSioOutput>()
+                    .map(|mut pin_od| {
+                        //
+                        // Remove pin from input, output and pwm arrays if it is there
+                        self.pins_i[pin_num] = None;
+                        self.pins_o[pin_num] = None;
+                        self.release_pwm(pin_num);
+                        stop_timed_outputs(pin_num);
+
+                        pin_od.set_low().unwrap();
+                        pin_od.set_pull_type(self.pins_pull[pin_num]);
+                        self.pins_od[pin_num as usize] = Some(pin_od);
+                    })
+                    .map_err(|_| {
+                        print_debug_message!(
+                            "      * error converting pin {:?} to open-drain",
+                            pin_num
+                        );
+                        ErrorCode::InvalidPin
+                    })
+            })
+    }
+
+    /// Set the pull type of a pin
+    ///
+    /// The pull type is stored so it is applied again each time the pin changes direction
+    ///
+    fn set_pin_pull(&mut self, pin_num: usize, pull: rp2040_hal::gpio::DynPullType) {
+        print_debug_message!("\t+pin {:?} pull {:?}", pin_num, pull);
+        self.pins_pull[pin_num] = pull;
+        if let Some(pin) = self.pins_i[pin_num].as_mut() {
+            pin.set_pull_type(pull);
+        }
+        if let Some(pin) = self.pins_o[pin_num].as_mut() {
+            pin.set_pull_type(pull);
+        }
+        if let Some(pin) = self.pins_od[pin_num].as_mut() {
+            pin.set_pull_type(pull);
+        }
+    }
+
+    /// Get the pull type of a pin
+    ///
+    fn get_pin_pull(&self, pin_num: usize) -> rp2040_hal::gpio::DynPullType {
+        self.pins_pull[pin_num]
+    }
+
+    /// Error to report when an output operation is requested on a pin that is not an output
+    ///
+    fn not_an_output_error(&self, pin_num: usize) -> ErrorCode {
+        if self.pins_pattern[pin_num].is_some() {
+            print_debug_message!("\t!!!pin {:?} driven by the pattern", pin_num);
+            ErrorCode::ResourceBusy
+        } else if self.pins_i[pin_num].is_some() || self.pins_pwm[pin_num].is_some() {
+            print_debug_message!("\t!!!pin {:?} not a sio output", pin_num);
+            ErrorCode::WrongDirection
+        } else {
+            print_debug_message!("\t!!!pin {:?} not configured", pin_num);
+            ErrorCode::PinNotConfigured
+        }
+    }
+
+    /// Set the drive strength and slew rate of an output pin
+    ///
+    fn set_pin_drive(
+        &mut self,
+        pin_num: usize,
+        strength: OutputDriveStrength,
+        slew_rate: OutputSlewRate,
+    ) -> Result<(), ErrorCode> {
+        print_debug_message!("\t+pin {:?} drive {:?}", pin_num, strength);
+        match self.pins_o[pin_num]
+            .as_mut()
+            .or(self.pins_od[pin_num].as_mut())
+        {
+            Some(pin) => {
+                pin.set_drive_strength(strength);
+                pin.set_slew_rate(slew_rate);
+                Ok(())
+            }
+            None => Err(self.not_an_output_error(pin_num)),
+        }
+    }
+
+    /// Get the drive strength and slew rate of an output pin
+    ///
+    fn get_pin_drive(
+        &self,
+        pin_num: usize,
+    ) -> Result<(OutputDriveStrength, OutputSlewRate), ErrorCode> {
+        self.pins_o[pin_num]
+            .as_ref()
+            .or(self.pins_od[pin_num].as_ref())
+            .map(|pin| (pin.get_drive_strength(), pin.get_slew_rate()))
+            .ok_or_else(|| self.not_an_output_error(pin_num))
     }
 
     /// Set a pin low
     ///
-    fn set_pin_low(&mut self, pin_num: u32) -> Result<(), &'static str> {
+    fn set_pin_low(&mut self, pin_num: usize) -> Result<(), ErrorCode> {
         print_debug_message!("\t+pin {:?} low", pin_num);
-        self.pins_o[pin_num as usize]
-            .as_mut()
-            .map(|pin| {
+        stop_timed_outputs(pin_num);
+        if let Some(pin) = self.pins_od[pin_num].as_mut() {
+            // Drive the line low
+            pin.set_output_enable_override(OutputEnableOverride::Enable);
+            return Ok(());
+        }
+        match self.pins_o[pin_num].as_mut() {
+            Some(pin) => {
                 pin.set_low().unwrap();
-            })
-            .ok_or_else(|| {
-                print_debug_message!("\t!!!pin {:?} not available", pin_num);
-                "Pin not available"
-            })?;
-        Ok(())
+                Ok(())
+            }
+            None => Err(self.not_an_output_error(pin_num)),
+        }
     }
 
     /// Set a pin high
     ///
-    fn set_pin_high(&mut self, pin_num: u32) -> Result<(), &'static str> {
+    fn set_pin_high(&mut self, pin_num: usize) -> Result<(), ErrorCode> {
         print_debug_message!("\t+pin {:?} high", pin_num);
-        self.pins_o[pin_num as usize]
-            .as_mut()
-            .map(|pin| {
+        stop_timed_outputs(pin_num);
+        if let Some(pin) = self.pins_od[pin_num].as_mut() {
+            // Release the line
+            pin.set_output_enable_override(OutputEnableOverride::Disable);
+            return Ok(());
+        }
+        match self.pins_o[pin_num].as_mut() {
+            Some(pin) => {
                 pin.set_high().unwrap();
-            })
-            .ok_or_else(|| {
-                print_debug_message!("\t!!!pin {:?} not available", pin_num);
-                "Pin not available"
-            })?;
-        Ok(())
+                Ok(())
+            }
+            None => Err(self.not_an_output_error(pin_num)),
+        }
     }
 
-    /// Process a request, main entry point
+    /// Bitmask of the pins available on the device, bit n is GPIO n
     ///
-    pub fn process_request(
-        &mut self,
-        serial: &mut SerialPort<rp2040_hal::usb::UsbBus>,
-        request: PicohaDioRequest,
-    ) {
-        //
-        // Debug log
-        print_debug_message!("+ processing request: {:?}", request);
-
-        //
-        // Choose the correct process function
-        match request.r#type {
-            femtopb::EnumValue::Known(k) => match k {
-                crate::api_dio::RequestType::Ping => Self::process_request_ping(serial),
-                crate::api_dio::RequestType::SetPinDirection => {
-                    self.process_request_set_pin_direction(serial, request)
-                }
-                crate::api_dio::RequestType::SetPinValue => {
-                    self.process_request_set_pin_value(serial, request)
-                }
-                crate::api_dio::RequestType::GetPinDirection => {
-                    self.process_request_get_pin_direction(serial, request)
-                }
-                crate::api_dio::RequestType::GetPinValue => {
-                    self.process_request_get_pin_value(serial, request)
-                }
-            },
-            femtopb::EnumValue::Unknown(_) => todo!(),
-        }
+    fn available_pins_mask(&self) -> u32 {
+        mask_of(&self.pins_id)
     }
 
-    /// Process a ping request
+    /// Check that all the pins of a port mask can be used
     ///
-    fn process_request_ping(serial: &mut SerialPort<rp2040_hal::usb::UsbBus>) {
-        print_debug_message!(b"\t* processing request: PING\r\n");
-        let mut answer = PicohaDioAnswer::default();
-        answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
-        Self::send_answer(serial, answer);
+    fn check_port_mask(&self, mask: u32) -> Result<(), ErrorCode> {
+        let unavailable = mask & !self.available_pins_mask();
+        if unavailable != 0 {
+            // Report the first unavailable pin like a single pin request would
+            return self.check_pin(unavailable.trailing_zeros()).map(|_| ());
+        }
+        Ok(())
     }
 
-    /// Process a set pin direction request
+    /// Set the level of several output pins in the same cycle
     ///
-    fn process_request_set_pin_direction(
-        &mut self,
-        serial: &mut SerialPort<rp2040_hal::usb::UsbBus>,
-        request: PicohaDioRequest,
-    ) {
-        print_debug_message!(b"      * processing request: SET_PIN_DIRECTION\r\n");
-
-        match request.value {
-            femtopb::EnumValue::Known(v) => match v {
-                crate::api_dio::PinValue::Input => self.set_pin_as_input(request.pin_num as usize),
-                crate::api_dio::PinValue::Output => {
-                    self.set_pin_as_output(request.pin_num as usize)
-                }
-                _ => {
-                    print_debug_message!("      * invalid value: {:?}", v);
+    /// Open-drain pins are driven through their output enable override, which cannot be
+    /// changed for several pins at once, so they are rejected
+    ///
+    fn set_port_value(&mut self, mask: u32, value: u32) -> Result<(), ErrorCode> {
+        print_debug_message!("\t+port {:#010x} value {:#010x}", mask, value);
+        self.check_port_mask(mask)?;
+        for pin in pins_in_mask(mask) {
+            if self.pins_o[pin].is_none() {
+                if self.pins_od[pin].is_some() {
+                    return Err(ErrorCode::WrongDirection);
                 }
-            },
-            femtopb::EnumValue::Unknown(_) => todo!(),
+                return Err(self.not_an_output_error(pin));
+            }
         }
 
-        let mut answer = PicohaDioAnswer::default();
-        answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
-        Self::send_answer(serial, answer);
+        // The port value replaces the pulses and the sequence of its pins
+        for pin in pins_in_mask(mask) {
+            stop_timed_outputs(pin);
+        }
+
+        // A single xor write flips every pin that must change
+        let sio = unsafe { &*rp2040_hal::pac::SIO::ptr() };
+        let out = sio.gpio_out().read().bits();
+        sio.gpio_out_xor()
+            .write(|w| unsafe { w.bits((out ^ value) & mask) });
+        Ok(())
     }
 
-    /// Process a set pin value request
+    /// Set the direction of several pins in the same cycle
     ///
-    fn process_request_set_pin_value(
-        &mut self,
-        serial: &mut SerialPort<rp2040_hal::usb::UsbBus>,
-        request: PicohaDioRequest,
-    ) {
-        //
-        // Debug log
-        print_debug_message!(b"\tprocessing request: SET_PIN_VALUE\r\n");
+    fn set_port_direction(&mut self, mask: u32, value: u32) -> Result<(), ErrorCode> {
+        print_debug_message!("\t+port {:#010x} direction {:#010x}", mask, value);
+        self.check_port_mask(mask)?;
 
-        //
-        // Process the request
-        let r = match request.value {
-            femtopb::EnumValue::Known(v) => match v {
-                crate::api_dio::PinValue::Low => self.set_pin_low(request.pin_num),
-                crate::api_dio::PinValue::High => self.set_pin_high(request.pin_num),
-                _ => {
-                    print_debug_message!("\t!!! invalid value: {:?}", v);
-                    Err("Invalid value")
-                }
-            },
-            femtopb::EnumValue::Unknown(_) => todo!(),
-        };
+        // Switch all the output enables with a single xor write, the conversions below
+        // then find the pins already in their new direction
+        let sio = unsafe { &*rp2040_hal::pac::SIO::ptr() };
+        let oe = sio.gpio_oe().read().bits();
+        sio.gpio_oe_xor()
+            .write(|w| unsafe { w.bits((oe ^ value) & mask) });
 
-        let mut answer = PicohaDioAnswer::default();
-        match r {
-            Ok(_) => {
-                answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
-            }
-            Err(e) => {
-                answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Failure);
-                answer.error_message = Some(e);
+        for pin in pins_in_mask(mask) {
+            if value & (1 << pin) != 0 {
+                self.set_pin_as_output(pin)?;
+            } else {
+                self.set_pin_as_input(pin)?;
             }
         }
-        Self::send_answer(serial, answer);
+        Ok(())
     }
 
+    /// Take a snapshot of all the pins
     ///
-    /// This function process an incoming request to get a pin direction
+    fn get_port_state(&self) -> PortState<'static> {
+        // Each register is read once, so all the pad levels are sampled in the same cycle
+        let sio = unsafe { &*rp2040_hal::pac::SIO::ptr() };
+        let input_level = sio.gpio_in().read().bits();
+        let output_latch = sio.gpio_out().read().bits();
+
+        let available = self.available_pins_mask();
+        PortState {
+            available,
+            input: mask_of(&self.pins_i),
+            output: mask_of(&self.pins_o),
+            open_drain: mask_of(&self.pins_od),
+            pwm: mask_of(&self.pins_pwm),
+            pattern: mask_of(&self.pins_pattern),
+            input_level: edge_events::debounced_levels(input_level) & available,
+            output_latch: output_latch & available,
+            ..Default::default()
+        }
+    }
+
+    /// Load sequence steps, all the pins of their masks must be available
     ///
-    fn process_request_get_pin_direction(
+    fn load_sequence(&self, request: &LoadSequenceRequest) -> Result<(), ErrorCode> {
+        if dio_sequence::is_running() {
+            return Err(ErrorCode::ResourceBusy);
+        }
+        for step in request.steps.iter() {
+            self.check_port_mask(step.mask)?;
+        }
+        let steps = request.steps.iter().map(|step| Step {
+            offset_us: step.offset_us,
+            mask: step.mask,
+            levels: step.levels,
+        });
+        match dio_sequence::load(steps, request.append) {
+            true => Ok(()),
+            false => Err(ErrorCode::InvalidValue),
+        }
+    }
+
+    /// Start the sequence on pins set as output
+    ///
+    fn start_sequence(&self, loop_period_us: u32) -> Result<(), ErrorCode> {
+        if dio_sequence::is_running() {
+            return Err(ErrorCode::ResourceBusy);
+        }
+
+        //
+        // The steps write the SIO outputs, open-drain pins would be driven high
+        for pin in pins_in_mask(dio_sequence::driven_pins()) {
+            if self.pins_o[pin].is_none() {
+                if self.pins_od[pin].is_some() {
+                    return Err(ErrorCode::WrongDirection);
+                }
+                return Err(self.not_an_output_error(pin));
+            }
+            if dio_pulse::is_running(pin) {
+                return Err(ErrorCode::ResourceBusy);
+            }
+        }
+
+        match dio_sequence::start(loop_period_us) {
+            true => Ok(()),
+            false => Err(ErrorCode::InvalidValue),
+        }
+    }
+
+    /// Check that a range of consecutive pins can be used
+    ///
+    fn check_pin_range(&self, first_pin: u32, pin_count: u32) -> Result<(usize, usize), ErrorCode> {
+        let (first_pin, pin_count) = (first_pin as usize, pin_count as usize);
+        if pin_count == 0 || first_pin.saturating_add(pin_count) > 32 {
+            return Err(ErrorCode::InvalidValue);
+        }
+        self.check_port_mask((((1u64 << pin_count) - 1) << first_pin) as u32)?;
+        Ok((first_pin, pin_count))
+    }
+
+    /// Start a capture on a range of available pins
+    ///
+    fn start_capture(&mut self, request: &StartCaptureRequest) -> Result<(), ErrorCode> {
+        if self.capture.is_running() {
+            return Err(ErrorCode::ResourceBusy);
+        }
+        let (first_pin, pin_count) = self.check_pin_range(request.first_pin, request.pin_count)?;
+
+        let trigger = match request.trigger {
+            femtopb::EnumValue::Known(CaptureTrigger::Now) => Trigger::Now,
+            femtopb::EnumValue::Known(CaptureTrigger::Level) => Trigger::Level,
+            femtopb::EnumValue::Known(CaptureTrigger::Edge) => Trigger::Edge,
+            femtopb::EnumValue::Unknown(_) => return Err(ErrorCode::InvalidValue),
+        };
+        let setting = CaptureSetting {
+            first_pin,
+            pin_count,
+            sample_rate_hz: request.sample_rate_hz,
+            pre_trigger_samples: request.pre_trigger_samples,
+            post_trigger_samples: request.post_trigger_samples,
+            trigger,
+            trigger_mask: request.trigger_mask,
+            trigger_value: request.trigger_value,
+        };
+        match self.capture.start(setting) {
+            true => Ok(()),
+            false => Err(ErrorCode::InvalidValue),
+        }
+    }
+
+    /// Load pattern samples for a range of available pins
+    ///
+    fn load_pattern(&mut self, request: &LoadPatternRequest) -> Result<(), ErrorCode> {
+        if self.pattern.is_running() {
+            return Err(ErrorCode::ResourceBusy);
+        }
+        let (first_pin, pin_count) = self.check_pin_range(request.first_pin, request.pin_count)?;
+        match self
+            .pattern
+            .load(first_pin, pin_count, request.data, request.append)
+        {
+            true => Ok(()),
+            false => Err(ErrorCode::InvalidValue),
+        }
+    }
+
+    /// Start the pattern on pins set as output, the pattern generator drives them until
+    /// the pattern stops
+    ///
+    fn start_pattern(&mut self, request: &StartPatternRequest) -> Result<(), ErrorCode> {
+        if self.pattern.is_running() {
+            return Err(ErrorCode::ResourceBusy);
+        }
+        let pins = self.pattern.pins();
+        for pin in pins_in_mask(pins) {
+            if self.pins_o[pin].is_none() {
+                if self.pins_od[pin].is_some() {
+                    return Err(ErrorCode::WrongDirection);
+                }
+                return Err(self.not_an_output_error(pin));
+            }
+            if dio_pulse::is_running(pin) || dio_sequence::is_driving(pin) {
+                return Err(ErrorCode::ResourceBusy);
+            }
+        }
+        if !self.pattern.start(request.sample_rate_hz, request.looping) {
+            return Err(ErrorCode::InvalidValue);
+        }
+
+        //
+        // The first sample is already written by the state machine, the pins switch to it
+        // from their SIO level
+        for pin in pins_in_mask(pins) {
+            let dyn_id = self.pins_id[pin].ok_or(ErrorCode::InvalidPin)?;
+            match unsafe { new_pin(dyn_id) }.try_into_function::<rp2040_hal::gpio::FunctionPio1>() {
+                Ok(mut pin_pattern) => {
+                    self.pins_o[pin] = None;
+                    pin_pattern.set_output_enable_override(OutputEnableOverride::Enable);
+                    self.pins_pattern[pin] = Some(pin_pattern);
+                }
+                Err(_) => {
+                    print_debug_message!("      * error converting pin {:?} to pattern", pin);
+                    self.stop_pattern();
+                    return Err(ErrorCode::InvalidPin);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop the pattern and give its pins back as outputs
+    ///
+    /// The output latches take the level of the last sample, so the pins do not change
+    ///
+    fn stop_pattern(&mut self) {
+        self.pattern.stop();
+        let pins = mask_of(&self.pins_pattern);
+        self.pins_pattern = [PINPATTERN_NONE; MAX_PINS];
+
+        let sio = unsafe { &*rp2040_hal::pac::SIO::ptr() };
+        let levels = sio.gpio_in().read().bits();
+        let out = sio.gpio_out().read().bits();
+        sio.gpio_out_xor()
+            .write(|w| unsafe { w.bits((out ^ levels) & pins) });
+        for pin in pins_in_mask(pins) {
+            self.set_pin_as_output(pin).ok();
+        }
+    }
+
+    /// Register a wait request on available pins
+    ///
+    fn start_wait(&mut self, request: &WaitForRequest) -> Result<(), ErrorCode> {
+        if request.mask == 0 || request.value & !request.mask != 0 {
+            return Err(ErrorCode::InvalidValue);
+        }
+        self.check_port_mask(request.mask)?;
+        let edge = match request.condition {
+            femtopb::EnumValue::Known(WaitCondition::Level) => false,
+            femtopb::EnumValue::Known(WaitCondition::Edge) => true,
+            femtopb::EnumValue::Unknown(_) => return Err(ErrorCode::InvalidValue),
+        };
+
+        let slot = self
+            .pending_waits
+            .iter_mut()
+            .find(|wait| wait.is_none())
+            .ok_or(ErrorCode::ResourceBusy)?;
+        *slot = Some(PendingWait {
+            echo: self.echo,
+            mask: request.mask,
+            value: request.value,
+            edge,
+            armed: false,
+            started_at: device_time::now_us(),
+            timeout_us: request.timeout_us,
+        });
+        Ok(())
+    }
+
+    /// Check that the answer of a new request can be queued whole
+    ///
+    /// The requests wait while the host does not read the answers, none is dropped
+    ///
+    pub fn can_accept_request(&self) -> bool {
+        TX_BACKLOG_SIZE - self.tx_backlog.len() >= MAX_FRAME_SIZE
+    }
+
+    /// Process a protocol v1 request, main entry point for v1 clients
+    ///
+    pub fn process_request(&mut self, serial: &mut DioSerialPort, request: PicohaDioRequest) {
+        //
+        // Debug log
+        print_debug_message!("+ processing request: {:?}", request);
+
+        // Any valid frame is a heartbeat, the silence starts again once it is processed
+        self.failsafe.frame_received(device_time::now_us());
+
+        //
+        // Convert the request into its v2 payload, then process it like a v2 request
+        match api_dio_utils::request_v1_to_payload(&request) {
+            Ok(payload) => self.process_payload(serial, request.request_id, payload),
+            Err(e) => {
+                print_debug_message!("      * invalid v1 request: {:?}", e);
+                self.echo = RequestEcho {
+                    request_id: request.request_id,
+                    request_type: match request.r#type {
+                        femtopb::EnumValue::Known(t) => Some(t),
+                        femtopb::EnumValue::Unknown(_) => None,
+                    },
+                    pin_num: Some(request.pin_num),
+                };
+                self.send_answer(serial, Self::answer_from_result(Err(e)));
+            }
+        }
+
+        // Long requests, like a frequency measure or a flash write, do not count as silence
+        self.failsafe.frame_received(device_time::now_us());
+    }
+
+    /// Process a protocol v2 request, main entry point for v2 clients
+    ///
+    pub fn process_request_v2(&mut self, serial: &mut DioSerialPort, request: PicohaDioRequestV2) {
+        //
+        // Debug log
+        print_debug_message!("+ processing request: {:?}", request);
+
+        // Any valid frame is a heartbeat, the silence starts again once it is processed
+        self.failsafe.frame_received(device_time::now_us());
+
+        match request.payload {
+            Some(mut payload) => match self.resolve_pin_name(request.pin_name, &mut payload) {
+                Ok(()) => self.process_payload(serial, request.request_id, payload),
+                Err(e) => {
+                    self.echo = RequestEcho {
+                        request_id: request.request_id,
+                        request_type: api_dio_utils::payload_request_type(&payload),
+                        pin_num: None,
+                    };
+                    self.send_answer(serial, Self::answer_from_result(Err(e)));
+                }
+            },
+            None => {
+                self.echo = RequestEcho {
+                    request_id: request.request_id,
+                    ..Default::default()
+                };
+                self.send_answer(
+                    serial,
+                    Self::answer_from_result(Err(ErrorCode::UnknownRequestType)),
+                );
+            }
+        }
+
+        // Long requests, like a frequency measure or a flash write, do not count as silence
+        self.failsafe.frame_received(device_time::now_us());
+    }
+
+    /// Answer a frame that could not be decoded as a request
+    ///
+    pub fn process_decode_failure(&mut self, serial: &mut DioSerialPort) {
+        print_debug_message!(b"+ answering decode failure\r\n");
+        self.echo = RequestEcho::default();
+        self.send_answer(
+            serial,
+            Self::answer_from_result(Err(ErrorCode::DecodeFailure)),
+        );
+    }
+
+    /// Choose the correct process function for a request payload
+    ///
+    fn process_payload(
         &mut self,
-        serial: &mut SerialPort<rp2040_hal::usb::UsbBus>,
-        request: PicohaDioRequest,
+        serial: &mut DioSerialPort,
+        request_id: Option<u32>,
+        payload: Payload,
     ) {
+        self.received_at = device_time::now_us();
+
+        // Pulses cancelled by this request are answered before it
+        self.process_pulse_completions(serial);
+
+        self.echo = RequestEcho {
+            request_id,
+            request_type: api_dio_utils::payload_request_type(&payload),
+            pin_num: api_dio_utils::payload_pin_num(&payload),
+        };
+
+        match payload {
+            Payload::Batch(r) => self.process_request_batch(serial, r),
+            Payload::Pulse(r) if r.wait_completion => {
+                let answer = self.process_request_pulse(r);
+                match answer.r#type {
+                    femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success) => {
+                        // Answered by process_pulse_completions when the pulses end
+                        self.pending_pulses[r.pin_num as usize] = Some(self.echo);
+                    }
+                    _ => self.send_answer(serial, answer),
+                }
+            }
+            Payload::GetPinNames(_) => self.process_request_get_pin_names(serial),
+            Payload::WaitFor(r) => match self.start_wait(&r) {
+                // Answered by process_waits, right away when the levels already match
+                Ok(()) => self.process_waits(serial),
+                Err(e) => self.send_answer(serial, Self::answer_from_result(Err(e))),
+            },
+            other => {
+                let answer = self.execute_payload(other);
+                self.send_answer(serial, answer);
+            }
+        }
+    }
+
+    /// Execute a single request payload and build its answer
+    ///
+    fn execute_payload(&mut self, payload: Payload) -> PicohaDioAnswer<'static> {
+        let mut answer = match payload {
+            Payload::Ping(_) => self.process_request_ping(),
+            Payload::SetPinDirection(r) => self.process_request_set_pin_direction(r),
+            Payload::SetPinValue(r) => self.process_request_set_pin_value(r),
+            Payload::GetPinDirection(r) => self.process_request_get_pin_direction(r),
+            Payload::GetPinValue(r) => self.process_request_get_pin_value(r),
+            Payload::SetPinPull(r) => self.process_request_set_pin_pull(r),
+            Payload::GetPinPull(r) => self.process_request_get_pin_pull(r),
+            Payload::SetPinDrive(r) => self.process_request_set_pin_drive(r),
+            Payload::GetPinDrive(r) => self.process_request_get_pin_drive(r),
+            Payload::SetPortValue(r) => self.process_request_set_port_value(r),
+            Payload::SetPortDirection(r) => self.process_request_set_port_direction(r),
+            Payload::GetPortState(_) => self.process_request_get_port_state(),
+            Payload::SubscribeEdges(r) => self.process_request_subscribe_edges(r),
+            Payload::SetPinDebounce(r) => self.process_request_set_pin_debounce(r),
+            Payload::GetPinDebounce(r) => self.process_request_get_pin_debounce(r),
+            Payload::ConfigureEdgeCounter(r) => self.process_request_configure_edge_counter(r),
+            Payload::ReadEdgeCounter(r) => self.process_request_read_edge_counter(r),
+            Payload::ResetEdgeCounter(r) => self.process_request_reset_edge_counter(r),
+            Payload::MeasureFrequency(r) => self.process_request_measure_frequency(r),
+            Payload::SetPinPwm(r) => self.process_request_set_pin_pwm(r),
+            Payload::SetPinPwmDuty(r) => self.process_request_set_pin_pwm_duty(r),
+            Payload::Pulse(r) => self.process_request_pulse(r),
+            Payload::LoadSequence(r) => self.process_request_load_sequence(r),
+            Payload::StartSequence(r) => self.process_request_start_sequence(r),
+            Payload::StopSequence(_) => self.process_request_stop_sequence(),
+            Payload::GetSequenceState(_) => self.process_request_get_sequence_state(),
+            Payload::StartCapture(r) => self.process_request_start_capture(r),
+            Payload::StopCapture(_) => self.process_request_stop_capture(),
+            Payload::GetCaptureState(_) => self.process_request_get_capture_state(),
+            Payload::ReadCapture(r) => self.process_request_read_capture(r),
+            Payload::LoadPattern(r) => self.process_request_load_pattern(r),
+            Payload::StartPattern(r) => self.process_request_start_pattern(r),
+            Payload::StopPattern(_) => self.process_request_stop_pattern(),
+            Payload::GetPatternState(_) => self.process_request_get_pattern_state(),
+            Payload::SyncTime(r) => self.process_request_sync_time(r),
+            Payload::SetPinSafeState(r) => self.process_request_set_pin_safe_state(r),
+            Payload::ConfigureFailsafe(r) => self.process_request_configure_failsafe(r),
+            Payload::GetFailsafeState(r) => self.process_request_get_failsafe_state(r),
+            Payload::SaveConfig(_) => self.process_request_save_config(),
+            Payload::GetSavedConfig(r) => self.process_request_get_saved_config(r),
+            Payload::EraseConfig(_) => self.process_request_erase_config(),
+            Payload::RestoreDefaults(_) => self.process_request_restore_defaults(),
+            Payload::SetPinName(r) => self.process_request_set_pin_name(r),
+            // Batches cannot be nested, waits are answered later, the name table does not fit
+            // in a batch result
+            Payload::Batch(_)
+            | Payload::WaitFor(_)
+            | Payload::GetPinNames(_)
+            | Payload::_Phantom(_) => Self::answer_from_result(Err(ErrorCode::UnknownRequestType)),
+        };
+
+        // The operation took effect when its handler returned
+        answer.timestamp_us = Some(device_time::now_us());
+        answer
+    }
+
+    /// Process a batch request
+    ///
+    /// Operations are executed in order and answered all together, the answer fails with the
+    /// error of the first failed operation
+    ///
+    fn process_request_batch(&mut self, serial: &mut DioSerialPort, request: BatchRequest) {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: BATCH\r\n");
+
+        //
+        // Prepare a default answer
+        let mut answer = PicohaDioAnswer::default();
+        answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
+
+        //
+        // Reject the whole batch if its results cannot be stored
+        if request.operations.iter().count() > MAX_BATCH_OPERATIONS {
+            Self::set_answer_failure(&mut answer, ErrorCode::InvalidValue);
+            self.send_answer(serial, answer);
+            return;
+        }
+
+        //
+        // Execute the operations
+        let mut results: heapless::Vec<BatchOperationResult, MAX_BATCH_OPERATIONS> =
+            heapless::Vec::new();
+        for operation in request.operations.iter() {
+            // The answer would be dropped if it did not fit in the buffer
+            if !Self::batch_answer_fits(&results) {
+                print_debug_message!("      * batch answer full after {:?}", results.len());
+                Self::set_answer_failure(&mut answer, ErrorCode::AnswerTooLarge);
+                break;
+            }
+
+            let op_answer = match operation
+                .operation
+                .and_then(api_dio_utils::batch_operation_to_payload)
+            {
+                Some(mut payload) => {
+                    match self.resolve_pin_name(operation.pin_name, &mut payload) {
+                        Ok(()) => self.execute_payload(payload),
+                        Err(e) => Self::answer_from_result(Err(e)),
+                    }
+                }
+                None => Self::answer_from_result(Err(ErrorCode::UnknownRequestType)),
+            };
+
+            let failed =
+                op_answer.r#type == femtopb::EnumValue::Known(crate::api_dio::AnswerType::Failure);
+
+            // Cannot overflow, the number of operations has been checked
+            results
+                .push(BatchOperationResult {
+                    r#type: op_answer.r#type,
+                    error_code: op_answer.error_code,
+                    direction: op_answer.direction,
+                    level: op_answer.level,
+                    pull: op_answer.pull,
+                    drive_strength: op_answer.drive_strength,
+                    slew_rate: op_answer.slew_rate,
+                    port_state: op_answer.port_state,
+                    debounce_us: op_answer.debounce_us,
+                    edge_count: op_answer.edge_count,
+                    counted_edge: op_answer.counted_edge,
+                    frequency: op_answer.frequency,
+                    pwm: op_answer.pwm,
+                    sequence: op_answer.sequence,
+                    capture: op_answer.capture,
+                    pattern: op_answer.pattern,
+                    timestamp_us: op_answer.timestamp_us,
+                    failsafe: op_answer.failsafe,
+                    saved_config: op_answer.saved_config,
+                    ..Default::default()
+                })
+                .ok();
+
+            if failed {
+                print_debug_message!("      * operation {:?} failed", results.len() - 1);
+                if answer.error_code.is_none() {
+                    let error = match op_answer.error_code {
+                        Some(femtopb::EnumValue::Known(e)) => e,
+                        _ => ErrorCode::None,
+                    };
+                    Self::set_answer_failure(&mut answer, error);
+                }
+                if request.stop_on_failure {
+                    break;
+                }
+            }
+        }
+
+        //
+        // Send back the message
+        answer.batch_results = femtopb::repeated::Repeated::from_slice(&results);
+        self.send_answer(serial, answer);
+    }
+
+    /// Check that a batch answer holding these results has room for one more result
+    ///
+    fn batch_answer_fits(results: &[BatchOperationResult]) -> bool {
+        let mut answer = PicohaDioAnswer::default();
+        answer.batch_results = femtopb::repeated::Repeated::from_slice(results);
+        answer.encoded_len() + BATCH_ANSWER_MARGIN <= MAX_ANSWER_SIZE
+    }
+
+    /// Process a ping request
+    ///
+    fn process_request_ping(&self) -> PicohaDioAnswer<'static> {
+        print_debug_message!(b"\t* processing request: PING\r\n");
+        Self::answer_from_result(Ok(()))
+    }
+
+    /// Process a set pin direction request
+    ///
+    fn process_request_set_pin_direction(
+        &mut self,
+        request: SetPinDirectionRequest,
+    ) -> PicohaDioAnswer<'static> {
+        print_debug_message!(b"      * processing request: SET_PIN_DIRECTION\r\n");
+
+        let r = self
+            .check_pin(request.pin_num)
+            .and_then(|pin| match request.direction {
+                femtopb::EnumValue::Known(d) => match d {
+                    Direction::Input => self.set_pin_as_input(pin),
+                    Direction::Output => self.set_pin_as_output(pin),
+                    Direction::OpenDrain => self.set_pin_as_open_drain(pin),
+                    // The PWM mode needs a frequency, see SetPinPwmRequest
+                    Direction::Pwm => Err(ErrorCode::InvalidValue),
+                    // Pins enter the pattern mode with StartPatternRequest
+                    Direction::Pattern => Err(ErrorCode::InvalidValue),
+                },
+                femtopb::EnumValue::Unknown(_) => Err(ErrorCode::InvalidValue),
+            });
+
+        Self::answer_from_result(r)
+    }
+
+    /// Process a set pin value request
+    ///
+    fn process_request_set_pin_value(
+        &mut self,
+        request: SetPinValueRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"\tprocessing request: SET_PIN_VALUE\r\n");
+
+        //
+        // Process the request
+        let r = self
+            .check_pin(request.pin_num)
+            .and_then(|pin| match request.level {
+                femtopb::EnumValue::Known(l) => match l {
+                    Level::Low => self.set_pin_low(pin),
+                    Level::High => self.set_pin_high(pin),
+                },
+                femtopb::EnumValue::Unknown(_) => Err(ErrorCode::InvalidValue),
+            });
+
+        Self::answer_from_result(r)
+    }
+
+    ///
+    /// This function process an incoming request to get a pin direction
+    ///
+    fn process_request_get_pin_direction(
+        &mut self,
+        request: GetPinDirectionRequest,
+    ) -> PicohaDioAnswer<'static> {
         // Debug log
         print_debug_message!(b"      * processing request: GET_PIN_DIRECTION\r\n");
 
@@ -354,36 +1438,51 @@ impl DioRequestProcessor {
         // Fill the return message
         // Success if the pin has a direction set
         // Failure if the pin is not already configured
-        match self.get_internal_pin_direction(request.pin_num as usize) {
-            Some(direction) => {
+        // Both the v1 value and the v2 direction are filled
+        let r = self.check_pin(request.pin_num).and_then(|pin| {
+            self.get_internal_pin_direction(pin)
+                .ok_or(ErrorCode::PinNotConfigured)
+        });
+        match r {
+            Ok(direction) => {
                 answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
-                match direction {
+                let (value, direction) = match direction {
                     PinDirection::input => {
                         print_debug_message!(b"      * input\r\n");
-                        answer.value =
-                            Some(femtopb::EnumValue::Known(crate::api_dio::PinValue::Input));
+                        (crate::api_dio::PinValue::Input, Direction::Input)
                     }
                     PinDirection::output => {
                         print_debug_message!(b"      * output\r\n");
-                        answer.value =
-                            Some(femtopb::EnumValue::Known(crate::api_dio::PinValue::Output));
+                        (crate::api_dio::PinValue::Output, Direction::Output)
                     }
-                }
-            }
-            None => {
-                answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Failure);
+                    PinDirection::open_drain => {
+                        print_debug_message!(b"      * open-drain\r\n");
+                        (crate::api_dio::PinValue::OpenDrain, Direction::OpenDrain)
+                    }
+                    PinDirection::pwm => {
+                        // v1 has no PWM value, the pin drives its line like an output
+                        print_debug_message!(b"      * pwm\r\n");
+                        (crate::api_dio::PinValue::Output, Direction::Pwm)
+                    }
+                    PinDirection::pattern => {
+                        print_debug_message!(b"      * pattern\r\n");
+                        (crate::api_dio::PinValue::Output, Direction::Pattern)
+                    }
+                };
+                answer.value = Some(femtopb::EnumValue::Known(value));
+                answer.direction = Some(femtopb::EnumValue::Known(direction));
             }
+            Err(e) => Self::set_answer_failure(&mut answer, e),
         }
 
-        // Send back the message
-        Self::send_answer(serial, answer);
+        // Return the message
+        answer
     }
 
     fn process_request_get_pin_value(
         &mut self,
-        serial: &mut SerialPort<rp2040_hal::usb::UsbBus>,
-        request: PicohaDioRequest,
-    ) {
+        request: GetPinValueRequest,
+    ) -> PicohaDioAnswer<'static> {
         //
         // Debug log
         print_debug_message!(b"      * processing request: GET_PIN_VALUE\r\n");
@@ -391,78 +1490,1359 @@ impl DioRequestProcessor {
         //
         // Prepare a default answer
         let mut answer = PicohaDioAnswer::default();
-        answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
 
         //
-        // Fill the return message
-        match self.get_internal_pin_value(request.pin_num as usize) {
-            Some(val) => {
+        // Fill the return message, both the v1 value and the v2 level
+        let r = self.check_pin(request.pin_num).and_then(|pin| {
+            self.get_internal_pin_value(pin)
+                .ok_or(ErrorCode::PinNotConfigured)
+        });
+        match r {
+            Ok(val) => {
                 answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
-                match val {
+                let (value, level) = match val {
                     PinValue::low => {
                         print_debug_message!(b"      * low\r\n");
-                        answer.value =
-                            Some(femtopb::EnumValue::Known(crate::api_dio::PinValue::Low));
+                        (crate::api_dio::PinValue::Low, Level::Low)
                     }
                     PinValue::high => {
                         print_debug_message!(b"      * high\r\n");
-                        answer.value =
-                            Some(femtopb::EnumValue::Known(crate::api_dio::PinValue::High));
+                        (crate::api_dio::PinValue::High, Level::High)
                     }
-                }
-            }
-            None => {
-                answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Failure);
+                };
+                answer.value = Some(femtopb::EnumValue::Known(value));
+                answer.level = Some(femtopb::EnumValue::Known(level));
             }
+            Err(e) => Self::set_answer_failure(&mut answer, e),
         }
 
         //
-        // Send back the message
-        Self::send_answer(serial, answer);
+        // Return the message
+        answer
     }
 
-    /// Send an answer
+    /// Process a set pin pull request
     ///
-    fn send_answer(serial: &mut SerialPort<rp2040_hal::usb::UsbBus>, answer: PicohaDioAnswer) {
-        let mut buffer = [0u8; 64];
-        let encoded_len = answer.encoded_len();
-        answer.encode(&mut buffer.as_mut()).unwrap();
+    fn process_request_set_pin_pull(
+        &mut self,
+        request: SetPinPullRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PIN_PULL\r\n");
 
-        print_debug_message!("      * answer: {:?}", answer);
-        // print_debug_message!("      * sending answer: {:?}", encoded_len);
-        // print_debug_message!("      * sending answer: {:?}", &buffer[..encoded_len]);
+        //
+        // Process the request
+        let r = self
+            .check_pin(request.pin_num)
+            .and_then(|pin| match request.pull {
+                femtopb::EnumValue::Known(p) => {
+                    self.set_pin_pull(pin, pull_from_api(p));
+                    Ok(())
+                }
+                femtopb::EnumValue::Unknown(_) => Err(ErrorCode::InvalidValue),
+            });
 
-        // Prepare encoding
-        let mut encoded_command = [0u8; 1024];
-        let mut slip_encoder = serial_line_ip::Encoder::new();
+        Self::answer_from_result(r)
+    }
 
-        // Encode the command
-        let mut totals = match slip_encoder.encode(&buffer[..encoded_len], &mut encoded_command) {
-            Ok(t) => t,
-            Err(e) => {
-                print_debug_message!("      * error encoding answer: {:?}", e);
-                return;
+    /// Process a get pin pull request
+    ///
+    fn process_request_get_pin_pull(
+        &mut self,
+        request: GetPinPullRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_PIN_PULL\r\n");
+
+        //
+        // Fill the return message
+        let mut answer = PicohaDioAnswer::default();
+        match self.check_pin(request.pin_num) {
+            Ok(pin) => {
+                answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
+                answer.pull = Some(femtopb::EnumValue::Known(pull_to_api(
+                    self.get_pin_pull(pin),
+                )));
             }
-        };
+            Err(e) => Self::set_answer_failure(&mut answer, e),
+        }
 
-        // Finalise the encoding
-        totals += match slip_encoder.finish(&mut encoded_command[totals.written..]) {
-            Ok(t) => t,
-            Err(e) => {
-                print_debug_message!("      * error encoding answer: {:?}", e);
-                return;
+        //
+        // Return the message
+        answer
+    }
+
+    /// Process a set pin drive request
+    ///
+    fn process_request_set_pin_drive(
+        &mut self,
+        request: SetPinDriveRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PIN_DRIVE\r\n");
+
+        //
+        // Process the request
+        let r = self.check_pin(request.pin_num).and_then(|pin| {
+            match (request.drive_strength, request.slew_rate) {
+                (femtopb::EnumValue::Known(d), femtopb::EnumValue::Known(sr)) => {
+                    self.set_pin_drive(pin, drive_from_api(d), slew_from_api(sr))
+                }
+                _ => Err(ErrorCode::InvalidValue),
             }
-        };
+        });
 
-        // print_debug_message!("      * sending answer: {:?}", totals.written);
-        print_debug_message!(
-            "      * sending answer: {:?}",
-            &encoded_command[..totals.written]
-        );
+        Self::answer_from_result(r)
+    }
 
-        match serial.write(&encoded_command[..totals.written]) {
-            Ok(_) => print_debug_message!(b"      * answer sent\r\n"),
-            Err(e) => print_debug_message!("      * answer not sent {:?}", e),
-        }
+    /// Process a get pin drive request
+    ///
+    fn process_request_get_pin_drive(
+        &mut self,
+        request: GetPinDriveRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_PIN_DRIVE\r\n");
+
+        //
+        // Fill the return message
+        let mut answer = PicohaDioAnswer::default();
+        match self
+            .check_pin(request.pin_num)
+            .and_then(|pin| self.get_pin_drive(pin))
+        {
+            Ok((strength, slew_rate)) => {
+                answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
+                answer.drive_strength = Some(femtopb::EnumValue::Known(drive_to_api(strength)));
+                answer.slew_rate = Some(femtopb::EnumValue::Known(slew_to_api(slew_rate)));
+            }
+            Err(e) => Self::set_answer_failure(&mut answer, e),
+        }
+
+        //
+        // Return the message
+        answer
+    }
+
+    /// Process a set port value request
+    ///
+    fn process_request_set_port_value(
+        &mut self,
+        request: SetPortValueRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PORT_VALUE\r\n");
+
+        let r = self.set_port_value(request.mask, request.value);
+        Self::answer_from_result(r)
+    }
+
+    /// Process a set port direction request
+    ///
+    fn process_request_set_port_direction(
+        &mut self,
+        request: SetPortDirectionRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PORT_DIRECTION\r\n");
+
+        let r = self.set_port_direction(request.mask, request.value);
+        Self::answer_from_result(r)
+    }
+
+    /// Process a get port state request
+    ///
+    fn process_request_get_port_state(&self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_PORT_STATE\r\n");
+
+        //
+        // Fill the return message
+        let mut answer = Self::answer_from_result(Ok(()));
+        answer.port_state = Some(self.get_port_state());
+        answer
+    }
+
+    /// Process a subscribe edges request
+    ///
+    fn process_request_subscribe_edges(
+        &mut self,
+        request: SubscribeEdgesRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SUBSCRIBE_EDGES\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            let edges = edge_from_api(request.edge)?;
+
+            // Stopping the events is always allowed, starting them needs a pin that is not driven
+            if edges != EdgeSelection::None {
+                self.check_pin_not_driven(pin_num)?;
+            }
+
+            edge_events::subscribe(pin_num, edges);
+            Ok(())
+        });
+        Self::answer_from_result(r)
+    }
+
+    /// Process a set pin debounce request
+    ///
+    fn process_request_set_pin_debounce(
+        &mut self,
+        request: SetPinDebounceRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PIN_DEBOUNCE\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            if request.debounce_us > MAX_DEBOUNCE_US {
+                return Err(ErrorCode::InvalidValue);
+            }
+            if request.debounce_us > 0 {
+                self.check_pin_not_driven(pin_num)?;
+            }
+
+            edge_events::set_debounce(pin_num, request.debounce_us);
+            Ok(())
+        });
+        Self::answer_from_result(r)
+    }
+
+    /// Process a get pin debounce request
+    ///
+    fn process_request_get_pin_debounce(
+        &mut self,
+        request: GetPinDebounceRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_PIN_DEBOUNCE\r\n");
+
+        match self.check_pin(request.pin_num) {
+            Ok(pin_num) => {
+                let mut answer = Self::answer_from_result(Ok(()));
+                answer.debounce_us = Some(edge_events::debounce(pin_num));
+                answer
+            }
+            Err(e) => Self::answer_from_result(Err(e)),
+        }
+    }
+
+    /// Process a configure edge counter request
+    ///
+    fn process_request_configure_edge_counter(
+        &mut self,
+        request: ConfigureEdgeCounterRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: CONFIGURE_EDGE_COUNTER\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            let edges = edge_from_api(request.edge)?;
+
+            // Stopping the counter is always allowed, starting it needs a pin that is not driven
+            if edges != EdgeSelection::None {
+                self.check_pin_not_driven(pin_num)?;
+            }
+
+            edge_events::configure_counter(pin_num, edges);
+            Ok(())
+        });
+        Self::answer_from_result(r)
+    }
+
+    /// Process a read edge counter request
+    ///
+    fn process_request_read_edge_counter(
+        &mut self,
+        request: ReadEdgeCounterRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: READ_EDGE_COUNTER\r\n");
+
+        match self.check_pin(request.pin_num) {
+            Ok(pin_num) => {
+                let mut answer = Self::answer_from_result(Ok(()));
+                answer.edge_count = Some(edge_events::read_counter(pin_num, request.clear));
+                answer.counted_edge = Some(femtopb::EnumValue::Known(edge_to_api(
+                    edge_events::counted_edges(pin_num),
+                )));
+                answer
+            }
+            Err(e) => Self::answer_from_result(Err(e)),
+        }
+    }
+
+    /// Process a reset edge counter request
+    ///
+    fn process_request_reset_edge_counter(
+        &mut self,
+        request: ResetEdgeCounterRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: RESET_EDGE_COUNTER\r\n");
+
+        let r = self.check_pin(request.pin_num).map(|pin_num| {
+            edge_events::read_counter(pin_num, true);
+        });
+        Self::answer_from_result(r)
+    }
+
+    /// Process a measure frequency request
+    ///
+    fn process_request_measure_frequency(
+        &mut self,
+        request: MeasureFrequencyRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: MEASURE_FREQUENCY\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            if !dio_pwm::can_measure(pin_num) {
+                return Err(ErrorCode::NotSupported);
+            }
+            if request.gate_time_us == 0 || request.gate_time_us > MAX_GATE_TIME_US {
+                return Err(ErrorCode::InvalidValue);
+            }
+            if self.pwm_pins_on_slice(pin_num).next().is_some() {
+                return Err(ErrorCode::ResourceBusy);
+            }
+            let pin = match self.get_internal_pin_direction(pin_num) {
+                Some(PinDirection::input) => self.pins_i[pin_num].take(),
+                Some(_) => return Err(ErrorCode::WrongDirection),
+                None => return Err(ErrorCode::PinNotConfigured),
+            }
+            .ok_or(ErrorCode::PinNotConfigured)?;
+
+            //
+            // The slice reads the pad through the PWM function, the pin goes back to SIO after
+            let pin = match pin.try_into_function::<rp2040_hal::gpio::FunctionPwm>() {
+                Ok(pin) => pin,
+                Err(pin) => {
+                    self.pins_i[pin_num] = Some(pin);
+                    return Err(ErrorCode::NotSupported);
+                }
+            };
+            let measurement = dio_pwm::measure(pin_num, request.gate_time_us);
+            self.pins_i[pin_num] = pin.try_into_function().ok();
+            Ok(measurement)
+        });
+
+        match r {
+            Ok(measurement) => {
+                let mut answer = Self::answer_from_result(Ok(()));
+                answer.frequency = Some(FrequencyMeasurement {
+                    edge_count: measurement.edge_count,
+                    frequency_hz: measurement.frequency_hz,
+                    period_us: measurement.period_us,
+                    duty_cycle: measurement.duty_cycle,
+                    ..Default::default()
+                });
+                answer
+            }
+            Err(e) => Self::answer_from_result(Err(e)),
+        }
+    }
+
+    /// Process a set pin pwm request
+    ///
+    fn process_request_set_pin_pwm(
+        &mut self,
+        request: SetPinPwmRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PIN_PWM\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            self.set_pin_as_pwm(pin_num, request.frequency_hz, request.duty_cycle)
+                .map(|duty_cycle| (pin_num, duty_cycle))
+        });
+        Self::answer_from_pwm_result(r)
+    }
+
+    /// Process a set pin pwm duty request
+    ///
+    fn process_request_set_pin_pwm_duty(
+        &mut self,
+        request: SetPinPwmDutyRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PIN_PWM_DUTY\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            if self.pins_pwm[pin_num].is_none() {
+                return Err(match self.get_internal_pin_direction(pin_num) {
+                    Some(_) => ErrorCode::WrongDirection,
+                    None => ErrorCode::PinNotConfigured,
+                });
+            }
+            if !(0.0..=1.0).contains(&request.duty_cycle) {
+                return Err(ErrorCode::InvalidValue);
+            }
+            Ok((pin_num, dio_pwm::set_duty(pin_num, request.duty_cycle)))
+        });
+        Self::answer_from_pwm_result(r)
+    }
+
+    /// Process a pulse request
+    ///
+    /// The pulses are started, the answer does not wait for them
+    ///
+    fn process_request_pulse(&mut self, request: PulseRequest) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: PULSE\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            let drive = if self.pins_o[pin_num].is_some() {
+                PulseDrive::PushPull
+            } else if self.pins_od[pin_num].is_some() {
+                PulseDrive::OpenDrain
+            } else {
+                return Err(self.not_an_output_error(pin_num));
+            };
+            if dio_sequence::is_driving(pin_num) {
+                return Err(ErrorCode::ResourceBusy);
+            }
+
+            let count = request.repeat_count.max(1);
+            if request.duration_us == 0 || (count > 1 && request.period_us <= request.duration_us) {
+                return Err(ErrorCode::InvalidValue);
+            }
+            let active_high = match request.active_level {
+                femtopb::EnumValue::Known(Level::High) => true,
+                femtopb::EnumValue::Known(Level::Low) => false,
+                femtopb::EnumValue::Unknown(_) => return Err(ErrorCode::InvalidValue),
+            };
+
+            let train = PulseTrain {
+                drive,
+                active_high,
+                duration_us: request.duration_us,
+                period_us: request.period_us,
+                count,
+            };
+            match dio_pulse::start(pin_num, train) {
+                true => Ok(()),
+                false => Err(ErrorCode::ResourceBusy),
+            }
+        });
+        Self::answer_from_result(r)
+    }
+
+    /// Process a load sequence request
+    ///
+    fn process_request_load_sequence(
+        &mut self,
+        request: LoadSequenceRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: LOAD_SEQUENCE\r\n");
+
+        let r = self.load_sequence(&request);
+        Self::answer_from_sequence_result(r)
+    }
+
+    /// Process a start sequence request
+    ///
+    fn process_request_start_sequence(
+        &mut self,
+        request: StartSequenceRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: START_SEQUENCE\r\n");
+
+        let r = self.start_sequence(request.loop_period_us);
+        Self::answer_from_sequence_result(r)
+    }
+
+    /// Process a stop sequence request
+    ///
+    fn process_request_stop_sequence(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: STOP_SEQUENCE\r\n");
+
+        dio_sequence::stop();
+        Self::answer_from_sequence_result(Ok(()))
+    }
+
+    /// Process a get sequence state request
+    ///
+    fn process_request_get_sequence_state(&self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_SEQUENCE_STATE\r\n");
+
+        Self::answer_from_sequence_result(Ok(()))
+    }
+
+    /// Build the answer of a sequence request, with the state of the sequence
+    ///
+    fn answer_from_sequence_result(r: Result<(), ErrorCode>) -> PicohaDioAnswer<'static> {
+        let mut answer = Self::answer_from_result(r);
+        if r.is_ok() {
+            let status = dio_sequence::status();
+            answer.sequence = Some(SequenceState {
+                step_count: status.step_count as u32,
+                running: status.running,
+                completed_loops: status.completed_loops,
+                ..Default::default()
+            });
+        }
+        answer
+    }
+
+    /// Process a start capture request
+    ///
+    fn process_request_start_capture(
+        &mut self,
+        request: StartCaptureRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: START_CAPTURE\r\n");
+
+        let r = self.start_capture(&request);
+        self.answer_from_capture_result(r)
+    }
+
+    /// Process a stop capture request
+    ///
+    fn process_request_stop_capture(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: STOP_CAPTURE\r\n");
+
+        self.capture.stop();
+        self.answer_from_capture_result(Ok(()))
+    }
+
+    /// Process a get capture state request
+    ///
+    fn process_request_get_capture_state(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_CAPTURE_STATE\r\n");
+
+        // The state is up to date even if the main loop did not poll since the last request
+        self.capture.poll();
+        self.answer_from_capture_result(Ok(()))
+    }
+
+    /// Process a read capture request
+    ///
+    fn process_request_read_capture(
+        &mut self,
+        request: ReadCaptureRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: READ_CAPTURE\r\n");
+
+        match self.capture.chunk(request.offset as usize) {
+            Some(data) => {
+                let mut answer = Self::answer_from_result(Ok(()));
+                answer.capture_chunk = Some(CaptureChunk {
+                    offset: request.offset,
+                    data,
+                    data_size: self.capture.info().data_size as u32,
+                    ..Default::default()
+                });
+                answer
+            }
+            None => Self::answer_from_result(Err(ErrorCode::InvalidValue)),
+        }
+    }
+
+    /// Build the answer of a capture request, with the state of the capture
+    ///
+    fn answer_from_capture_result(&self, r: Result<(), ErrorCode>) -> PicohaDioAnswer<'static> {
+        let mut answer = Self::answer_from_result(r);
+        if r.is_ok() {
+            let info = self.capture.info();
+            answer.capture = Some(CaptureState {
+                status: femtopb::EnumValue::Known(capture_status_to_api(info.status)),
+                first_pin: info.first_pin as u32,
+                pin_count: info.pin_count as u32,
+                sample_width: info.sample_width,
+                sample_rate_hz: info.sample_rate_hz,
+                first_sample: info.first_sample,
+                sample_count: info.sample_count,
+                trigger_sample: info.trigger_sample,
+                data_size: info.data_size as u32,
+                samples_skipped: info.samples_skipped,
+                ..Default::default()
+            });
+        }
+        answer
+    }
+
+    /// Process a load pattern request
+    ///
+    fn process_request_load_pattern(
+        &mut self,
+        request: LoadPatternRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: LOAD_PATTERN\r\n");
+
+        let r = self.load_pattern(&request);
+        self.answer_from_pattern_result(r)
+    }
+
+    /// Process a start pattern request
+    ///
+    fn process_request_start_pattern(
+        &mut self,
+        request: StartPatternRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: START_PATTERN\r\n");
+
+        let r = self.start_pattern(&request);
+        self.answer_from_pattern_result(r)
+    }
+
+    /// Process a stop pattern request
+    ///
+    fn process_request_stop_pattern(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: STOP_PATTERN\r\n");
+
+        self.stop_pattern();
+        self.answer_from_pattern_result(Ok(()))
+    }
+
+    /// Process a get pattern state request
+    ///
+    fn process_request_get_pattern_state(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_PATTERN_STATE\r\n");
+
+        // Report a pattern that just ended as stopped
+        self.process_pattern();
+        self.answer_from_pattern_result(Ok(()))
+    }
+
+    /// Process a sync time request
+    ///
+    /// The answer timestamp is taken right after this, just before the answer is sent
+    ///
+    fn process_request_sync_time(&self, request: SyncTimeRequest) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SYNC_TIME\r\n");
+
+        let mut answer = Self::answer_from_result(Ok(()));
+        answer.time_sync = Some(TimeSync {
+            host_time_us: request.host_time_us,
+            received_us: self.received_at,
+            ..Default::default()
+        });
+        answer
+    }
+
+    /// Process a set pin safe state request
+    ///
+    fn process_request_set_pin_safe_state(
+        &mut self,
+        request: SetPinSafeStateRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PIN_SAFE_STATE\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            let state = match request.state {
+                femtopb::EnumValue::Known(s) => safe_state_from_api(s),
+                femtopb::EnumValue::Unknown(_) => return Err(ErrorCode::InvalidValue),
+            };
+            self.failsafe.set_safe_state(pin_num, state);
+            Ok(())
+        });
+        self.answer_from_failsafe_result(r)
+    }
+
+    /// Process a configure failsafe request
+    ///
+    fn process_request_configure_failsafe(
+        &mut self,
+        request: ConfigureFailsafeRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: CONFIGURE_FAILSAFE\r\n");
+
+        self.failsafe
+            .configure(request.heartbeat_timeout_ms, self.received_at);
+        self.answer_from_failsafe_result(Ok(()))
+    }
+
+    /// Process a get failsafe state request
+    ///
+    fn process_request_get_failsafe_state(
+        &mut self,
+        request: GetFailsafeStateRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_FAILSAFE_STATE\r\n");
+
+        // The answer reports the flags as they were before clearing them
+        let answer = self.answer_from_failsafe_result(Ok(()));
+        if request.clear {
+            self.failsafe.clear();
+        }
+        answer
+    }
+
+    /// Build the answer of a failsafe request, with the state of the failsafe
+    ///
+    fn answer_from_failsafe_result(&self, r: Result<(), ErrorCode>) -> PicohaDioAnswer<'static> {
+        let mut answer = Self::answer_from_result(r);
+        if r.is_ok() {
+            answer.failsafe = Some(FailsafeState {
+                heartbeat_timeout_ms: self.failsafe.heartbeat_timeout_ms(),
+                tripped: self.failsafe.tripped(),
+                tripped_at_us: self.failsafe.tripped_at_us(),
+                safe_pins: self.failsafe.safe_pins(),
+                session_closed: self.failsafe.session_closed(),
+                session_closed_at_us: self.failsafe.session_closed_at_us(),
+                ..Default::default()
+            });
+        }
+        answer
+    }
+
+    /// Process a save config request
+    ///
+    fn process_request_save_config(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SAVE_CONFIG\r\n");
+
+        let mut configs = dio_config_store::FACTORY_CONFIGS;
+        for pin_num in 0..MAX_PINS {
+            if self.pins_id[pin_num].is_some() {
+                configs[pin_num] = self.get_pin_config(pin_num);
+            }
+        }
+        let r = match dio_config_store::save(&configs) {
+            true => Ok(()),
+            false => Err(ErrorCode::StorageFailure),
+        };
+        Self::answer_from_result(r)
+    }
+
+    /// Process a get saved config request
+    ///
+    fn process_request_get_saved_config(
+        &mut self,
+        request: GetSavedConfigRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_SAVED_CONFIG\r\n");
+
+        match self.check_pin(request.pin_num) {
+            Ok(pin_num) => {
+                let saved = dio_config_store::load();
+                let config = saved.map_or(PinConfig::FACTORY, |configs| configs[pin_num]);
+                let mut answer = Self::answer_from_result(Ok(()));
+                answer.saved_config = Some(SavedPinConfig {
+                    saved: saved.is_some(),
+                    version: dio_config_store::CONFIG_VERSION as u32,
+                    direction: femtopb::EnumValue::Known(match config.mode {
+                        PinMode::Input => Direction::Input,
+                        PinMode::Output => Direction::Output,
+                        PinMode::OpenDrain => Direction::OpenDrain,
+                    }),
+                    level: femtopb::EnumValue::Known(match config.high {
+                        true => Level::High,
+                        false => Level::Low,
+                    }),
+                    pull: femtopb::EnumValue::Known(pull_to_api(config.pull)),
+                    drive_strength: femtopb::EnumValue::Known(drive_to_api(config.strength)),
+                    slew_rate: femtopb::EnumValue::Known(slew_to_api(config.slew_rate)),
+                    safe_state: femtopb::EnumValue::Known(safe_state_to_api(config.safe_state)),
+                    ..Default::default()
+                });
+                answer
+            }
+            Err(e) => Self::answer_from_result(Err(e)),
+        }
+    }
+
+    /// Process an erase config request
+    ///
+    fn process_request_erase_config(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: ERASE_CONFIG\r\n");
+
+        let r = match dio_config_store::erase() {
+            true => Ok(()),
+            false => Err(ErrorCode::StorageFailure),
+        };
+        Self::answer_from_result(r)
+    }
+
+    /// Process a restore defaults request
+    ///
+    fn process_request_restore_defaults(&mut self) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: RESTORE_DEFAULTS\r\n");
+
+        for pin_num in 0..MAX_PINS {
+            if self.pins_id[pin_num].is_some() {
+                self.apply_pin_config(pin_num, &PinConfig::FACTORY).ok();
+            }
+        }
+        Self::answer_from_result(Ok(()))
+    }
+
+    /// Process a set pin name request
+    ///
+    fn process_request_set_pin_name(
+        &mut self,
+        request: SetPinNameRequest,
+    ) -> PicohaDioAnswer<'static> {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: SET_PIN_NAME\r\n");
+
+        let r = self.check_pin(request.pin_num).and_then(|pin_num| {
+            if !dio_config_store::is_valid_name(request.name) {
+                return Err(ErrorCode::InvalidValue);
+            }
+            match self.pin_by_name(request.name) {
+                Ok(other) if other != pin_num => return Err(ErrorCode::InvalidValue),
+                _ => {}
+            }
+
+            // The names in use stay the saved ones if the flash cannot be written
+            let mut names = self.pin_names.clone();
+            names[pin_num].clear();
+            names[pin_num].push_str(request.name).ok();
+            if !dio_config_store::save_names(&names) {
+                return Err(ErrorCode::StorageFailure);
+            }
+            self.pin_names = names;
+            Ok(())
+        });
+        Self::answer_from_result(r)
+    }
+
+    /// Process a get pin names request, answered right away as the answer borrows the names
+    ///
+    fn process_request_get_pin_names(&mut self, serial: &mut DioSerialPort) {
+        //
+        // Debug log
+        print_debug_message!(b"      * processing request: GET_PIN_NAMES\r\n");
+
+        let pin_names = self.pin_names.clone();
+        let mut names: heapless::Vec<crate::api_dio::PinName, MAX_PINS> = heapless::Vec::new();
+        for (pin_num, name) in pin_names.iter().enumerate() {
+            if !name.is_empty() {
+                // Cannot overflow, there is at most one name per pin
+                names
+                    .push(crate::api_dio::PinName {
+                        pin_num: pin_num as u32,
+                        name: name.as_str(),
+                        ..Default::default()
+                    })
+                    .ok();
+            }
+        }
+
+        let mut answer: PicohaDioAnswer = Self::answer_from_result(Ok(()));
+        answer.pin_names = femtopb::repeated::Repeated::from_slice(&names);
+        self.send_answer(serial, answer);
+    }
+
+    /// Build the answer of a pattern request, with the state of the pattern
+    ///
+    fn answer_from_pattern_result(&self, r: Result<(), ErrorCode>) -> PicohaDioAnswer<'static> {
+        let mut answer = Self::answer_from_result(r);
+        if r.is_ok() {
+            let info = self.pattern.info();
+            answer.pattern = Some(PatternState {
+                first_pin: info.first_pin as u32,
+                pin_count: info.pin_count as u32,
+                sample_width: info.sample_width,
+                sample_count: info.sample_count,
+                running: info.running,
+                looping: info.looping,
+                sample_rate_hz: info.sample_rate_hz,
+                ..Default::default()
+            });
+        }
+        answer
+    }
+
+    /// Search the capture trigger and end the capture in the samples written since the last
+    /// call
+    ///
+    pub fn process_capture(&mut self) {
+        self.capture.poll();
+    }
+
+    /// Give the pins back once a pattern played once has ended
+    ///
+    pub fn process_pattern(&mut self) {
+        if self.pattern.poll() {
+            self.stop_pattern();
+        }
+    }
+
+    /// Put the pins in their safe state once the host has been silent for the heartbeat timeout
+    ///
+    /// The safe states are applied once per silence, the pulses they cancel are answered as
+    /// usual
+    ///
+    pub fn process_failsafe(&mut self) {
+        if !self.failsafe.check(device_time::now_us()) {
+            return;
+        }
+        print_debug_message!(b"+ failsafe tripped\r\n");
+        self.apply_safe_states();
+    }
+
+    /// End the session of the host, on a USB suspend, a bus reset or the port being closed
+    ///
+    /// The pins go to their safe state like when the failsafe trips, and the answers and
+    /// events of the session are dropped so the next session starts fresh
+    ///
+    pub fn close_session(&mut self) {
+        print_debug_message!(b"+ session closed\r\n");
+        self.failsafe.close_session(device_time::now_us());
+        self.apply_safe_states();
+
+        // Nobody is left to read them
+        self.pending_pulses = [None; MAX_PINS];
+        self.pending_waits = [None; MAX_PENDING_WAITS];
+        for pin_num in 0..MAX_PINS {
+            edge_events::subscribe(pin_num, EdgeSelection::None);
+        }
+        while edge_events::pop_event().is_some() {}
+        self.tx_backlog.clear();
+    }
+
+    /// Put every pin with a safe state in it
+    ///
+    fn apply_safe_states(&mut self) {
+        for pin_num in 0..MAX_PINS {
+            if self.pins_id[pin_num].is_none() {
+                continue;
+            }
+            // Pins failing to change keep their state, the others are still made safe
+            match self.failsafe.safe_state(pin_num) {
+                dio_failsafe::SafeState::None => Ok(()),
+                dio_failsafe::SafeState::Input => self.set_pin_as_input(pin_num),
+                dio_failsafe::SafeState::Low => self
+                    .set_pin_as_output(pin_num)
+                    .and_then(|_| self.set_pin_low(pin_num)),
+                dio_failsafe::SafeState::High => self
+                    .set_pin_as_output(pin_num)
+                    .and_then(|_| self.set_pin_high(pin_num)),
+            }
+            .ok();
+        }
+    }
+
+    /// Answer the pulse requests waiting for pulses that ended since the last call
+    ///
+    pub fn process_pulse_completions(&mut self, serial: &mut DioSerialPort) {
+        for pin_num in 0..MAX_PINS {
+            // Answered at a later call once the host read the previous answers
+            if !self.can_accept_request() {
+                break;
+            }
+            if self.pending_pulses[pin_num].is_none() || dio_pulse::is_running(pin_num) {
+                continue;
+            }
+            if let Some(echo) = self.pending_pulses[pin_num].take() {
+                let r = match dio_pulse::take_completed(pin_num) {
+                    true => Ok(()),
+                    false => Err(ErrorCode::Cancelled),
+                };
+                self.echo = echo;
+                self.send_answer(serial, Self::answer_from_result(r));
+            }
+        }
+    }
+
+    /// Answer the wait requests whose condition is met or whose timeout expired
+    ///
+    /// The levels are checked once per call, like the port state they are debounced
+    ///
+    pub fn process_waits(&mut self, serial: &mut DioSerialPort) {
+        if self.pending_waits.iter().all(|wait| wait.is_none()) {
+            return;
+        }
+        let sio = unsafe { &*rp2040_hal::pac::SIO::ptr() };
+        let levels = edge_events::debounced_levels(sio.gpio_in().read().bits());
+        let now = device_time::now_us();
+
+        for n in 0..MAX_PENDING_WAITS {
+            // Answered at a later call once the host read the previous answers
+            if !self.can_accept_request() {
+                break;
+            }
+            let Some(mut wait) = self.pending_waits[n] else {
+                continue;
+            };
+            let matched = levels & wait.mask == wait.value;
+            let met = matched && (!wait.edge || wait.armed);
+            let elapsed_us = now - wait.started_at;
+            if !met && elapsed_us < wait.timeout_us as u64 {
+                wait.armed = !matched;
+                self.pending_waits[n] = Some(wait);
+                continue;
+            }
+
+            self.pending_waits[n] = None;
+            let r = match met {
+                true => Ok(()),
+                false => Err(ErrorCode::Timeout),
+            };
+            self.echo = wait.echo;
+            let mut answer = Self::answer_from_result(r);
+            answer.timestamp_us = Some(now);
+            answer.wait = Some(WaitResult {
+                elapsed_us: elapsed_us as u32,
+                levels: levels & wait.mask,
+                ..Default::default()
+            });
+            self.send_answer(serial, answer);
+        }
+    }
+
+    /// Build the answer of a pwm request, with the output really generated
+    ///
+    fn answer_from_pwm_result(r: Result<(usize, f64), ErrorCode>) -> PicohaDioAnswer<'static> {
+        match r {
+            Ok((pin_num, duty_cycle)) => {
+                let mut answer = Self::answer_from_result(Ok(()));
+                answer.pwm = Some(PwmOutput {
+                    frequency_hz: dio_pwm::output_frequency(dio_pwm::slice_of(pin_num)),
+                    duty_cycle,
+                    ..Default::default()
+                });
+                answer
+            }
+            Err(e) => Self::answer_from_result(Err(e)),
+        }
+    }
+
+    /// Send the edge events detected since the last call
+    ///
+    pub fn process_edge_events(&mut self, serial: &mut DioSerialPort) {
+        self.flush_frames(serial);
+
+        for _ in 0..MAX_EVENTS_PER_POLL {
+            // Events stay queued until their whole frame fits, an overflow counts them as lost
+            let free = TX_BACKLOG_SIZE - self.tx_backlog.len();
+            if free < MAX_EVENT_FRAME_SIZE + MAX_FRAME_SIZE {
+                break;
+            }
+            let Some(edge) = edge_events::pop_event() else {
+                break;
+            };
+
+            let event = PicohaDioEvent {
+                event: Some(Event::Edge(EdgeEvent {
+                    pin_num: edge.pin_num as u32,
+                    level: femtopb::EnumValue::Known(match edge.level_high {
+                        true => Level::High,
+                        false => Level::Low,
+                    }),
+                    timestamp_us: edge.timestamp_us,
+                    lost_events: edge.lost_events,
+                    ..Default::default()
+                })),
+                ..Default::default()
+            };
+
+            let mut buffer = [0u8; MAX_EVENT_SIZE];
+            let encoded_len = event.encoded_len();
+            if let Err(e) = event.encode(&mut buffer.as_mut()) {
+                print_debug_message!("      * error encoding event: {:?}", e);
+                continue;
+            }
+            self.send_frame(serial, &buffer[..encoded_len]);
+        }
+    }
+
+    /// Build a bare SUCCESS or FAILURE answer from the result of an operation
+    ///
+    fn answer_from_result(r: Result<(), ErrorCode>) -> PicohaDioAnswer<'static> {
+        let mut answer = PicohaDioAnswer::default();
+        match r {
+            Ok(_) => {
+                answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Success);
+            }
+            Err(e) => Self::set_answer_failure(&mut answer, e),
+        }
+        answer
+    }
+
+    /// Mark an answer as failed with its error code and message
+    ///
+    fn set_answer_failure(answer: &mut PicohaDioAnswer, error: ErrorCode) {
+        answer.r#type = femtopb::EnumValue::Known(crate::api_dio::AnswerType::Failure);
+        answer.error_code = Some(femtopb::EnumValue::Known(error));
+        answer.error_message = Some(api_dio_utils::error_code_message(error));
+    }
+
+    /// Send an answer to the request currently processed
+    ///
+    fn send_answer(&mut self, serial: &mut DioSerialPort, mut answer: PicohaDioAnswer) {
+        answer.request_id = self.echo.request_id;
+        answer.request_type = self.echo.request_type.map(femtopb::EnumValue::Known);
+        answer.pin_num = self.echo.pin_num;
+        if answer.timestamp_us.is_none() {
+            // Answers not built by execute_payload, like the end of pulses or a whole batch
+            answer.timestamp_us = Some(device_time::now_us());
+        }
+
+        let mut buffer = [0u8; MAX_ANSWER_SIZE];
+        let encoded_len = answer.encoded_len();
+        if let Err(e) = answer.encode(&mut buffer.as_mut()) {
+            print_debug_message!("      * error encoding answer: {:?}", e);
+            return;
+        }
+
+        print_debug_message!("      * answer: {:?}", answer);
+        // print_debug_message!("      * sending answer: {:?}", encoded_len);
+        // print_debug_message!("      * sending answer: {:?}", &buffer[..encoded_len]);
+
+        self.send_frame(serial, &buffer[..encoded_len]);
+    }
+
+    /// SLIP encode a message and queue its whole frame for the serial port
+    ///
+    fn send_frame(&mut self, serial: &mut DioSerialPort, message: &[u8]) {
+        // Prepare encoding
+        let mut encoded_command = [0u8; MAX_FRAME_SIZE];
+        let mut slip_encoder = serial_line_ip::Encoder::new();
+
+        // Encode the command
+        let mut totals = match slip_encoder.encode(message, &mut encoded_command) {
+            Ok(t) => t,
+            Err(e) => {
+                print_debug_message!("      * error encoding frame: {:?}", e);
+                return;
+            }
+        };
+
+        // Finalise the encoding
+        totals += match slip_encoder.finish(&mut encoded_command[totals.written..]) {
+            Ok(t) => t,
+            Err(e) => {
+                print_debug_message!("      * error encoding frame: {:?}", e);
+                return;
+            }
+        };
+
+        // print_debug_message!("      * sending frame: {:?}", totals.written);
+        print_debug_message!(
+            "      * sending frame: {:?}",
+            &encoded_command[..totals.written]
+        );
+
+        // A frame is queued whole or not at all, so the host never gets a cut frame
+        match self
+            .tx_backlog
+            .extend_from_slice(&encoded_command[..totals.written])
+        {
+            Ok(_) => self.flush_frames(serial),
+            Err(_) => print_debug_message!(b"      * frame not sent, backlog full\r\n"),
+        }
+    }
+
+    /// Write the queued frames the serial port has room for, the rest waits for the next poll
+    ///
+    fn flush_frames(&mut self, serial: &mut DioSerialPort) {
+        if self.tx_backlog.is_empty() {
+            return;
+        }
+        match serial.write(&self.tx_backlog) {
+            Ok(count) => {
+                let remaining = self.tx_backlog.len() - count;
+                self.tx_backlog.copy_within(count.., 0);
+                self.tx_backlog.truncate(remaining);
+            }
+            // The USB has not taken the previous packets yet
+            Err(usb_device::UsbError::WouldBlock) => {}
+            Err(e) => print_debug_message!("      * frames not sent {:?}", e),
+        }
+    }
+}
+
+/// Stop the pulses and the sequence setting a pin, the host takes the pin back
+///
+fn stop_timed_outputs(pin_num: usize) {
+    dio_pulse::cancel(pin_num);
+    if dio_sequence::is_driving(pin_num) {
+        dio_sequence::stop();
+    }
+}
+
+/// Convert a safe state of the API into the failsafe one
+///
+fn safe_state_from_api(state: SafeState) -> dio_failsafe::SafeState {
+    match state {
+        SafeState::None => dio_failsafe::SafeState::None,
+        SafeState::Input => dio_failsafe::SafeState::Input,
+        SafeState::Low => dio_failsafe::SafeState::Low,
+        SafeState::High => dio_failsafe::SafeState::High,
+    }
+}
+
+/// Convert a failsafe safe state into the API value
+///
+fn safe_state_to_api(state: dio_failsafe::SafeState) -> SafeState {
+    match state {
+        dio_failsafe::SafeState::None => SafeState::None,
+        dio_failsafe::SafeState::Input => SafeState::Input,
+        dio_failsafe::SafeState::Low => SafeState::Low,
+        dio_failsafe::SafeState::High => SafeState::High,
+    }
+}
+
+/// Bitmask of the set entries of a pin array, bit n is GPIO n
+///
+fn mask_of<T>(pins: &[Option<T>]) -> u32 {
+    pins.iter()
+        .enumerate()
+        .filter(|(_, pin)| pin.is_some())
+        .fold(0, |mask, (n, _)| mask | (1 << n))
+}
+
+/// Pin numbers selected by a port mask, bit n is GPIO n
+///
+fn pins_in_mask(mask: u32) -> impl Iterator<Item = usize> {
+    (0..MAX_PINS).filter(move |n| mask & (1 << n) != 0)
+}
+
+/// Convert a pull type from the API into the HAL pull type
+///
+fn pull_from_api(pull: crate::api_dio::PinPull) -> rp2040_hal::gpio::DynPullType {
+    match pull {
+        crate::api_dio::PinPull::PullNone => rp2040_hal::gpio::DynPullType::None,
+        crate::api_dio::PinPull::PullUp => rp2040_hal::gpio::DynPullType::Up,
+        crate::api_dio::PinPull::PullDown => rp2040_hal::gpio::DynPullType::Down,
+        crate::api_dio::PinPull::PullBusKeep => rp2040_hal::gpio::DynPullType::BusKeep,
+    }
+}
+
+/// Convert a HAL pull type into the API pull type
+///
+fn pull_to_api(pull: rp2040_hal::gpio::DynPullType) -> crate::api_dio::PinPull {
+    match pull {
+        rp2040_hal::gpio::DynPullType::None => crate::api_dio::PinPull::PullNone,
+        rp2040_hal::gpio::DynPullType::Up => crate::api_dio::PinPull::PullUp,
+        rp2040_hal::gpio::DynPullType::Down => crate::api_dio::PinPull::PullDown,
+        rp2040_hal::gpio::DynPullType::BusKeep => crate::api_dio::PinPull::PullBusKeep,
+    }
+}
+
+/// Convert a drive strength from the API into the HAL drive strength
+///
+fn drive_from_api(strength: crate::api_dio::PinDriveStrength) -> OutputDriveStrength {
+    match strength {
+        crate::api_dio::PinDriveStrength::Drive2ma => OutputDriveStrength::TwoMilliAmps,
+        crate::api_dio::PinDriveStrength::Drive4ma => OutputDriveStrength::FourMilliAmps,
+        crate::api_dio::PinDriveStrength::Drive8ma => OutputDriveStrength::EightMilliAmps,
+        crate::api_dio::PinDriveStrength::Drive12ma => OutputDriveStrength::TwelveMilliAmps,
+    }
+}
+
+/// Convert a HAL drive strength into the API drive strength
+///
+fn drive_to_api(strength: OutputDriveStrength) -> crate::api_dio::PinDriveStrength {
+    match strength {
+        OutputDriveStrength::TwoMilliAmps => crate::api_dio::PinDriveStrength::Drive2ma,
+        OutputDriveStrength::FourMilliAmps => crate::api_dio::PinDriveStrength::Drive4ma,
+        OutputDriveStrength::EightMilliAmps => crate::api_dio::PinDriveStrength::Drive8ma,
+        OutputDriveStrength::TwelveMilliAmps => crate::api_dio::PinDriveStrength::Drive12ma,
+    }
+}
+
+/// Convert a slew rate from the API into the HAL slew rate
+///
+fn slew_from_api(slew_rate: crate::api_dio::PinSlewRate) -> OutputSlewRate {
+    match slew_rate {
+        crate::api_dio::PinSlewRate::SlewSlow => OutputSlewRate::Slow,
+        crate::api_dio::PinSlewRate::SlewFast => OutputSlewRate::Fast,
+    }
+}
+
+/// Convert a HAL slew rate into the API slew rate
+///
+fn slew_to_api(slew_rate: OutputSlewRate) -> crate::api_dio::PinSlewRate {
+    match slew_rate {
+        OutputSlewRate::Slow => crate::api_dio::PinSlewRate::SlewSlow,
+        OutputSlewRate::Fast => crate::api_dio::PinSlewRate::SlewFast,
+    }
+}
+
+/// Convert an edge selection from the API
+///
+fn edge_from_api(edge: femtopb::EnumValue<Edge>) -> Result<EdgeSelection, ErrorCode> {
+    match edge {
+        femtopb::EnumValue::Known(Edge::None) => Ok(EdgeSelection::None),
+        femtopb::EnumValue::Known(Edge::Rising) => Ok(EdgeSelection::Rising),
+        femtopb::EnumValue::Known(Edge::Falling) => Ok(EdgeSelection::Falling),
+        femtopb::EnumValue::Known(Edge::Both) => Ok(EdgeSelection::Both),
+        femtopb::EnumValue::Unknown(_) => Err(ErrorCode::InvalidValue),
+    }
+}
+
+/// Convert a capture status into the API value
+///
+fn capture_status_to_api(status: dio_capture::CaptureStatus) -> CaptureStatus {
+    match status {
+        dio_capture::CaptureStatus::Idle => CaptureStatus::Idle,
+        dio_capture::CaptureStatus::WaitingTrigger => CaptureStatus::WaitingTrigger,
+        dio_capture::CaptureStatus::Triggered => CaptureStatus::Triggered,
+        dio_capture::CaptureStatus::Done => CaptureStatus::Done,
+        dio_capture::CaptureStatus::Aborted => CaptureStatus::Aborted,
+    }
+}
+
+/// Convert an edge selection into the API value
+///
+fn edge_to_api(edges: EdgeSelection) -> Edge {
+    match edges {
+        EdgeSelection::None => Edge::None,
+        EdgeSelection::Rising => Edge::Rising,
+        EdgeSelection::Falling => Edge::Falling,
+        EdgeSelection::Both => Edge::Both,
     }
 }
@@ -0,0 +1,224 @@
+use core::cell::RefCell;
+
+use cortex_m::interrupt::Mutex;
+use heapless::Vec;
+use rp2040_hal::pac::{self, interrupt};
+use rp2040_hal::timer::{Alarm, Alarm2, Instant};
+
+use crate::device_time;
+
+/// Number of steps kept in RAM
+const MAX_SEQUENCE_STEPS: usize = 128;
+
+/// Shortest loop period, every step of a loop is played from one interrupt
+const MIN_LOOP_PERIOD_US: u32 = 100;
+
+/// Levels set on output pins at a time offset from the start of the sequence
+#[derive(Clone, Copy)]
+pub struct Step {
+    pub offset_us: u32,
+    pub mask: u32,
+    pub levels: u32,
+}
+
+/// State of the sequence reported to the host
+pub struct SequenceStatus {
+    pub step_count: usize,
+    pub running: bool,
+    pub completed_loops: u32,
+}
+
+/// Loaded sequence and play position, shared with the alarm interrupt
+struct Sequence {
+    steps: Vec<Step, MAX_SEQUENCE_STEPS>,
+    running: bool,
+    // 0 plays the sequence once
+    loop_period_us: u32,
+    next_step: usize,
+    // Start of the current loop
+    started_at: u64,
+    completed_loops: u32,
+    alarm: Option<Alarm2>,
+}
+
+impl Sequence {
+    /// Pins set by at least one step
+    ///
+    fn driven_pins(&self) -> u32 {
+        self.steps.iter().fold(0, |pins, step| pins | step.mask)
+    }
+
+    /// Schedule the alarm on the next step
+    ///
+    fn schedule_alarm(&mut self) {
+        if !self.running {
+            return;
+        }
+        let next = self.started_at + self.steps[self.next_step].offset_us as u64;
+        if let Some(alarm) = self.alarm.as_mut() {
+            // A step already due fires the interrupt right away
+            alarm.schedule_at(Instant::from_ticks(next)).ok();
+        }
+    }
+}
+
+static SEQUENCE: Mutex<RefCell<Sequence>> = Mutex::new(RefCell::new(Sequence {
+    steps: Vec::new(),
+    running: false,
+    loop_period_us: 0,
+    next_step: 0,
+    started_at: 0,
+    completed_loops: 0,
+    alarm: None,
+}));
+
+/// Set the levels of a step on the SIO outputs
+///
+fn apply_step(step: &Step) {
+    // A single xor write flips every pin that must change
+    let sio = unsafe { &*pac::SIO::ptr() };
+    let out = sio.gpio_out().read().bits();
+    sio.gpio_out_xor()
+        .write(|w| unsafe { w.bits((out ^ step.levels) & step.mask) });
+}
+
+/// Store the alarm playing the sequence and enable its interrupt
+///
+pub fn dio_sequence_init(mut alarm: Alarm2) {
+    alarm.enable_interrupt();
+    cortex_m::interrupt::free(|cs| {
+        SEQUENCE.borrow(cs).borrow_mut().alarm = Some(alarm);
+    });
+    unsafe {
+        pac::NVIC::unmask(pac::Interrupt::TIMER_IRQ_2);
+    }
+}
+
+/// Load steps, after the steps already loaded when appending
+///
+/// Returns false when the steps do not fit or are not in time order, the loaded steps are
+/// then kept unchanged. Nothing is loaded while the sequence is playing.
+///
+pub fn load(steps: impl Iterator<Item = Step>, append: bool) -> bool {
+    cortex_m::interrupt::free(|cs| {
+        let mut sequence = SEQUENCE.borrow(cs).borrow_mut();
+        if sequence.running {
+            return false;
+        }
+        let mut loaded = match append {
+            true => sequence.steps.clone(),
+            false => Vec::new(),
+        };
+        for step in steps {
+            let in_order = loaded
+                .last()
+                .map_or(true, |last| step.offset_us >= last.offset_us);
+            if !in_order || loaded.push(step).is_err() {
+                return false;
+            }
+        }
+        sequence.steps = loaded;
+        true
+    })
+}
+
+/// Start playing the loaded steps, the steps at offset 0 are set right away
+///
+/// Returns false when no step is loaded or the loop period is shorter than the sequence
+///
+pub fn start(loop_period_us: u32) -> bool {
+    cortex_m::interrupt::free(|cs| {
+        let mut sequence = SEQUENCE.borrow(cs).borrow_mut();
+        let Some(last) = sequence.steps.last() else {
+            return false;
+        };
+        if loop_period_us != 0
+            && (loop_period_us < MIN_LOOP_PERIOD_US || loop_period_us < last.offset_us)
+        {
+            return false;
+        }
+
+        sequence.loop_period_us = loop_period_us;
+        sequence.next_step = 0;
+        sequence.completed_loops = 0;
+        sequence.started_at = device_time::now_us();
+        sequence.running = true;
+        sequence.schedule_alarm();
+        true
+    })
+}
+
+/// Stop playing the sequence, the pins keep their current levels
+///
+pub fn stop() {
+    cortex_m::interrupt::free(|cs| {
+        SEQUENCE.borrow(cs).borrow_mut().running = false;
+    });
+}
+
+/// Check if the sequence is playing
+///
+pub fn is_running() -> bool {
+    cortex_m::interrupt::free(|cs| SEQUENCE.borrow(cs).borrow().running)
+}
+
+/// Pins set by at least one loaded step
+///
+pub fn driven_pins() -> u32 {
+    cortex_m::interrupt::free(|cs| SEQUENCE.borrow(cs).borrow().driven_pins())
+}
+
+/// Check if a pin is set by the sequence being played
+///
+pub fn is_driving(pin_num: usize) -> bool {
+    cortex_m::interrupt::free(|cs| {
+        let sequence = SEQUENCE.borrow(cs).borrow();
+        sequence.running && sequence.driven_pins() & (1 << pin_num) != 0
+    })
+}
+
+/// Get the state of the sequence
+///
+pub fn status() -> SequenceStatus {
+    cortex_m::interrupt::free(|cs| {
+        let sequence = SEQUENCE.borrow(cs).borrow();
+        SequenceStatus {
+            step_count: sequence.steps.len(),
+            running: sequence.running,
+            completed_loops: sequence.completed_loops,
+        }
+    })
+}
+
+#[interrupt]
+fn TIMER_IRQ_2() {
+    let now = device_time::now_us();
+
+    cortex_m::interrupt::free(|cs| {
+        let mut sequence = SEQUENCE.borrow(cs).borrow_mut();
+        if let Some(alarm) = sequence.alarm.as_mut() {
+            alarm.clear_interrupt();
+        }
+
+        // Steps are timed from the loop start, a late interrupt plays every step due
+        while sequence.running {
+            let step = sequence.steps[sequence.next_step];
+            if sequence.started_at + step.offset_us as u64 > now {
+                break;
+            }
+            apply_step(&step);
+
+            sequence.next_step += 1;
+            if sequence.next_step == sequence.steps.len() {
+                sequence.next_step = 0;
+                sequence.completed_loops = sequence.completed_loops.wrapping_add(1);
+                match sequence.loop_period_us {
+                    0 => sequence.running = false,
+                    period_us => sequence.started_at += period_us as u64,
+                }
+            }
+        }
+
+        sequence.schedule_alarm();
+    });
+}
@@ -0,0 +1,372 @@
+use core::cell::RefCell;
+
+use cortex_m::interrupt::Mutex;
+use heapless::Deque;
+use rp2040_hal::pac::{self, interrupt};
+use rp2040_hal::timer::{Alarm, Alarm0, Instant};
+
+use crate::device_time;
+use crate::BANK0_PINS;
+
+/// Number of events kept until the main loop sends them
+const EVENT_QUEUE_SIZE: usize = 64;
+
+/// Interrupt bits of one pin in the IO_BANK0 interrupt registers, 4 bits per pin
+const EDGE_LOW: u32 = 0b0100;
+const EDGE_HIGH: u32 = 0b1000;
+
+/// Edge bits of all the pins of one interrupt register
+const EDGE_BITS: u32 = 0xCCCC_CCCC;
+
+/// Number of IO_BANK0 interrupt registers, 8 pins each
+const INTERRUPT_REGISTERS: usize = 4;
+
+/// Edges selected on a pin, for the events or the counter
+#[derive(Clone, Copy, PartialEq)]
+pub enum EdgeSelection {
+    None,
+    Rising,
+    Falling,
+    Both,
+}
+
+impl EdgeSelection {
+    /// Check if the edge leading to a level is selected
+    ///
+    fn selects(self, level_high: bool) -> bool {
+        match self {
+            EdgeSelection::None => false,
+            EdgeSelection::Rising => level_high,
+            EdgeSelection::Falling => !level_high,
+            EdgeSelection::Both => true,
+        }
+    }
+
+    /// Interrupt bits detecting the selected edges
+    ///
+    fn interrupt_bits(self) -> u32 {
+        match self {
+            EdgeSelection::None => 0,
+            EdgeSelection::Rising => EDGE_HIGH,
+            EdgeSelection::Falling => EDGE_LOW,
+            EdgeSelection::Both => EDGE_HIGH | EDGE_LOW,
+        }
+    }
+}
+
+/// Edge detected on a subscribed pin
+#[derive(Clone, Copy)]
+pub struct EdgeEvent {
+    pub pin_num: usize,
+    // Level of the pin after the edge
+    pub level_high: bool,
+    pub timestamp_us: u64,
+    // Events dropped just before this one because the queue was full
+    pub lost_events: u32,
+}
+
+/// Edge configuration of the pins and events waiting to be sent to the host
+struct PinEdges {
+    subscriptions: [EdgeSelection; BANK0_PINS],
+    // Edges counted on each pin, counts keep running when the host does not poll
+    counted_edges: [EdgeSelection; BANK0_PINS],
+    counts: [u32; BANK0_PINS],
+    // Time a level must stay unchanged to be accepted, 0 disables the filter
+    debounce_us: [u32; BANK0_PINS],
+    // Time at which the level of a bouncing pin is considered settled
+    settle_at: [Option<u64>; BANK0_PINS],
+    // Last settled level of the debounced pins, bit n is GPIO n
+    settled_levels: u32,
+    // Alarm waking the filter up at the next settle time
+    debounce_alarm: Option<Alarm0>,
+    events: Deque<EdgeEvent, EVENT_QUEUE_SIZE>,
+    lost_events: u32,
+}
+
+impl PinEdges {
+    /// Handle an accepted edge of a pin
+    ///
+    fn report_edge(&mut self, pin_num: usize, level_high: bool, timestamp_us: u64) {
+        if self.counted_edges[pin_num].selects(level_high) {
+            self.counts[pin_num] = self.counts[pin_num].wrapping_add(1);
+        }
+        if self.subscriptions[pin_num].selects(level_high) {
+            self.push(pin_num, level_high, timestamp_us);
+        }
+    }
+
+    /// Queue an event, or count it as lost when the queue is full
+    ///
+    fn push(&mut self, pin_num: usize, level_high: bool, timestamp_us: u64) {
+        let event = EdgeEvent {
+            pin_num,
+            level_high,
+            timestamp_us,
+            lost_events: self.lost_events,
+        };
+        match self.events.push_back(event) {
+            Ok(_) => self.lost_events = 0,
+            Err(_) => self.lost_events = self.lost_events.saturating_add(1),
+        }
+    }
+
+    /// Enable the edge interrupts a pin needs, and only those
+    ///
+    fn update_interrupts(&self, pin_num: usize) {
+        // The debounce filter watches both edges, the selections filter them afterwards
+        let bits = if self.debounce_us[pin_num] > 0 {
+            EDGE_HIGH | EDGE_LOW
+        } else {
+            self.subscriptions[pin_num].interrupt_bits()
+                | self.counted_edges[pin_num].interrupt_bits()
+        };
+        let (n, offset) = interrupt_position(pin_num);
+        let io_bank0 = unsafe { &*pac::IO_BANK0::ptr() };
+
+        // Forget the edges latched before the change
+        io_bank0
+            .intr(n)
+            .write(|w| unsafe { w.bits((EDGE_HIGH | EDGE_LOW) << offset) });
+        io_bank0.proc0_inte(n).modify(|r, w| unsafe {
+            w.bits((r.bits() & !((EDGE_HIGH | EDGE_LOW) << offset)) | (bits << offset))
+        });
+    }
+
+    /// Wake the filter up at the earliest settle time
+    ///
+    fn schedule_debounce_alarm(&mut self) {
+        let next = self.settle_at.iter().flatten().min().copied();
+        if let (Some(alarm), Some(next)) = (self.debounce_alarm.as_mut(), next) {
+            // A settle time already passed fires the interrupt right away
+            alarm.schedule_at(Instant::from_ticks(next)).ok();
+        }
+    }
+}
+
+static PIN_EDGES: Mutex<RefCell<PinEdges>> = Mutex::new(RefCell::new(PinEdges {
+    subscriptions: [EdgeSelection::None; BANK0_PINS],
+    counted_edges: [EdgeSelection::None; BANK0_PINS],
+    counts: [0; BANK0_PINS],
+    debounce_us: [0; BANK0_PINS],
+    settle_at: [None; BANK0_PINS],
+    settled_levels: 0,
+    debounce_alarm: None,
+    events: Deque::new(),
+    lost_events: 0,
+}));
+
+/// Register index and bit offset of a pin in the IO_BANK0 interrupt registers
+///
+fn interrupt_position(pin_num: usize) -> (usize, usize) {
+    (pin_num / 8, 4 * (pin_num % 8))
+}
+
+/// Current level on the pads, bit n is GPIO n
+///
+fn pad_levels() -> u32 {
+    let sio = unsafe { &*pac::SIO::ptr() };
+    sio.gpio_in().read().bits()
+}
+
+/// Enable the GPIO interrupt and take the alarm used by the debounce filter
+///
+/// Pins only raise the GPIO interrupt once subscribed, counted or debounced
+///
+pub fn edge_events_init(mut debounce_alarm: Alarm0) {
+    debounce_alarm.enable_interrupt();
+    cortex_m::interrupt::free(|cs| {
+        PIN_EDGES.borrow(cs).borrow_mut().debounce_alarm = Some(debounce_alarm);
+    });
+    unsafe {
+        pac::NVIC::unmask(pac::Interrupt::IO_IRQ_BANK0);
+        pac::NVIC::unmask(pac::Interrupt::TIMER_IRQ_0);
+    }
+}
+
+/// Select the edges reported for a pin, EdgeSelection::None stops the reports
+///
+pub fn subscribe(pin_num: usize, edges: EdgeSelection) {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_edges = PIN_EDGES.borrow(cs).borrow_mut();
+        pin_edges.subscriptions[pin_num] = edges;
+        pin_edges.update_interrupts(pin_num);
+    });
+}
+
+/// Set the debounce time of a pin, 0 disables the filter
+///
+pub fn set_debounce(pin_num: usize, debounce_us: u32) {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_edges = PIN_EDGES.borrow(cs).borrow_mut();
+        pin_edges.debounce_us[pin_num] = debounce_us;
+        pin_edges.settle_at[pin_num] = None;
+
+        // The filter starts from the current level
+        let mask = 1 << pin_num;
+        pin_edges.settled_levels = (pin_edges.settled_levels & !mask) | (pad_levels() & mask);
+        pin_edges.update_interrupts(pin_num);
+    });
+}
+
+/// Get the debounce time of a pin
+///
+pub fn debounce(pin_num: usize) -> u32 {
+    cortex_m::interrupt::free(|cs| PIN_EDGES.borrow(cs).borrow().debounce_us[pin_num])
+}
+
+/// Settled level of a debounced pin, None when the pin is not debounced
+///
+pub fn debounced_level(pin_num: usize) -> Option<bool> {
+    cortex_m::interrupt::free(|cs| {
+        let pin_edges = PIN_EDGES.borrow(cs).borrow();
+        (pin_edges.debounce_us[pin_num] > 0)
+            .then_some(pin_edges.settled_levels & (1 << pin_num) != 0)
+    })
+}
+
+/// Replace the pad levels of the debounced pins by their settled levels, bit n is GPIO n
+///
+pub fn debounced_levels(levels: u32) -> u32 {
+    cortex_m::interrupt::free(|cs| {
+        let pin_edges = PIN_EDGES.borrow(cs).borrow();
+        let debounced = pin_edges
+            .debounce_us
+            .iter()
+            .enumerate()
+            .filter(|(_, debounce_us)| **debounce_us > 0)
+            .fold(0u32, |mask, (n, _)| mask | (1 << n));
+        (levels & !debounced) | (pin_edges.settled_levels & debounced)
+    })
+}
+
+/// Select the edges counted on a pin and restart its count, EdgeSelection::None stops counting
+///
+pub fn configure_counter(pin_num: usize, edges: EdgeSelection) {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_edges = PIN_EDGES.borrow(cs).borrow_mut();
+        pin_edges.counted_edges[pin_num] = edges;
+        pin_edges.counts[pin_num] = 0;
+        pin_edges.update_interrupts(pin_num);
+    });
+}
+
+/// Get the edges counted on a pin
+///
+pub fn counted_edges(pin_num: usize) -> EdgeSelection {
+    cortex_m::interrupt::free(|cs| PIN_EDGES.borrow(cs).borrow().counted_edges[pin_num])
+}
+
+/// Get the edge count of a pin, cleared in the same critical section when asked
+///
+pub fn read_counter(pin_num: usize, clear: bool) -> u32 {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_edges = PIN_EDGES.borrow(cs).borrow_mut();
+        let count = pin_edges.counts[pin_num];
+        if clear {
+            pin_edges.counts[pin_num] = 0;
+        }
+        count
+    })
+}
+
+/// Stop every edge feature of a pin, used when the pin starts driving its line
+///
+pub fn release_pin(pin_num: usize) {
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_edges = PIN_EDGES.borrow(cs).borrow_mut();
+        pin_edges.subscriptions[pin_num] = EdgeSelection::None;
+        pin_edges.counted_edges[pin_num] = EdgeSelection::None;
+        pin_edges.counts[pin_num] = 0;
+        pin_edges.debounce_us[pin_num] = 0;
+        pin_edges.settle_at[pin_num] = None;
+        pin_edges.update_interrupts(pin_num);
+    });
+}
+
+/// Take the oldest event waiting to be sent
+///
+pub fn pop_event() -> Option<EdgeEvent> {
+    cortex_m::interrupt::free(|cs| PIN_EDGES.borrow(cs).borrow_mut().events.pop_front())
+}
+
+#[interrupt]
+fn IO_IRQ_BANK0() {
+    let timestamp_us = device_time::now_us();
+    let io_bank0 = unsafe { &*pac::IO_BANK0::ptr() };
+    let levels = pad_levels();
+
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_edges = PIN_EDGES.borrow(cs).borrow_mut();
+        let mut bouncing = false;
+        for n in 0..INTERRUPT_REGISTERS {
+            let edges = io_bank0.proc0_ints(n).read().bits() & EDGE_BITS;
+            if edges == 0 {
+                continue;
+            }
+            io_bank0.intr(n).write(|w| unsafe { w.bits(edges) });
+
+            for i in 0..8 {
+                let pin_num = n * 8 + i;
+                let pin_bits = (edges >> (4 * i)) & (EDGE_HIGH | EDGE_LOW);
+                if pin_bits == 0 {
+                    continue;
+                }
+
+                // Debounced pins wait for the level to settle, each edge restarts the wait
+                let debounce_us = pin_edges.debounce_us[pin_num];
+                if debounce_us > 0 {
+                    pin_edges.settle_at[pin_num] = Some(timestamp_us + debounce_us as u64);
+                    bouncing = true;
+                    continue;
+                }
+
+                match pin_bits {
+                    EDGE_HIGH => pin_edges.report_edge(pin_num, true, timestamp_us),
+                    EDGE_LOW => pin_edges.report_edge(pin_num, false, timestamp_us),
+                    _ => {
+                        // Both edges latched since the last interrupt, the current level
+                        // tells which one came last
+                        let level_high = levels & (1 << pin_num) != 0;
+                        pin_edges.report_edge(pin_num, !level_high, timestamp_us);
+                        pin_edges.report_edge(pin_num, level_high, timestamp_us);
+                    }
+                }
+            }
+        }
+
+        if bouncing {
+            pin_edges.schedule_debounce_alarm();
+        }
+    });
+}
+
+#[interrupt]
+fn TIMER_IRQ_0() {
+    let timestamp_us = device_time::now_us();
+    let levels = pad_levels();
+
+    cortex_m::interrupt::free(|cs| {
+        let mut pin_edges = PIN_EDGES.borrow(cs).borrow_mut();
+        if let Some(alarm) = pin_edges.debounce_alarm.as_mut() {
+            alarm.clear_interrupt();
+        }
+
+        for pin_num in 0..BANK0_PINS {
+            match pin_edges.settle_at[pin_num] {
+                Some(settle_at) if settle_at <= timestamp_us => {
+                    pin_edges.settle_at[pin_num] = None;
+
+                    // A bounce that came back to the settled level is not an edge
+                    let mask = 1 << pin_num;
+                    if (levels ^ pin_edges.settled_levels) & mask != 0 {
+                        pin_edges.settled_levels ^= mask;
+                        pin_edges.report_edge(pin_num, levels & mask != 0, settle_at);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        pin_edges.schedule_debounce_alarm();
+    });
+}
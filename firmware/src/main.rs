@@ -8,19 +8,31 @@
 mod uart_debug;
 use embedded_hal::digital::OutputPin;
 use rp2040_hal::gpio::DynPinId;
-use rp2040_hal::gpio::OutputDriveStrength;
 // use rp2040_hal::gpio::new_pin;
 #[cfg(any(feature = "uart0_debug"))]
 use uart_debug::uart_debug_init;
 #[cfg(any(feature = "uart0_debug"))]
 use uart_debug::uart_debug_print;
 
-use crate::api_dio::PicohaDioRequest;
+use crate::api_dio::{PicohaDioRequest, PicohaDioRequestV2};
 // application logic
 mod api_dio_utils;
+mod device_time;
+mod dio_capture;
+mod dio_config_store;
+mod dio_failsafe;
+mod dio_pattern;
+mod dio_pio;
+mod dio_pulse;
+mod dio_pwm;
+mod dio_sequence;
 mod dio_request_processor;
+mod edge_events;
 
-use dio_request_processor::DioRequestProcessor;
+/// Number of GPIO in bank 0
+pub const BANK0_PINS: usize = 30;
+
+use dio_request_processor::{DioRequestProcessor, DioSerialPort, MAX_FRAME_SIZE};
 
 use bsp::entry;
 use femtopb::Message;
@@ -110,7 +122,12 @@ unsafe fn main() -> ! {
     .ok()
     .unwrap();
 
-    // let timer = hal::Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+    // Device time base, used to timestamp the events
+    let mut timer = hal::Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+    let debounce_alarm = timer.alarm_0().unwrap();
+    let pulse_alarm = timer.alarm_1().unwrap();
+    let sequence_alarm = timer.alarm_2().unwrap();
+    device_time::device_time_init(timer);
     // let mut delay = cortex_m::delay::Delay::new(core.SYST, clocks.system_clock.freq().to_Hz());
 
     let use_boot2 = true;
@@ -195,32 +212,6 @@ unsafe fn main() -> ! {
     }
 
     // --------------------------------------------------------------
-    // USB CDC
-    // Set up the USB driver
-    let usb_bus = UsbBusAllocator::new(hal::usb::UsbBus::new(
-        pac.USBCTRL_REGS,
-        pac.USBCTRL_DPRAM,
-        clocks.usb_clock,
-        true,
-        &mut pac.RESETS,
-    ));
-    // Set up the USB Communications Class Device driver
-    let mut serial: SerialPort<rp2040_hal::usb::UsbBus> = SerialPort::new(&usb_bus);
-    // Create a USB device with a fake VID and PID
-    let mut usb_dev = UsbDeviceBuilder::new(&usb_bus, UsbVidPid(0x16c0, 0x05E1))
-        .strings(&[
-            StringDescriptors::default()
-                .manufacturer("panduza")
-                .product("picoha-dio")
-                // .serial_number("TEST")
-                .serial_number(serial_id_str), // .serial_number(str::from_utf8(&buf_display[..id_count]).unwrap())
-        ])
-        .unwrap()
-        .device_class(2) // from: https://www.usb.org/defined-class-codes
-        .build();
-
-    // --------------------------------------------------------------
-
     let mut pins_id: [Option<DynPinId>; 23] = [
         None, // 0 debug uart
         None, // 1 debug uart
@@ -259,18 +250,92 @@ unsafe fn main() -> ! {
         pins_id[1] = Some(pins.gpio1.into_dyn_pin().id());
     }
 
-    // let mut request_buffer = DioRequestBuffer::new();
-    let mut decode_buffer: serial_line_ip::DecoderBuffer<512> =
-        serial_line_ip::DecoderBuffer::new();
+    // The modules are initialised before the pins are configured, changing a pin stops what
+    // the modules drive on it, even for the saved configuration applied at boot
+
+    // PWM slices, used to measure the inputs and to drive the PWM outputs
+    dio_pwm::dio_pwm_init(
+        pac.PWM,
+        &mut pac.RESETS,
+        clocks.system_clock.freq().to_Hz(),
+    );
+
+    // PIO and DMA sample the pins of the logic analyzer
+    dio_capture::dio_capture_init(
+        pac.PIO0,
+        &mut pac.RESETS,
+        clocks.system_clock.freq().to_Hz(),
+    );
+
+    // Another PIO and DMA play the pattern generator
+    dio_pattern::dio_pattern_init(
+        pac.PIO1,
+        &mut pac.RESETS,
+        clocks.system_clock.freq().to_Hz(),
+    );
+
+    // Pins raise edge events once subscribed or debounced
+    edge_events::edge_events_init(debounce_alarm);
+
+    // Output pulses are timed by a timer alarm
+    dio_pulse::dio_pulse_init(pulse_alarm);
+
+    // Uploaded output sequences are played by another timer alarm
+    dio_sequence::dio_sequence_init(sequence_alarm);
 
     // Create the request processor and init all pin to input
     let mut request_processor = DioRequestProcessor::new(pins_id);
     request_processor.init_all_pins_as_input();
 
+    // The saved power-on configuration is applied before the host can see the device
+    request_processor.apply_saved_config();
+    request_processor.load_pin_names();
+
+    // --------------------------------------------------------------
+    // USB CDC
+    // Set up the USB driver
+    let usb_bus = UsbBusAllocator::new(hal::usb::UsbBus::new(
+        pac.USBCTRL_REGS,
+        pac.USBCTRL_DPRAM,
+        clocks.usb_clock,
+        true,
+        &mut pac.RESETS,
+    ));
+    // Set up the USB Communications Class Device driver
+    let mut serial: DioSerialPort = SerialPort::new_with_store(&usb_bus, [0u8; 128], [0u8; MAX_FRAME_SIZE]);
+    // Create a USB device with a fake VID and PID
+    let mut usb_dev = UsbDeviceBuilder::new(&usb_bus, UsbVidPid(0x16c0, 0x05E1))
+        .strings(&[
+            StringDescriptors::default()
+                .manufacturer("panduza")
+                .product("picoha-dio")
+                // .serial_number("TEST")
+                .serial_number(serial_id_str), // .serial_number(str::from_utf8(&buf_display[..id_count]).unwrap())
+        ])
+        .unwrap()
+        .device_class(2) // from: https://www.usb.org/defined-class-codes
+        .build();
+
+    // --------------------------------------------------------------
+
+    // let mut request_buffer = DioRequestBuffer::new();
+    let mut decode_buffer: serial_line_ip::DecoderBuffer<512> =
+        serial_line_ip::DecoderBuffer::new();
+
+    // State of the host session at the previous iteration
+    let mut usb_configured = false;
+    let mut serial_dtr = false;
+
+    // Bytes read from the serial port and not decoded yet
+    let mut buf = [0u8; 512];
+    let mut buf_start = 0;
+    let mut buf_end = 0;
+
     loop {
-        // Check for new data
-        if usb_dev.poll(&mut [&mut serial]) {
-            let mut buf = [0u8; 512];
+        usb_dev.poll(&mut [&mut serial]);
+
+        // Check for new data, once the previous bytes are decoded
+        if buf_start == buf_end {
             match serial.read(&mut buf) {
                 Err(_e) => {
                     // Do nothing
@@ -279,52 +344,110 @@ unsafe fn main() -> ! {
                     // Do nothing
                 }
                 Ok(count) => {
-                    let mut data = &buf[..count];
+                    buf_start = 0;
+                    buf_end = count;
                     print_debug_message!(b"========================\r\n");
-                    print_debug_message!("+ recieved: {:?}", data);
-
-                    loop {
-                        // print_debug_message!(b"1");
-                        // Check if we have enough data to decode
-                        match decode_buffer.feed(data) {
-                            Ok((nb_bytes_processed, found_trame_complete)) => {
-                                // print_debug_message!(b"2");
-                                if found_trame_complete {
-                                    let trame = decode_buffer.slice();
-                                    let request = try_to_decode_api_request(trame).unwrap();
-                                    print_debug_message!("+ process request: {:?}", request);
-                                    request_processor.process_request(&mut serial, request);
-                                    decode_buffer.reset();
-                                    data = &buf[..count - nb_bytes_processed];
-                                } else {
-                                    // print_debug_message!(b"3");
-                                    break;
-                                }
+                    print_debug_message!("+ recieved: {:?}", &buf[..count]);
+                }
+            }
+        }
+
+        // A request is only decoded when its answer can be queued, the bytes left wait here
+        // and the USB flow control holds the host back
+        while buf_start < buf_end && request_processor.can_accept_request() {
+            // Check if we have enough data to decode
+            match decode_buffer.feed(&buf[buf_start..buf_end]) {
+                Ok((nb_bytes_processed, found_trame_complete)) => {
+                    buf_start += nb_bytes_processed;
+                    if found_trame_complete {
+                        let trame = decode_buffer.slice();
+                        match try_to_decode_api_request(trame) {
+                            Some(ApiRequest::V1(request)) => {
+                                print_debug_message!("+ process request: {:?}", request);
+                                request_processor.process_request(&mut serial, request);
                             }
-                            other => {
-                                print_debug_message!("{:?}", other);
-                                break;
+                            Some(ApiRequest::V2(request)) => {
+                                print_debug_message!("+ process request: {:?}", request);
+                                request_processor.process_request_v2(&mut serial, request);
+                            }
+                            None => {
+                                request_processor.process_decode_failure(&mut serial);
                             }
                         }
-
-                        // Try to parse a request from the buffer
+                        decode_buffer.reset();
                     }
                 }
+                other => {
+                    // Drop the broken frame, the next one starts from a clean buffer
+                    print_debug_message!("{:?}", other);
+                    decode_buffer.reset();
+                    buf_start = buf_end;
+                }
             }
         }
+
+        // A suspend or a bus reset leaves the configured state, closing the port drops DTR
+        let configured = usb_dev.state() == UsbDeviceState::Configured;
+        let dtr = configured && serial.dtr();
+        if (usb_configured && !configured) || (serial_dtr && !dtr) {
+            // Partial frames of the closed session are dropped
+            decode_buffer.reset();
+            buf_start = buf_end;
+            request_processor.close_session();
+        }
+        usb_configured = configured;
+        serial_dtr = dtr;
+
+        // Push the edges detected since the last iteration
+        request_processor.process_edge_events(&mut serial);
+
+        // Search the capture trigger in the new samples
+        request_processor.process_capture();
+
+        // Give the pins back once the pattern ended
+        request_processor.process_pattern();
+
+        // Put the pins in their safe state when the host went silent
+        request_processor.process_failsafe();
+
+        // Answer the pulse requests whose pulses ended
+        request_processor.process_pulse_completions(&mut serial);
+
+        // Answer the wait requests whose condition is met or timed out
+        request_processor.process_waits(&mut serial);
     }
 }
 
+/// API request decoded from a frame
+///
+enum ApiRequest<'a> {
+    V1(PicohaDioRequest<'a>),
+    V2(PicohaDioRequestV2<'a>),
+}
+
 /// Try to decode an API request
 ///
-fn try_to_decode_api_request(frame: &[u8]) -> Option<PicohaDioRequest> {
+/// v2 payload tags are never used by v1, so a frame that decodes with a v2 payload is a v2
+/// request and anything else is handled as a v1 request.
+///
+fn try_to_decode_api_request(frame: &[u8]) -> Option<ApiRequest> {
+    if let Ok(request) = PicohaDioRequestV2::decode(frame) {
+        if request.payload.is_some() {
+            return Some(ApiRequest::V2(request));
+        }
+    }
+
     match PicohaDioRequest::decode(frame) {
         Ok(ppp) => {
             let mut new_request = PicohaDioRequest::default();
             new_request.r#type = ppp.r#type;
             new_request.pin_num = ppp.pin_num;
             new_request.value = ppp.value;
-            Some(new_request)
+            new_request.pull = ppp.pull;
+            new_request.drive_strength = ppp.drive_strength;
+            new_request.slew_rate = ppp.slew_rate;
+            new_request.request_id = ppp.request_id;
+            Some(ApiRequest::V1(new_request))
         }
         Err(e) => {
             print_debug_message!("      * error decoding request: {:?}", e);
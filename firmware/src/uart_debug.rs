@@ -61,12 +61,13 @@ macro_rules! print_debug_message {
     }};
     ($fmt:expr, $arg0:expr) => {{
         let mut debug_message = heapless::String::<512>::new();
-        writeln!(&mut debug_message, $fmt, $arg0).unwrap();
+        // A message too long for the buffer is just truncated
+        writeln!(&mut debug_message, $fmt, $arg0).ok();
         crate::uart_debug_print(debug_message.as_bytes());
     }};
     ($fmt:expr, $arg0:expr, $arg1:expr) => {{
         let mut debug_message = heapless::String::<512>::new();
-        writeln!(&mut debug_message, $fmt, $arg0, $arg1).unwrap();
+        writeln!(&mut debug_message, $fmt, $arg0, $arg1).ok();
         crate::uart_debug_print(debug_message.as_bytes());
     }};
 }
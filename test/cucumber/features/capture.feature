@@ -0,0 +1,85 @@
+Feature: Capture Feature
+
+  Scenario: An edge trigger keeps the samples around the edge
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I start a capture with:
+      | first_pin            | 14      |
+      | pin_count            | 2       |
+      | sample_rate_hz       | 1000000 |
+      | pre_trigger_samples  | 100     |
+      | post_trigger_samples | 1000    |
+      | trigger              | edge    |
+      | trigger_mask         | 0x2     |
+      | trigger_value        | 0x0     |
+    Then I must receive a SUCCESS response from the device
+    When I wait 10 ms
+    When I get the capture state
+    Then the capture must be "waiting_trigger"
+    When I send a v2 set_value "low" in pin "15" command to the device
+    When I wait 10 ms
+    When I get the capture state
+    Then the capture must be "done"
+    When I download the capture
+    Then the capture must hold 1100 samples with the trigger at sample 100
+    Then the captured pin "15" must go from "high" to "low" at the trigger
+
+  Scenario: A capture without trigger starts right away
+    Given a serial connection to the device opened
+    When I start a capture with:
+      | first_pin            | 0     |
+      | pin_count            | 8     |
+      | sample_rate_hz       | 10000 |
+      | post_trigger_samples | 500   |
+      | trigger              | now   |
+    When I wait 100 ms
+    When I get the capture state
+    Then the capture must be "done"
+    When I download the capture
+    Then the capture must hold 500 samples with the trigger at sample 0
+
+  Scenario: A running capture can only be stopped
+    Given a serial connection to the device opened
+    When I send a set_direction "input" in pin "14" command to the device
+    When I send a set_pull "up" in pin "14" command to the device
+    When I start a capture with:
+      | first_pin            | 14    |
+      | pin_count            | 1     |
+      | sample_rate_hz       | 10000 |
+      | post_trigger_samples | 100   |
+      | trigger              | level |
+      | trigger_mask         | 0x1   |
+      | trigger_value        | 0x0   |
+    When I start a capture with:
+      | first_pin            | 14    |
+      | pin_count            | 1     |
+      | sample_rate_hz       | 10000 |
+      | post_trigger_samples | 100   |
+    Then the device must report the error "resource_busy"
+    When I stop the capture
+    Then the capture must be "aborted"
+
+  Scenario: Invalid captures are rejected
+    Given a serial connection to the device opened
+    When I start a capture with:
+      | first_pin            | 14    |
+      | pin_count            | 0     |
+      | sample_rate_hz       | 10000 |
+      | post_trigger_samples | 100   |
+    Then the device must report the error "invalid_value"
+    When I start a capture with:
+      | first_pin            | 30    |
+      | pin_count            | 4     |
+      | sample_rate_hz       | 10000 |
+      | post_trigger_samples | 100   |
+    Then the device must report the error "invalid_value"
+    When I start a capture with:
+      | first_pin            | 14       |
+      | pin_count            | 1        |
+      | sample_rate_hz       | 50000000 |
+      | post_trigger_samples | 100      |
+    Then the device must report the error "invalid_value"
+    When I read the capture from offset 1000000
+    Then the device must report the error "invalid_value"
@@ -0,0 +1,37 @@
+Feature: Config Feature
+
+  Scenario: The pin configuration is saved in flash
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "high" in pin "14" command to the device
+    When I save the pin configuration
+    Then the answer must echo the request "save_config"
+    Then I must receive a SUCCESS response from the device
+    When I get the saved configuration of pin "14"
+    Then the saved configuration must be "output" at level "high"
+    When I get the saved configuration of pin "15"
+    Then the saved configuration must be "input" at level "low"
+    When I erase the saved configuration
+
+  Scenario: The factory defaults are restored without erasing the saved configuration
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "high" in pin "14" command to the device
+    When I save the pin configuration
+    When I restore the factory defaults
+    Then I must receive a SUCCESS response from the device
+    When I send a get_direction in pin "14" command to the device
+    Then the device must report the direction "input"
+    When I get the saved configuration of pin "14"
+    Then the saved configuration must be "output" at level "high"
+    When I erase the saved configuration
+
+  Scenario: The saved configuration is erased
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I save the pin configuration
+    When I erase the saved configuration
+    Then I must receive a SUCCESS response from the device
+    When I get the saved configuration of pin "14"
+    Then no configuration must be saved
+    When I restore the factory defaults
@@ -35,3 +35,76 @@ Feature: Control Pin Feature
       | pin_out | pin_in |
       |       2 |      3 |
       # |       3 |      2 |
+
+  Scenario Outline: Pull type is kept across direction changes
+    Given a serial connection to the device opened
+    When I send a set_pull "<pull>" in pin "<pin>" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a set_direction "output" in pin "<pin>" command to the device
+    When I send a set_direction "input" in pin "<pin>" command to the device
+    When I send a get_pull in pin "<pin>" command to the device
+    Then the device must report the pull "<pull>"
+
+    Examples:
+      | pin | pull     |
+      |   4 | up       |
+      |   4 | none     |
+      |   4 | bus_keep |
+      |   4 | down     |
+
+  Scenario Outline: Drive strength and slew rate of an output can be read back
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "<pin>" command to the device
+    When I send a set_drive "<strength>" "<slew>" in pin "<pin>" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a get_drive in pin "<pin>" command to the device
+    Then the device must report the drive "<strength>" "<slew>"
+
+    Examples:
+      | pin | strength | slew |
+      |   5 | 2mA      | slow |
+      |   5 | 12mA     | fast |
+      |   5 | 4mA      | slow |
+
+  Scenario: Drive strength cannot be set on an input
+    Given a serial connection to the device opened
+    When I send a set_direction "input" in pin "5" command to the device
+    When I send a set_drive "8mA" "fast" in pin "5" command to the device
+    Then I must receive a FAILURE response from the device
+
+  Scenario: Open-drain pin drives low and releases high
+    Given a serial connection to the device opened
+    When I send a set_pull "up" in pin "6" command to the device
+    When I send a set_direction "open_drain" in pin "6" command to the device
+    When I send a get_direction in pin "6" command to the device
+    Then the device must report the value "open_drain"
+    When I send a set_value "low" in pin "6" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a get_value in pin "6" command to the device
+    Then the device must report the value "low"
+    When I send a set_value "high" in pin "6" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a get_value in pin "6" command to the device
+    Then the device must report the value "high"
+
+  Scenario Outline: Requests on an unavailable pin report an invalid pin
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "<pin>" command to the device
+    Then the device must report the error "invalid_pin"
+
+    Examples:
+      | pin |
+      |  23 |
+      |  30 |
+
+  Scenario: Setting the value of an input reports a wrong direction
+    Given a serial connection to the device opened
+    When I send a set_direction "input" in pin "7" command to the device
+    When I send a set_value "high" in pin "7" command to the device
+    Then the device must report the error "wrong_direction"
+
+  Scenario: A v1 direction used as a level reports an invalid value
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "7" command to the device
+    When I send a set_value "input" in pin "7" command to the device
+    Then the device must report the error "invalid_value"
@@ -0,0 +1,83 @@
+Feature: Events Feature
+
+  Scenario: An open-drain pin reports its own edges
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I subscribe to the "both" edges of pin "15"
+    Then I must receive a SUCCESS response from the device
+    When I send a v2 set_value "low" in pin "15" command to the device
+    Then I must receive an edge event on pin "15" with level "low"
+    When I send a v2 set_value "high" in pin "15" command to the device
+    Then I must receive an edge event on pin "15" with level "high"
+    When I subscribe to the "none" edges of pin "15"
+    Then I must receive a SUCCESS response from the device
+
+  Scenario: Only the subscribed edges are reported
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I subscribe to the "rising" edges of pin "15"
+    When I send a v2 set_value "low" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    Then I must receive an edge event on pin "15" with level "high"
+    When I subscribe to the "none" edges of pin "15"
+
+  Scenario: A driven output cannot be subscribed
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "15" command to the device
+    When I subscribe to the "both" edges of pin "15"
+    Then the device must report the error "wrong_direction"
+
+  Scenario: A debounced pin reports one edge per settled transition
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I send a set_debounce "50000" in pin "15" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a get_debounce in pin "15" command to the device
+    Then the device must report the debounce "50000"
+    When I subscribe to the "both" edges of pin "15"
+    When I send a v2 set_value "low" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I send a v2 set_value "low" in pin "15" command to the device
+    Then I must receive an edge event on pin "15" with level "low"
+    When I send a v2 get_value in pin "15" command to the device
+    Then the device must report the level "low"
+    When I send a set_debounce "0" in pin "15" command to the device
+    When I subscribe to the "none" edges of pin "15"
+
+  Scenario: Invalid debounce settings are rejected
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "15" command to the device
+    When I send a set_debounce "50000" in pin "15" command to the device
+    Then the device must report the error "wrong_direction"
+    When I send a set_direction "input" in pin "15" command to the device
+    When I send a set_debounce "2000000" in pin "15" command to the device
+    Then the device must report the error "invalid_value"
+
+  Scenario: Edge counters count on the device
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I count the "falling" edges of pin "15"
+    Then I must receive a SUCCESS response from the device
+    When I send a v2 set_value "low" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I send a v2 set_value "low" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I read the edge counter of pin "15"
+    Then the device must report the edge count 2
+    When I read and clear the edge counter of pin "15"
+    Then the device must report the edge count 2
+    When I read the edge counter of pin "15"
+    Then the device must report the edge count 0
+    When I send a v2 set_value "low" in pin "15" command to the device
+    When I reset the edge counter of pin "15"
+    When I read the edge counter of pin "15"
+    Then the device must report the edge count 0
+    When I count the "none" edges of pin "15"
@@ -0,0 +1,67 @@
+Feature: Failsafe Feature
+
+  Scenario: The pins go to their safe state when the host goes silent
+    Given a serial connection to the device opened
+    When I get and clear the failsafe state
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "high" in pin "14" command to the device
+    When I set the safe state of pin "14" to "low"
+    When I enable the failsafe with a heartbeat timeout of "100" ms
+    Then the failsafe must be "armed"
+    When I wait 300 ms
+    When I get and clear the failsafe state
+    Then the failsafe must be "tripped"
+    When I send a v2 get_value in pin "14" command to the device
+    Then the device must report the level "low"
+    When I get the failsafe state
+    Then the failsafe must be "armed"
+    When I disable the failsafe
+    Then the failsafe must be "disabled"
+    When I set the safe state of pin "14" to "none"
+
+  Scenario: Requests keep the failsafe from tripping
+    Given a serial connection to the device opened
+    When I get and clear the failsafe state
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "high" in pin "14" command to the device
+    When I set the safe state of pin "14" to "input"
+    When I enable the failsafe with a heartbeat timeout of "200" ms
+    When I wait 100 ms
+    When I send a v2 ping command to the device
+    When I wait 100 ms
+    When I send a v2 ping command to the device
+    When I wait 100 ms
+    When I get the failsafe state
+    Then the failsafe must be "armed"
+    When I send a v2 get_value in pin "14" command to the device
+    Then the device must report the level "high"
+    When I disable the failsafe
+    When I wait 300 ms
+    When I get the failsafe state
+    Then the failsafe must be "disabled"
+    When I set the safe state of pin "14" to "none"
+
+  Scenario: A request longer than the heartbeat timeout does not trip the failsafe
+    Given a serial connection to the device opened
+    When I get and clear the failsafe state
+    When I send a set_direction "input" in pin "15" command to the device
+    When I enable the failsafe with a heartbeat timeout of "150" ms
+    When I measure the frequency of pin "15" during "100000" us
+    Then I must receive a SUCCESS response from the device
+    When I get the failsafe state
+    Then the failsafe must be "armed"
+    When I disable the failsafe
+
+  Scenario: The pins go to their safe state when the port is closed
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "high" in pin "14" command to the device
+    When I set the safe state of pin "14" to "low"
+    When I get and clear the failsafe state
+    When I close the serial connection
+    Given a serial connection to the device opened
+    When I send a v2 get_value in pin "14" command to the device
+    Then the device must report the level "low"
+    When I get and clear the failsafe state
+    Then the failsafe must be "closed"
+    When I set the safe state of pin "14" to "none"
@@ -0,0 +1,30 @@
+Feature: Measure Feature
+
+  Scenario: A steady input has no edge and a duty cycle of its level
+    Given a serial connection to the device opened
+    When I send a set_direction "input" in pin "15" command to the device
+    When I send a set_pull "down" in pin "15" command to the device
+    When I measure the frequency of pin "15" during "10000" us
+    Then the measure must count 0 edges with a duty cycle of "0"
+    When I send a set_pull "up" in pin "15" command to the device
+    When I measure the frequency of pin "15" during "10000" us
+    Then the measure must count 0 edges with a duty cycle of "1"
+    When I send a get_direction in pin "15" command to the device
+    Then the device must report the value "input"
+
+  Scenario: Only the B channel pins can be measured
+    Given a serial connection to the device opened
+    When I send a set_direction "input" in pin "14" command to the device
+    When I measure the frequency of pin "14" during "10000" us
+    Then the device must report the error "not_supported"
+
+  Scenario: Invalid measures are rejected
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "15" command to the device
+    When I measure the frequency of pin "15" during "10000" us
+    Then the device must report the error "wrong_direction"
+    When I send a set_direction "input" in pin "15" command to the device
+    When I measure the frequency of pin "15" during "0" us
+    Then the device must report the error "invalid_value"
+    When I measure the frequency of pin "15" during "200000" us
+    Then the device must report the error "invalid_value"
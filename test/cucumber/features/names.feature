@@ -0,0 +1,53 @@
+Feature: Names Feature
+
+  Scenario: Pins are given names stored on the device
+    Given a serial connection to the device opened
+    When I name pin "14" "DUT_RESET_N"
+    Then the answer must echo the request "set_pin_name" on pin "14"
+    Then I must receive a SUCCESS response from the device
+    When I name pin "15" "RELAY_PUMP"
+    When I get the pin names
+    Then pin "14" must be named "DUT_RESET_N"
+    Then pin "15" must be named "RELAY_PUMP"
+    When I name pin "14" ""
+    When I get the pin names
+    Then no pin must be named "DUT_RESET_N"
+    When I name pin "15" ""
+
+  Scenario: Requests target a pin by its name
+    Given a serial connection to the device opened
+    When I name pin "14" "DUT_RESET_N"
+    When I send a v2 set_direction "output" in pin named "DUT_RESET_N" command to the device
+    Then the answer must echo the request "set_direction" on pin "14"
+    Then I must receive a SUCCESS response from the device
+    When I send a v2 set_value "high" in pin named "DUT_RESET_N" command to the device
+    When I send a v2 get_value in pin "14" command to the device
+    Then the device must report the level "high"
+    When I send a v2 get_value in pin named "NOT_A_PIN" command to the device
+    Then the device must report the error "unknown_pin_name"
+    When I name pin "14" ""
+
+  Scenario: A batch targets pins by their names
+    Given a serial connection to the device opened
+    When I name pin "14" "DUT_RESET_N"
+    When I send a batch with the operations:
+      | operation     | pin         | value  |
+      | set_direction | DUT_RESET_N | output |
+      | set_value     | DUT_RESET_N | low    |
+      | get_value     | 14          |        |
+      | get_value     | NOT_A_PIN   |        |
+    Then the batch must report 4 results
+    Then the batch result 3 must report the level "low"
+    Then the batch result 4 must report the error "unknown_pin_name"
+    When I name pin "14" ""
+
+  Scenario: A name is valid and used by a single pin
+    Given a serial connection to the device opened
+    When I name pin "14" "RELAY_PUMP"
+    When I name pin "15" "RELAY_PUMP"
+    Then the device must report the error "invalid_value"
+    When I name pin "15" "bad name"
+    Then the device must report the error "invalid_value"
+    When I name pin "15" "A_NAME_LONGER_THAN_16"
+    Then the device must report the error "invalid_value"
+    When I name pin "14" ""
@@ -0,0 +1,66 @@
+Feature: Pattern Feature
+
+  Scenario: A pattern played once leaves its pins at the last sample
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0xC000" value "0xC000" command to the device
+    When I send a set_port_value mask "0xC000" value "0x0" command to the device
+    When I load the pattern "0x00000000" on pins "14" to "15"
+    When I append the pattern "0x55555555" on pins "14" to "15"
+    Then the pattern must hold 32 samples and be "stopped"
+    When I start a capture with:
+      | first_pin            | 14      |
+      | pin_count            | 2       |
+      | sample_rate_hz       | 1000000 |
+      | pre_trigger_samples  | 10      |
+      | post_trigger_samples | 100     |
+      | trigger              | edge    |
+      | trigger_mask         | 0x1     |
+      | trigger_value        | 0x1     |
+    When I start the pattern at "100000" Hz
+    Then the pattern must hold 32 samples and be "running"
+    When I wait 10 ms
+    When I get the pattern state
+    Then the pattern must hold 32 samples and be "stopped"
+    When I download the capture
+    Then the captured pin "14" must go from "low" to "high" at the trigger
+    When I send a get_direction in pin "14" command to the device
+    Then the device must report the direction "output"
+    When I send a get_port_state command to the device
+    Then the port "output_latch" bits "0xC000" must be "0x4000"
+
+  Scenario: A looping pattern holds its pins until one is taken back
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0xC000" value "0xC000" command to the device
+    When I load the pattern "0x0000FFFF" on pins "14" to "15"
+    When I start the pattern looping at "10000" Hz
+    When I wait 50 ms
+    When I get the pattern state
+    Then the pattern must hold 16 samples and be "running"
+    When I send a get_direction in pin "14" command to the device
+    Then the device must report the direction "pattern"
+    When I send a get_port_state command to the device
+    Then the port "pattern" bits "0xC000" must be "0xC000"
+    When I send a v2 set_value "high" in pin "14" command to the device
+    Then the device must report the error "resource_busy"
+    When I load the pattern "0x00000000" on pins "14" to "15"
+    Then the device must report the error "resource_busy"
+    When I send a set_direction "input" in pin "15" command to the device
+    When I get the pattern state
+    Then the pattern must hold 16 samples and be "stopped"
+    When I send a get_direction in pin "14" command to the device
+    Then the device must report the direction "output"
+
+  Scenario: Invalid patterns are rejected
+    Given a serial connection to the device opened
+    When I load the pattern "0x0" on pins "20" to "24"
+    Then the device must report the error "invalid_pin"
+    When I load the pattern "0x0" on pins "14" to "15"
+    When I append the pattern "0x0" on pins "14" to "14"
+    Then the device must report the error "invalid_value"
+    When I send a set_direction "input" in pin "14" command to the device
+    When I send a set_direction "output" in pin "15" command to the device
+    When I start the pattern at "100000" Hz
+    Then the device must report the error "wrong_direction"
+    When I send a set_direction "output" in pin "14" command to the device
+    When I start the pattern at "0" Hz
+    Then the device must report the error "invalid_value"
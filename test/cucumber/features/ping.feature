@@ -4,3 +4,8 @@ Feature: Ping Feature
     Given a serial connection to the device opened
     When I send a ping command to the device
     Then I must receive a SUCCESS response from the device
+
+  Scenario: The answer echoes the request it belongs to
+    Given a serial connection to the device opened
+    When I send a ping command to the device
+    Then the answer must echo the request "ping"
@@ -0,0 +1,54 @@
+Feature: Port Feature
+
+  Scenario: Several pins change direction and level with single requests
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x3000" value "0x3000" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a get_direction in pin "12" command to the device
+    Then the device must report the value "output"
+    When I send a set_port_value mask "0x3000" value "0x1000" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a v2 get_value in pin "12" command to the device
+    Then the device must report the level "high"
+    When I send a v2 get_value in pin "13" command to the device
+    Then the device must report the level "low"
+    When I send a set_port_value mask "0x3000" value "0x2000" command to the device
+    When I send a v2 get_value in pin "12" command to the device
+    Then the device must report the level "low"
+    When I send a v2 get_value in pin "13" command to the device
+    Then the device must report the level "high"
+
+  Scenario: Pins outside the mask are left untouched
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x3000" value "0x3000" command to the device
+    When I send a set_port_value mask "0x3000" value "0x3000" command to the device
+    When I send a set_port_value mask "0x1000" value "0x0" command to the device
+    When I send a v2 get_value in pin "13" command to the device
+    Then the device must report the level "high"
+
+  Scenario: A port write on an input pin is rejected
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x3000" value "0x1000" command to the device
+    When I send a set_port_value mask "0x3000" value "0x3000" command to the device
+    Then the device must report the error "wrong_direction"
+
+  Scenario: A port mask with unavailable pins is rejected
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x800000" value "0x800000" command to the device
+    Then the device must report the error "invalid_pin"
+    When I send a set_port_value mask "0x80000000" value "0x0" command to the device
+    Then the device must report the error "invalid_pin"
+
+  Scenario: The port state reports every pin in one answer
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x3000" value "0x1000" command to the device
+    When I send a set_direction "open_drain" in pin "14" command to the device
+    When I send a set_port_value mask "0x1000" value "0x1000" command to the device
+    When I send a get_port_state command to the device
+    Then the port "available" bits "0x7FFFFC" must be "0x7FFFFC"
+    Then the port "available" bits "0xFF800000" must be "0x0"
+    Then the port "output" bits "0x7000" must be "0x1000"
+    Then the port "input" bits "0x7000" must be "0x2000"
+    Then the port "open_drain" bits "0x7000" must be "0x4000"
+    Then the port "output_latch" bits "0x1000" must be "0x1000"
+    Then the port "input_level" bits "0x1000" must be "0x1000"
@@ -0,0 +1,76 @@
+Feature: Protocol v2 Feature
+
+  Scenario: If we ping the device with a v2 request it answers
+    Given a serial connection to the device opened
+    When I send a v2 ping command to the device
+    Then I must receive a SUCCESS response from the device
+
+  Scenario Outline: Set then read back a pin level with v2 requests
+    Given a serial connection to the device opened
+    When I send a v2 set_direction "output" in pin "<pin>" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a v2 set_value "<level>" in pin "<pin>" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a v2 get_value in pin "<pin>" command to the device
+    Then the device must report the level "<level>"
+
+    Examples:
+      | pin | level |
+      |   7 | high  |
+      |   7 | low   |
+
+  Scenario: A v1 set_value carrying a direction is rejected
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "7" command to the device
+    When I send a set_value "input" in pin "7" command to the device
+    Then I must receive a FAILURE response from the device
+
+  Scenario: The answer echoes the request id, type and pin
+    Given a serial connection to the device opened
+    When I send a v2 set_direction "output" in pin "7" command to the device
+    Then the answer must echo the request "set_direction" on pin "7"
+    When I send a set_value "high" in pin "30" command to the device
+    Then the device must report the error "invalid_pin"
+    Then the answer must echo the request "set_value" on pin "30"
+
+  Scenario: A batch configures several pins in one frame
+    Given a serial connection to the device opened
+    When I send a batch with the operations:
+      | operation     | pin | value  |
+      | set_direction |  10 | output |
+      | set_value     |  10 | high   |
+      | set_direction |  11 | output |
+      | set_value     |  11 | low    |
+      | get_value     |  10 |        |
+      | get_value     |  11 |        |
+    Then I must receive a SUCCESS response from the device
+    Then the batch must report 6 results
+    Then the batch result 5 must report the level "high"
+    Then the batch result 6 must report the level "low"
+
+  Scenario: A batch keeps going after a failed operation
+    Given a serial connection to the device opened
+    When I send a batch with the operations:
+      | operation     | pin | value  |
+      | set_direction |  10 | output |
+      | set_value     |  30 | high   |
+      | set_value     |  10 | high   |
+    Then the device must report the error "invalid_pin"
+    Then the batch must report 3 results
+    Then the batch result 2 must report the error "invalid_pin"
+
+  Scenario: A batch can stop at the first failure
+    Given a serial connection to the device opened
+    When I send a batch stopping on failure with the operations:
+      | operation     | pin | value  |
+      | set_direction |  10 | output |
+      | set_value     |  30 | high   |
+      | set_value     |  10 | high   |
+    Then the device must report the error "invalid_pin"
+    Then the batch must report 2 results
+
+  Scenario: A batch stops before its answer overflows
+    Given a serial connection to the device opened
+    When I send a batch of 48 port state reads
+    Then the device must report the error "answer_too_large"
+    Then the batch must report fewer than 48 results
@@ -0,0 +1,48 @@
+Feature: Pulse Feature
+
+  Scenario: A pulse is answered when it starts
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I subscribe to the "both" edges of pin "15"
+    When I send a pulse "low" of "150" us in pin "15" command to the device
+    Then I must receive a SUCCESS response from the device
+    Then I must receive an edge event on pin "15" with level "low"
+    Then I must receive an edge event on pin "15" with level "high"
+    When I subscribe to the "none" edges of pin "15"
+
+  Scenario: Repeated pulses are answered when the last one ends
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I count the "falling" edges of pin "15"
+    When I send a pulse "low" of "150" us repeated 5 times every "1000" us in pin "15" command and wait for its end
+    Then I must receive a SUCCESS response from the device
+    When I read the edge counter of pin "15"
+    Then the device must report the edge count 5
+    When I count the "none" edges of pin "15"
+
+  Scenario: Pulses cannot overlap on a pin
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a set_value "low" in pin "14" command to the device
+    When I send a pulse "high" of "500000" us in pin "14" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a pulse "high" of "150" us in pin "14" command to the device
+    Then the device must report the error "resource_busy"
+    When I send a v2 set_value "low" in pin "14" command to the device
+    When I send a pulse "high" of "150" us in pin "14" command to the device
+    Then I must receive a SUCCESS response from the device
+
+  Scenario: Invalid pulses are rejected
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a pulse "high" of "0" us in pin "14" command to the device
+    Then the device must report the error "invalid_value"
+    When I send a pulse "high" of "150" us repeated 3 times every "100" us in pin "14" command to the device
+    Then the device must report the error "invalid_value"
+    When I send a set_direction "input" in pin "14" command to the device
+    When I send a pulse "high" of "150" us in pin "14" command to the device
+    Then the device must report the error "wrong_direction"
@@ -0,0 +1,43 @@
+Feature: PWM Feature
+
+  Scenario: A pin generates a PWM signal and goes back to SIO
+    Given a serial connection to the device opened
+    When I send a set_pwm "1000" Hz duty "0.25" in pin "14" command to the device
+    Then the device must report a pwm of "1000" Hz with a duty cycle of "0.25"
+    When I send a get_direction in pin "14" command to the device
+    Then the device must report the direction "pwm"
+    When I send a get_port_state command to the device
+    Then the port "pwm" bits "0x4000" must be "0x4000"
+    When I send a set_pwm_duty "0.5" in pin "14" command to the device
+    Then the device must report a pwm of "1000" Hz with a duty cycle of "0.5"
+    When I send a v2 set_value "high" in pin "14" command to the device
+    Then the device must report the error "wrong_direction"
+    When I send a set_direction "output" in pin "14" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I send a set_value "high" in pin "14" command to the device
+    When I send a v2 get_value in pin "14" command to the device
+    Then the device must report the level "high"
+    When I send a get_port_state command to the device
+    Then the port "pwm" bits "0x4000" must be "0x0"
+
+  Scenario: Pins sharing a slice must share the frequency
+    Given a serial connection to the device opened
+    When I send a set_pwm "1000" Hz duty "0.5" in pin "14" command to the device
+    When I send a set_pwm "2000" Hz duty "0.5" in pin "15" command to the device
+    Then the device must report the error "resource_busy"
+    When I send a set_pwm "1000" Hz duty "0.1" in pin "15" command to the device
+    Then the device must report a pwm of "1000" Hz with a duty cycle of "0.1"
+    When I send a set_direction "input" in pin "15" command to the device
+    When I measure the frequency of pin "15" during "10000" us
+    Then the device must report the error "resource_busy"
+    When I send a set_direction "input" in pin "14" command to the device
+
+  Scenario: Invalid PWM settings are rejected
+    Given a serial connection to the device opened
+    When I send a set_pwm "0" Hz duty "0.5" in pin "14" command to the device
+    Then the device must report the error "invalid_value"
+    When I send a set_pwm "1000" Hz duty "1.5" in pin "14" command to the device
+    Then the device must report the error "invalid_value"
+    When I send a set_direction "input" in pin "14" command to the device
+    When I send a set_pwm_duty "0.5" in pin "14" command to the device
+    Then the device must report the error "wrong_direction"
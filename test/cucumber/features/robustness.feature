@@ -5,3 +5,48 @@ Feature: Robustness Feature
     Given I send a corrupted data to the device
     When I send a ping command to the device
     Then I must receive a SUCCESS response from the device
+
+  Scenario Outline: A frame that is not a request gets a decode failure
+    Given a serial connection to the device opened
+    When I send the bytes "<bytes>" to the device
+    Then the device must report the error "decode_failure"
+    When I send a ping command to the device
+    Then I must receive a SUCCESS response from the device
+
+    Examples:
+      | bytes    |
+      | ff ff ff |
+      | 0a 05 01 |
+
+  Scenario: An unknown request type is rejected
+    Given a serial connection to the device opened
+    When I send a request of raw type "99" to the device
+    Then the device must report the error "unknown_request_type"
+    When I send a ping command to the device
+    Then I must receive a SUCCESS response from the device
+
+  Scenario: An unknown pin value is rejected
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "8" command to the device
+    When I send a set_value of raw value "42" in pin "8" command to the device
+    Then the device must report the error "invalid_value"
+    When I send a ping command to the device
+    Then I must receive a SUCCESS response from the device
+
+  Scenario Outline: Out of range pins are rejected
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "<pin>" command to the device
+    Then the device must report the error "invalid_pin"
+    When I send a set_value "high" in pin "<pin>" command to the device
+    Then the device must report the error "invalid_pin"
+    When I send a get_value in pin "<pin>" command to the device
+    Then the device must report the error "invalid_pin"
+    When I send a ping command to the device
+    Then I must receive a SUCCESS response from the device
+
+    Examples:
+      | pin        |
+      |         23 |
+      |         29 |
+      |       1000 |
+      | 4294967295 |
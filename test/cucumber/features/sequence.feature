@@ -0,0 +1,74 @@
+Feature: Sequence Feature
+
+  Scenario: A sequence sets its pins in order then stops
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x14000" value "0x14000" command to the device
+    When I send a set_port_value mask "0x14000" value "0x0" command to the device
+    When I load the sequence:
+      | offset_us | mask    | levels  |
+      | 0         | 0x4000  | 0x4000  |
+      | 2000      | 0x10000 | 0x10000 |
+    When I append to the sequence:
+      | offset_us | mask    | levels  |
+      | 12000     | 0x4000  | 0x0     |
+    Then the sequence must hold 3 steps and be "stopped"
+    When I start the sequence
+    Then the sequence must hold 3 steps and be "running"
+    When I wait 100 ms
+    When I get the sequence state
+    Then the sequence must hold 3 steps and be "stopped"
+    Then the sequence must have completed at least 1 loops
+    When I send a get_port_state command to the device
+    Then the port "output_latch" bits "0x14000" must be "0x10000"
+
+  Scenario: A looping sequence runs until stopped
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x4000" value "0x4000" command to the device
+    When I load the sequence:
+      | offset_us | mask   | levels |
+      | 0         | 0x4000 | 0x4000 |
+      | 500       | 0x4000 | 0x0    |
+    When I start the sequence looping every "1000" us
+    When I wait 50 ms
+    When I get the sequence state
+    Then the sequence must hold 2 steps and be "running"
+    Then the sequence must have completed at least 10 loops
+    When I load the sequence:
+      | offset_us | mask   | levels |
+      | 0         | 0x4000 | 0x0    |
+    Then the device must report the error "resource_busy"
+    When I stop the sequence
+    Then the sequence must hold 2 steps and be "stopped"
+
+  Scenario: Setting a pin of the sequence stops it
+    Given a serial connection to the device opened
+    When I send a set_port_direction mask "0x4000" value "0x4000" command to the device
+    When I load the sequence:
+      | offset_us | mask   | levels |
+      | 0         | 0x4000 | 0x4000 |
+      | 500       | 0x4000 | 0x0    |
+    When I start the sequence looping every "1000" us
+    When I send a v2 set_value "high" in pin "14" command to the device
+    When I get the sequence state
+    Then the sequence must hold 2 steps and be "stopped"
+
+  Scenario: Invalid sequences are rejected
+    Given a serial connection to the device opened
+    When I load the sequence:
+      | offset_us | mask   | levels |
+      | 1000      | 0x4000 | 0x4000 |
+      | 500       | 0x4000 | 0x0    |
+    Then the device must report the error "invalid_value"
+    When I load the sequence:
+      | offset_us | mask       | levels |
+      | 0         | 0x80000000 | 0x0    |
+    Then the device must report the error "invalid_pin"
+    When I load the sequence:
+      | offset_us | mask   | levels |
+      | 0         | 0x4000 | 0x4000 |
+      | 5000      | 0x4000 | 0x0    |
+    When I start the sequence looping every "1000" us
+    Then the device must report the error "invalid_value"
+    When I send a set_direction "input" in pin "14" command to the device
+    When I start the sequence
+    Then the device must report the error "wrong_direction"
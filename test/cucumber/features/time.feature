@@ -0,0 +1,21 @@
+Feature: Time Feature
+
+  Scenario: Answers are stamped with the device time
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "low" in pin "14" command to the device
+    When I remember the answer timestamp
+    When I wait 50 ms
+    When I send a v2 set_value "high" in pin "14" command to the device
+    Then the answer must echo the request "set_value" on pin "14"
+    Then the answer timestamp must be at least "50000" us after the remembered one
+
+  Scenario: The host reads the device clock
+    Given a serial connection to the device opened
+    When I synchronize the time with the device
+    Then the answer must echo the request "sync_time"
+    Then the time sync must report a round trip shorter than "100000" us
+    When I remember the answer timestamp
+    When I wait 100 ms
+    When I synchronize the time with the device
+    Then the answer timestamp must be at least "100000" us after the remembered one
@@ -0,0 +1,44 @@
+Feature: Wait Feature
+
+  Scenario: A level already reached is answered right away
+    Given a serial connection to the device opened
+    When I send a set_direction "open_drain" in pin "15" command to the device
+    When I send a set_pull "up" in pin "15" command to the device
+    When I send a v2 set_value "high" in pin "15" command to the device
+    When I start waiting for the pins "0x8000" at level "0x8000" within "100000" us
+    When I receive the wait answer
+    Then I must receive a SUCCESS response from the device
+    Then the answer must echo the request "wait_for"
+    Then the wait must have lasted between "0" and "10000" us
+
+  Scenario: Requests are served while waiting for an edge
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "high" in pin "14" command to the device
+    When I start waiting for the pins "0x4000" to change to "0x4000" within "1000000" us
+    When I send a v2 set_value "low" in pin "14" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I wait 50 ms
+    When I send a v2 set_value "high" in pin "14" command to the device
+    Then I must receive a SUCCESS response from the device
+    When I receive the wait answer
+    Then I must receive a SUCCESS response from the device
+    Then the wait must have lasted between "50000" and "500000" us
+
+  Scenario: A wait not met before its timeout fails
+    Given a serial connection to the device opened
+    When I send a set_direction "output" in pin "14" command to the device
+    When I send a v2 set_value "low" in pin "14" command to the device
+    When I start waiting for the pins "0x4000" at level "0x4000" within "20000" us
+    When I receive the wait answer
+    Then the device must report the error "timeout"
+    Then the wait must have lasted between "20000" and "100000" us
+
+  Scenario: Invalid waits are rejected
+    Given a serial connection to the device opened
+    When I start waiting for the pins "0x4000" at level "0x8000" within "1000" us
+    When I receive the wait answer
+    Then the device must report the error "invalid_value"
+    When I start waiting for the pins "0x800000" at level "0x0" within "1000" us
+    When I receive the wait answer
+    Then the device must report the error "invalid_pin"
@@ -7,14 +7,970 @@ pub struct PicohaDioRequest {
     pub pin_num: u32,
     #[prost(enumeration = "PinValue", tag = "3")]
     pub value: i32,
+    #[prost(enumeration = "PinPull", tag = "4")]
+    pub pull: i32,
+    #[prost(enumeration = "PinDriveStrength", tag = "5")]
+    pub drive_strength: i32,
+    #[prost(enumeration = "PinSlewRate", tag = "6")]
+    pub slew_rate: i32,
+    /// Copied back in the answer to match it with this request
+    #[prost(uint32, optional, tag = "7")]
+    pub request_id: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinDirectionRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(enumeration = "Direction", tag = "2")]
+    pub direction: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinValueRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(enumeration = "Level", tag = "2")]
+    pub level: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPinDirectionRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPinValueRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinPullRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(enumeration = "PinPull", tag = "2")]
+    pub pull: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPinPullRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinDriveRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(enumeration = "PinDriveStrength", tag = "2")]
+    pub drive_strength: i32,
+    #[prost(enumeration = "PinSlewRate", tag = "3")]
+    pub slew_rate: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPinDriveRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+}
+/// Change the level of several output pins at once, bit n of the masks is GPIO n
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPortValueRequest {
+    /// Pins to change
+    #[prost(uint32, tag = "1")]
+    pub mask: u32,
+    /// Level of each selected pin, 1 is high
+    #[prost(uint32, tag = "2")]
+    pub value: u32,
+}
+/// Change the direction of several pins at once, bit n of the masks is GPIO n
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPortDirectionRequest {
+    /// Pins to change
+    #[prost(uint32, tag = "1")]
+    pub mask: u32,
+    /// Direction of each selected pin, 1 is output and 0 is input
+    #[prost(uint32, tag = "2")]
+    pub value: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPortStateRequest {}
+/// Snapshot of all the pins, bit n of every mask is GPIO n
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PortState {
+    /// Pins handled by the device
+    #[prost(uint32, tag = "1")]
+    pub available: u32,
+    /// Pins configured as input, push-pull output or open-drain output
+    #[prost(uint32, tag = "2")]
+    pub input: u32,
+    #[prost(uint32, tag = "3")]
+    pub output: u32,
+    #[prost(uint32, tag = "4")]
+    pub open_drain: u32,
+    /// Level on the pads, all sampled in the same cycle
+    #[prost(uint32, tag = "5")]
+    pub input_level: u32,
+    /// Level driven by the output latches
+    #[prost(uint32, tag = "6")]
+    pub output_latch: u32,
+    /// Pins driven by their PWM slice
+    #[prost(uint32, tag = "7")]
+    pub pwm: u32,
+    /// Pins driven by the pattern generator
+    #[prost(uint32, tag = "8")]
+    pub pattern: u32,
+}
+/// Push an EdgeEvent on each selected edge of an input pin, EDGE_NONE stops the events
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeEdgesRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(enumeration = "Edge", tag = "2")]
+    pub edge: i32,
+}
+/// Only accept a new input level once it stayed unchanged for the debounce time
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinDebounceRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    /// 0 disables the filter
+    #[prost(uint32, tag = "2")]
+    pub debounce_us: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPinDebounceRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+}
+/// Count the selected edges of an input pin on the device, EDGE_NONE stops counting
+/// The count restarts from 0
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfigureEdgeCounterRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(enumeration = "Edge", tag = "2")]
+    pub edge: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadEdgeCounterRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    /// Restart the count from 0 once read, no edge is lost in between
+    #[prost(bool, tag = "2")]
+    pub clear: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResetEdgeCounterRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+}
+/// Measure the signal on an input pin with its PWM slice
+/// Only odd pins, the B channel of a slice, can be measured
+/// Cannot be part of a batch, the requests are not served while measuring
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeasureFrequencyRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    /// Duration of each of the two measures, frequency then duty cycle, up to 100 ms
+    #[prost(uint32, tag = "2")]
+    pub gate_time_us: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FrequencyMeasurement {
+    /// Rising edges counted during the gate time
+    #[prost(uint32, tag = "1")]
+    pub edge_count: u32,
+    #[prost(double, tag = "2")]
+    pub frequency_hz: f64,
+    /// 0 when no edge was counted
+    #[prost(double, tag = "3")]
+    pub period_us: f64,
+    /// Part of the time the input was high, from 0 to 1
+    #[prost(double, tag = "4")]
+    pub duty_cycle: f64,
+}
+/// Drive a pin with its PWM slice, the pin goes back to SIO with SetPinDirectionRequest
+/// Pins n and n + 1 (n even) share a slice, so they must use the same frequency
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinPwmRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(uint32, tag = "2")]
+    pub frequency_hz: u32,
+    /// Part of the period the output is high, from 0 to 1
+    #[prost(double, tag = "3")]
+    pub duty_cycle: f64,
+}
+/// Change the duty cycle of a pin already in PWM mode, the period is not restarted
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinPwmDutyRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(double, tag = "2")]
+    pub duty_cycle: f64,
+}
+/// Output really generated, rounded to the slice resolution
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PwmOutput {
+    #[prost(double, tag = "1")]
+    pub frequency_hz: f64,
+    #[prost(double, tag = "2")]
+    pub duty_cycle: f64,
+}
+/// Pulses timed by the device on an output or open-drain pin, the first one starts at once
+/// The pin is left at the other level, a new direction or value cancels the pulses
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PulseRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    /// Level of the pin during the pulses
+    #[prost(enumeration = "Level", tag = "2")]
+    pub active_level: i32,
+    #[prost(uint32, tag = "3")]
+    pub duration_us: u32,
+    /// Number of pulses, 0 is a single pulse
+    #[prost(uint32, tag = "4")]
+    pub repeat_count: u32,
+    /// Time between the starts of two pulses, longer than the duration
+    #[prost(uint32, tag = "5")]
+    pub period_us: u32,
+    /// Answer when the last pulse ended instead of when the first one started
+    /// Ignored in a batch, the batch is answered at once
+    #[prost(bool, tag = "6")]
+    pub wait_completion: bool,
+}
+/// Levels set on the output pins of the mask, at a time offset from the start of the sequence
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SequenceStep {
+    /// Not smaller than the offset of the previous step
+    #[prost(uint32, tag = "1")]
+    pub offset_us: u32,
+    #[prost(uint32, tag = "2")]
+    pub mask: u32,
+    #[prost(uint32, tag = "3")]
+    pub levels: u32,
+}
+/// Store sequence steps in the device RAM, up to 128 steps
+/// Long sequences are loaded with several requests, the sequence must be stopped
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoadSequenceRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub steps: ::prost::alloc::vec::Vec<SequenceStep>,
+    /// Add the steps after the ones already loaded instead of replacing them
+    #[prost(bool, tag = "2")]
+    pub append: bool,
+}
+/// Play the loaded steps from a timer interrupt, on pins set as output
+/// Setting the value or the direction of one of these pins stops the sequence
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartSequenceRequest {
+    /// Restart the sequence every period, at least 100 us and the offset of the last step
+    /// 0 plays the sequence once
+    #[prost(uint32, tag = "1")]
+    pub loop_period_us: u32,
+}
+/// Stop playing the sequence, the pins keep their current levels
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopSequenceRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSequenceStateRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SequenceState {
+    #[prost(uint32, tag = "1")]
+    pub step_count: u32,
+    #[prost(bool, tag = "2")]
+    pub running: bool,
+    /// Times the last step was played since the sequence started
+    #[prost(uint32, tag = "3")]
+    pub completed_loops: u32,
+}
+/// Sample a range of pins at a fixed rate into the device RAM, whatever their direction
+/// A new capture replaces the data of the previous one
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartCaptureRequest {
+    #[prost(uint32, tag = "1")]
+    pub first_pin: u32,
+    #[prost(uint32, tag = "2")]
+    pub pin_count: u32,
+    /// Up to 25 MHz
+    #[prost(uint32, tag = "3")]
+    pub sample_rate_hz: u32,
+    /// Samples kept before the trigger, fewer when the trigger comes earlier
+    #[prost(uint32, tag = "4")]
+    pub pre_trigger_samples: u32,
+    /// Samples from the trigger one, at least 1
+    #[prost(uint32, tag = "5")]
+    pub post_trigger_samples: u32,
+    #[prost(enumeration = "CaptureTrigger", tag = "6")]
+    pub trigger: i32,
+    /// Pattern on the sampled pins, bit n is pin first_pin + n
+    #[prost(uint32, tag = "7")]
+    pub trigger_mask: u32,
+    #[prost(uint32, tag = "8")]
+    pub trigger_value: u32,
+}
+/// Abort the capture in progress, the data of a finished capture is kept
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopCaptureRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCaptureStateRequest {}
+/// Read the captured data from a byte offset, answered with a CaptureChunk
+/// Cannot be part of a batch, a few chunks would not fit in the batch answer
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadCaptureRequest {
+    #[prost(uint32, tag = "1")]
+    pub offset: u32,
+}
+/// The sample fields are set once the capture is done
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CaptureState {
+    #[prost(enumeration = "CaptureStatus", tag = "1")]
+    pub status: i32,
+    #[prost(uint32, tag = "2")]
+    pub first_pin: u32,
+    #[prost(uint32, tag = "3")]
+    pub pin_count: u32,
+    /// Bits per sample in the data, pin_count rounded up to a power of 2
+    /// Sample n of a 32-bit little-endian word is at bit n * sample_width
+    #[prost(uint32, tag = "4")]
+    pub sample_width: u32,
+    /// Real rate, rounded to the clock divider resolution
+    #[prost(double, tag = "5")]
+    pub sample_rate_hz: f64,
+    /// Index of the first valid sample in the first word of the data
+    #[prost(uint32, tag = "6")]
+    pub first_sample: u32,
+    #[prost(uint32, tag = "7")]
+    pub sample_count: u32,
+    /// Index of the trigger sample from the first valid sample
+    #[prost(uint32, tag = "8")]
+    pub trigger_sample: u32,
+    /// Bytes of data, whole 32-bit words
+    #[prost(uint32, tag = "9")]
+    pub data_size: u32,
+    /// Samples overwritten before being searched for the trigger, it may be missed
+    #[prost(bool, tag = "10")]
+    pub samples_skipped: bool,
+}
+/// Part of the captured data, the next chunk starts at offset + the size of data
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CaptureChunk {
+    #[prost(uint32, tag = "1")]
+    pub offset: u32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag = "3")]
+    pub data_size: u32,
+}
+/// Store samples to play on a range of pins, in the layout of the captured data
+/// The data is made of whole 32-bit words, long patterns are loaded in several requests
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoadPatternRequest {
+    #[prost(uint32, tag = "1")]
+    pub first_pin: u32,
+    #[prost(uint32, tag = "2")]
+    pub pin_count: u32,
+    #[prost(bytes = "vec", tag = "3")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Add the data after the samples already loaded, for the same range of pins
+    #[prost(bool, tag = "4")]
+    pub append: bool,
+}
+/// Play the loaded samples on pins set as output, the pins are driven by the pattern
+/// generator until the end of the pattern or a StopPatternRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartPatternRequest {
+    #[prost(uint32, tag = "1")]
+    pub sample_rate_hz: u32,
+    /// Play the pattern again from its first sample after the last one
+    #[prost(bool, tag = "2")]
+    pub looping: bool,
+}
+/// Stop the pattern, the pins stay outputs at the level of the last sample
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopPatternRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPatternStateRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PatternState {
+    #[prost(uint32, tag = "1")]
+    pub first_pin: u32,
+    #[prost(uint32, tag = "2")]
+    pub pin_count: u32,
+    /// Bits per sample in the data, pin_count rounded up to a power of 2
+    #[prost(uint32, tag = "3")]
+    pub sample_width: u32,
+    #[prost(uint32, tag = "4")]
+    pub sample_count: u32,
+    #[prost(bool, tag = "5")]
+    pub running: bool,
+    #[prost(bool, tag = "6")]
+    pub looping: bool,
+    /// Real rate of the last start, rounded to the clock divider resolution
+    #[prost(double, tag = "7")]
+    pub sample_rate_hz: f64,
+}
+/// Wait for the levels of pins, answered when the condition is met or the timeout expires
+/// Other requests are served while waiting, it cannot be part of a batch
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitForRequest {
+    /// Pins watched, bit n is GPIO n, a single pin is a mask of one bit
+    #[prost(uint32, tag = "1")]
+    pub mask: u32,
+    /// Expected levels of the pins of the mask
+    #[prost(uint32, tag = "2")]
+    pub value: u32,
+    #[prost(enumeration = "WaitCondition", tag = "3")]
+    pub condition: i32,
+    #[prost(uint32, tag = "4")]
+    pub timeout_us: u32,
+}
+/// Filled whether the condition is met or not
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitResult {
+    /// Time from the request to the condition or the timeout
+    #[prost(uint32, tag = "1")]
+    pub elapsed_us: u32,
+    /// Levels of the pins of the mask when answered
+    #[prost(uint32, tag = "2")]
+    pub levels: u32,
+}
+/// Read the device clock to estimate its offset and drift from the host clock
+/// With the host times t0 when sending and t3 when receiving the answer, the device clock
+/// is ahead by ((received_us - t0) + (timestamp_us - t3)) / 2
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SyncTimeRequest {
+    /// Host time of the request, copied back in the answer
+    #[prost(uint64, tag = "1")]
+    pub host_time_us: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TimeSync {
+    #[prost(uint64, tag = "1")]
+    pub host_time_us: u64,
+    /// Device time when the request was received, the answer timestamp is when it is sent
+    #[prost(uint64, tag = "2")]
+    pub received_us: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinSafeStateRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(enumeration = "SafeState", tag = "2")]
+    pub state: i32,
+}
+/// The failsafe trips when no valid frame is received during the heartbeat timeout, counted
+/// from the end of the last request. The pins are then put in their safe state, which stops
+/// their pulses, sequence and pattern.
+/// The pins also go to their safe state when the session is closed, on a USB suspend, a bus
+/// reset or the DTR line going low, even with the failsafe disabled
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfigureFailsafeRequest {
+    /// 0 disables the failsafe
+    #[prost(uint32, tag = "1")]
+    pub heartbeat_timeout_ms: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetFailsafeStateRequest {
+    /// Clear the tripped and session closed flags once read
+    #[prost(bool, tag = "1")]
+    pub clear: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FailsafeState {
+    #[prost(uint32, tag = "1")]
+    pub heartbeat_timeout_ms: u32,
+    /// Set when the failsafe trips, until cleared by the host
+    #[prost(bool, tag = "2")]
+    pub tripped: bool,
+    /// Device time of the last trip
+    #[prost(uint64, tag = "3")]
+    pub tripped_at_us: u64,
+    /// Pins with a safe state, bit n is GPIO n
+    #[prost(uint32, tag = "4")]
+    pub safe_pins: u32,
+    /// Set when the session is closed, until cleared by the host, a close is not a trip
+    #[prost(bool, tag = "5")]
+    pub session_closed: bool,
+    /// Device time of the last session close
+    #[prost(uint64, tag = "6")]
+    pub session_closed_at_us: u64,
+}
+/// Save the direction, level, pull, drive and safe state of every pin as its power-on
+/// configuration, PWM and pattern pins are saved as outputs at their current level
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SaveConfigRequest {}
+/// Read the power-on configuration saved for a pin
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSavedConfigRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+}
+/// Erase the saved configuration, the pins start as inputs with pull-down again
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EraseConfigRequest {}
+/// Put every pin in its factory configuration now, input with pull-down and no safe state,
+/// the saved configuration is kept
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestoreDefaultsRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SavedPinConfig {
+    /// False when no valid configuration is saved, the other fields are then the factory ones
+    #[prost(bool, tag = "1")]
+    pub saved: bool,
+    #[prost(uint32, tag = "2")]
+    pub version: u32,
+    #[prost(enumeration = "Direction", tag = "3")]
+    pub direction: i32,
+    /// Output level, open-drain pins release the line when high
+    #[prost(enumeration = "Level", tag = "4")]
+    pub level: i32,
+    #[prost(enumeration = "PinPull", tag = "5")]
+    pub pull: i32,
+    #[prost(enumeration = "PinDriveStrength", tag = "6")]
+    pub drive_strength: i32,
+    #[prost(enumeration = "PinSlewRate", tag = "7")]
+    pub slew_rate: i32,
+    #[prost(enumeration = "SafeState", tag = "8")]
+    pub safe_state: i32,
+}
+/// Name a pin, an empty name removes it. A name has up to 16 letters, digits, '_', '-' or
+/// '.', and is used by a single pin. The names are written in the flash right away.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPinNameRequest {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+}
+/// Read the names of all the named pins, cannot be part of a batch
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPinNamesRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PinName {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+}
+/// One operation of a batch, same tags as the matching v2 payloads
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchOperation {
+    /// Name of the pin targeted by the operation, replaces its pin_num
+    #[prost(string, tag = "1")]
+    pub pin_name: ::prost::alloc::string::String,
+    #[prost(
+        oneof = "batch_operation::Operation",
+        tags = "17, 18, 19, 20, 21, 22, 23, 24, 26, 27, 28, 29, 30, 31, 32, 33, 34, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 47, 48, 49, 50, 53, 54, 55, 56, 57, 58, 59, 60"
+    )]
+    pub operation: ::core::option::Option<batch_operation::Operation>,
+}
+/// Nested message and enum types in `BatchOperation`.
+pub mod batch_operation {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Operation {
+        #[prost(message, tag = "17")]
+        SetPinDirection(super::SetPinDirectionRequest),
+        #[prost(message, tag = "18")]
+        SetPinValue(super::SetPinValueRequest),
+        #[prost(message, tag = "19")]
+        GetPinDirection(super::GetPinDirectionRequest),
+        #[prost(message, tag = "20")]
+        GetPinValue(super::GetPinValueRequest),
+        #[prost(message, tag = "21")]
+        SetPinPull(super::SetPinPullRequest),
+        #[prost(message, tag = "22")]
+        GetPinPull(super::GetPinPullRequest),
+        #[prost(message, tag = "23")]
+        SetPinDrive(super::SetPinDriveRequest),
+        #[prost(message, tag = "24")]
+        GetPinDrive(super::GetPinDriveRequest),
+        #[prost(message, tag = "26")]
+        SetPortValue(super::SetPortValueRequest),
+        #[prost(message, tag = "27")]
+        SetPortDirection(super::SetPortDirectionRequest),
+        #[prost(message, tag = "28")]
+        GetPortState(super::GetPortStateRequest),
+        #[prost(message, tag = "29")]
+        SubscribeEdges(super::SubscribeEdgesRequest),
+        #[prost(message, tag = "30")]
+        SetPinDebounce(super::SetPinDebounceRequest),
+        #[prost(message, tag = "31")]
+        GetPinDebounce(super::GetPinDebounceRequest),
+        #[prost(message, tag = "32")]
+        ConfigureEdgeCounter(super::ConfigureEdgeCounterRequest),
+        #[prost(message, tag = "33")]
+        ReadEdgeCounter(super::ReadEdgeCounterRequest),
+        #[prost(message, tag = "34")]
+        ResetEdgeCounter(super::ResetEdgeCounterRequest),
+        #[prost(message, tag = "36")]
+        SetPinPwm(super::SetPinPwmRequest),
+        #[prost(message, tag = "37")]
+        SetPinPwmDuty(super::SetPinPwmDutyRequest),
+        #[prost(message, tag = "38")]
+        Pulse(super::PulseRequest),
+        #[prost(message, tag = "39")]
+        LoadSequence(super::LoadSequenceRequest),
+        #[prost(message, tag = "40")]
+        StartSequence(super::StartSequenceRequest),
+        #[prost(message, tag = "41")]
+        StopSequence(super::StopSequenceRequest),
+        #[prost(message, tag = "42")]
+        GetSequenceState(super::GetSequenceStateRequest),
+        #[prost(message, tag = "43")]
+        StartCapture(super::StartCaptureRequest),
+        #[prost(message, tag = "44")]
+        StopCapture(super::StopCaptureRequest),
+        #[prost(message, tag = "45")]
+        GetCaptureState(super::GetCaptureStateRequest),
+        #[prost(message, tag = "47")]
+        LoadPattern(super::LoadPatternRequest),
+        #[prost(message, tag = "48")]
+        StartPattern(super::StartPatternRequest),
+        #[prost(message, tag = "49")]
+        StopPattern(super::StopPatternRequest),
+        #[prost(message, tag = "50")]
+        GetPatternState(super::GetPatternStateRequest),
+        #[prost(message, tag = "53")]
+        SetPinSafeState(super::SetPinSafeStateRequest),
+        #[prost(message, tag = "54")]
+        ConfigureFailsafe(super::ConfigureFailsafeRequest),
+        #[prost(message, tag = "55")]
+        GetFailsafeState(super::GetFailsafeStateRequest),
+        #[prost(message, tag = "56")]
+        SaveConfig(super::SaveConfigRequest),
+        #[prost(message, tag = "57")]
+        GetSavedConfig(super::GetSavedConfigRequest),
+        #[prost(message, tag = "58")]
+        EraseConfig(super::EraseConfigRequest),
+        #[prost(message, tag = "59")]
+        RestoreDefaults(super::RestoreDefaultsRequest),
+        #[prost(message, tag = "60")]
+        SetPinName(super::SetPinNameRequest),
+    }
+}
+/// Operations executed in order, answered with a single PicohaDioAnswer
+/// The batch stops with ERROR_CODE_ANSWER_TOO_LARGE before an operation whose result might not
+/// fit in the answer, the results of the operations already executed are kept
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub operations: ::prost::alloc::vec::Vec<BatchOperation>,
+    /// Skip the remaining operations after the first failure
+    #[prost(bool, tag = "2")]
+    pub stop_on_failure: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PicohaDioRequestV2 {
+    /// Same tag as the v1 request_id, copied back in the answer
+    #[prost(uint32, optional, tag = "7")]
+    pub request_id: ::core::option::Option<u32>,
+    /// Name of the pin targeted by the request, replaces its pin_num
+    #[prost(string, tag = "8")]
+    pub pin_name: ::prost::alloc::string::String,
+    #[prost(
+        oneof = "picoha_dio_request_v2::Payload",
+        tags = "16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61"
+    )]
+    pub payload: ::core::option::Option<picoha_dio_request_v2::Payload>,
+}
+/// Nested message and enum types in `PicohaDioRequestV2`.
+pub mod picoha_dio_request_v2 {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payload {
+        #[prost(message, tag = "16")]
+        Ping(super::PingRequest),
+        #[prost(message, tag = "17")]
+        SetPinDirection(super::SetPinDirectionRequest),
+        #[prost(message, tag = "18")]
+        SetPinValue(super::SetPinValueRequest),
+        #[prost(message, tag = "19")]
+        GetPinDirection(super::GetPinDirectionRequest),
+        #[prost(message, tag = "20")]
+        GetPinValue(super::GetPinValueRequest),
+        #[prost(message, tag = "21")]
+        SetPinPull(super::SetPinPullRequest),
+        #[prost(message, tag = "22")]
+        GetPinPull(super::GetPinPullRequest),
+        #[prost(message, tag = "23")]
+        SetPinDrive(super::SetPinDriveRequest),
+        #[prost(message, tag = "24")]
+        GetPinDrive(super::GetPinDriveRequest),
+        #[prost(message, tag = "25")]
+        Batch(super::BatchRequest),
+        #[prost(message, tag = "26")]
+        SetPortValue(super::SetPortValueRequest),
+        #[prost(message, tag = "27")]
+        SetPortDirection(super::SetPortDirectionRequest),
+        #[prost(message, tag = "28")]
+        GetPortState(super::GetPortStateRequest),
+        #[prost(message, tag = "29")]
+        SubscribeEdges(super::SubscribeEdgesRequest),
+        #[prost(message, tag = "30")]
+        SetPinDebounce(super::SetPinDebounceRequest),
+        #[prost(message, tag = "31")]
+        GetPinDebounce(super::GetPinDebounceRequest),
+        #[prost(message, tag = "32")]
+        ConfigureEdgeCounter(super::ConfigureEdgeCounterRequest),
+        #[prost(message, tag = "33")]
+        ReadEdgeCounter(super::ReadEdgeCounterRequest),
+        #[prost(message, tag = "34")]
+        ResetEdgeCounter(super::ResetEdgeCounterRequest),
+        #[prost(message, tag = "35")]
+        MeasureFrequency(super::MeasureFrequencyRequest),
+        #[prost(message, tag = "36")]
+        SetPinPwm(super::SetPinPwmRequest),
+        #[prost(message, tag = "37")]
+        SetPinPwmDuty(super::SetPinPwmDutyRequest),
+        #[prost(message, tag = "38")]
+        Pulse(super::PulseRequest),
+        #[prost(message, tag = "39")]
+        LoadSequence(super::LoadSequenceRequest),
+        #[prost(message, tag = "40")]
+        StartSequence(super::StartSequenceRequest),
+        #[prost(message, tag = "41")]
+        StopSequence(super::StopSequenceRequest),
+        #[prost(message, tag = "42")]
+        GetSequenceState(super::GetSequenceStateRequest),
+        #[prost(message, tag = "43")]
+        StartCapture(super::StartCaptureRequest),
+        #[prost(message, tag = "44")]
+        StopCapture(super::StopCaptureRequest),
+        #[prost(message, tag = "45")]
+        GetCaptureState(super::GetCaptureStateRequest),
+        #[prost(message, tag = "46")]
+        ReadCapture(super::ReadCaptureRequest),
+        #[prost(message, tag = "47")]
+        LoadPattern(super::LoadPatternRequest),
+        #[prost(message, tag = "48")]
+        StartPattern(super::StartPatternRequest),
+        #[prost(message, tag = "49")]
+        StopPattern(super::StopPatternRequest),
+        #[prost(message, tag = "50")]
+        GetPatternState(super::GetPatternStateRequest),
+        #[prost(message, tag = "51")]
+        WaitFor(super::WaitForRequest),
+        #[prost(message, tag = "52")]
+        SyncTime(super::SyncTimeRequest),
+        #[prost(message, tag = "53")]
+        SetPinSafeState(super::SetPinSafeStateRequest),
+        #[prost(message, tag = "54")]
+        ConfigureFailsafe(super::ConfigureFailsafeRequest),
+        #[prost(message, tag = "55")]
+        GetFailsafeState(super::GetFailsafeStateRequest),
+        #[prost(message, tag = "56")]
+        SaveConfig(super::SaveConfigRequest),
+        #[prost(message, tag = "57")]
+        GetSavedConfig(super::GetSavedConfigRequest),
+        #[prost(message, tag = "58")]
+        EraseConfig(super::EraseConfigRequest),
+        #[prost(message, tag = "59")]
+        RestoreDefaults(super::RestoreDefaultsRequest),
+        #[prost(message, tag = "60")]
+        SetPinName(super::SetPinNameRequest),
+        #[prost(message, tag = "61")]
+        GetPinNames(super::GetPinNamesRequest),
+    }
+}
+/// Result of one operation of a batch, values are filled like in PicohaDioAnswer
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchOperationResult {
+    #[prost(enumeration = "AnswerType", tag = "1")]
+    pub r#type: i32,
+    #[prost(enumeration = "ErrorCode", optional, tag = "2")]
+    pub error_code: ::core::option::Option<i32>,
+    #[prost(enumeration = "Direction", optional, tag = "3")]
+    pub direction: ::core::option::Option<i32>,
+    #[prost(enumeration = "Level", optional, tag = "4")]
+    pub level: ::core::option::Option<i32>,
+    #[prost(enumeration = "PinPull", optional, tag = "5")]
+    pub pull: ::core::option::Option<i32>,
+    #[prost(enumeration = "PinDriveStrength", optional, tag = "6")]
+    pub drive_strength: ::core::option::Option<i32>,
+    #[prost(enumeration = "PinSlewRate", optional, tag = "7")]
+    pub slew_rate: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "8")]
+    pub port_state: ::core::option::Option<PortState>,
+    #[prost(uint32, optional, tag = "9")]
+    pub debounce_us: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "10")]
+    pub edge_count: ::core::option::Option<u32>,
+    #[prost(enumeration = "Edge", optional, tag = "11")]
+    pub counted_edge: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "12")]
+    pub frequency: ::core::option::Option<FrequencyMeasurement>,
+    #[prost(message, optional, tag = "13")]
+    pub pwm: ::core::option::Option<PwmOutput>,
+    #[prost(message, optional, tag = "14")]
+    pub sequence: ::core::option::Option<SequenceState>,
+    #[prost(message, optional, tag = "15")]
+    pub capture: ::core::option::Option<CaptureState>,
+    #[prost(message, optional, tag = "17")]
+    pub pattern: ::core::option::Option<PatternState>,
+    #[prost(uint64, optional, tag = "18")]
+    pub timestamp_us: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "19")]
+    pub failsafe: ::core::option::Option<FailsafeState>,
+    #[prost(message, optional, tag = "20")]
+    pub saved_config: ::core::option::Option<SavedPinConfig>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PicohaDioAnswer {
     #[prost(enumeration = "AnswerType", tag = "1")]
     pub r#type: i32,
-    #[prost(enumeration = "PinValue", optional, tag = "3")]
+    #[prost(enumeration = "PinValue", optional, tag = "2")]
     pub value: ::core::option::Option<i32>,
+    #[prost(string, optional, tag = "3")]
+    pub error_message: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "PinPull", optional, tag = "4")]
+    pub pull: ::core::option::Option<i32>,
+    #[prost(enumeration = "PinDriveStrength", optional, tag = "5")]
+    pub drive_strength: ::core::option::Option<i32>,
+    #[prost(enumeration = "PinSlewRate", optional, tag = "6")]
+    pub slew_rate: ::core::option::Option<i32>,
+    #[prost(enumeration = "Direction", optional, tag = "7")]
+    pub direction: ::core::option::Option<i32>,
+    #[prost(enumeration = "Level", optional, tag = "8")]
+    pub level: ::core::option::Option<i32>,
+    #[prost(enumeration = "ErrorCode", optional, tag = "9")]
+    pub error_code: ::core::option::Option<i32>,
+    /// Echo of the request this answer belongs to
+    #[prost(uint32, optional, tag = "10")]
+    pub request_id: ::core::option::Option<u32>,
+    #[prost(enumeration = "RequestType", optional, tag = "11")]
+    pub request_type: ::core::option::Option<i32>,
+    #[prost(uint32, optional, tag = "12")]
+    pub pin_num: ::core::option::Option<u32>,
+    /// One result per executed operation of a batch, in order
+    #[prost(message, repeated, tag = "13")]
+    pub batch_results: ::prost::alloc::vec::Vec<BatchOperationResult>,
+    #[prost(message, optional, tag = "14")]
+    pub port_state: ::core::option::Option<PortState>,
+    #[prost(uint32, optional, tag = "15")]
+    pub debounce_us: ::core::option::Option<u32>,
+    /// Edge count of the pin and the edges it counts
+    #[prost(uint32, optional, tag = "16")]
+    pub edge_count: ::core::option::Option<u32>,
+    #[prost(enumeration = "Edge", optional, tag = "17")]
+    pub counted_edge: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "18")]
+    pub frequency: ::core::option::Option<FrequencyMeasurement>,
+    #[prost(message, optional, tag = "19")]
+    pub pwm: ::core::option::Option<PwmOutput>,
+    #[prost(message, optional, tag = "20")]
+    pub sequence: ::core::option::Option<SequenceState>,
+    #[prost(message, optional, tag = "21")]
+    pub capture: ::core::option::Option<CaptureState>,
+    #[prost(message, optional, tag = "22")]
+    pub capture_chunk: ::core::option::Option<CaptureChunk>,
+    #[prost(message, optional, tag = "23")]
+    pub pattern: ::core::option::Option<PatternState>,
+    #[prost(message, optional, tag = "24")]
+    pub wait: ::core::option::Option<WaitResult>,
+    /// Device time when the operation took effect, microseconds since boot
+    #[prost(uint64, optional, tag = "25")]
+    pub timestamp_us: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "26")]
+    pub time_sync: ::core::option::Option<TimeSync>,
+    #[prost(message, optional, tag = "27")]
+    pub failsafe: ::core::option::Option<FailsafeState>,
+    #[prost(message, optional, tag = "28")]
+    pub saved_config: ::core::option::Option<SavedPinConfig>,
+    /// Named pins, in pin number order
+    #[prost(message, repeated, tag = "29")]
+    pub pin_names: ::prost::alloc::vec::Vec<PinName>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EdgeEvent {
+    #[prost(uint32, tag = "1")]
+    pub pin_num: u32,
+    /// Level of the pin after the edge
+    #[prost(enumeration = "Level", tag = "2")]
+    pub level: i32,
+    /// Device time of the edge, microseconds since boot
+    #[prost(uint64, tag = "3")]
+    pub timestamp_us: u64,
+    /// Events dropped just before this one because they were not sent in time
+    #[prost(uint32, tag = "4")]
+    pub lost_events: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PicohaDioEvent {
+    #[prost(oneof = "picoha_dio_event::Event", tags = "32")]
+    pub event: ::core::option::Option<picoha_dio_event::Event>,
+}
+/// Nested message and enum types in `PicohaDioEvent`.
+pub mod picoha_dio_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag = "32")]
+        Edge(super::EdgeEvent),
+    }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -24,6 +980,48 @@ pub enum RequestType {
     SetPinValue = 2,
     GetPinDirection = 3,
     GetPinValue = 4,
+    SetPinPull = 5,
+    GetPinPull = 6,
+    SetPinDrive = 7,
+    GetPinDrive = 8,
+    /// v2 only, a v1 request cannot carry the operations
+    Batch = 9,
+    SetPortValue = 10,
+    SetPortDirection = 11,
+    GetPortState = 12,
+    SubscribeEdges = 13,
+    SetPinDebounce = 14,
+    GetPinDebounce = 15,
+    ConfigureEdgeCounter = 16,
+    ReadEdgeCounter = 17,
+    ResetEdgeCounter = 18,
+    MeasureFrequency = 19,
+    SetPinPwm = 20,
+    SetPinPwmDuty = 21,
+    Pulse = 22,
+    LoadSequence = 23,
+    StartSequence = 24,
+    StopSequence = 25,
+    GetSequenceState = 26,
+    StartCapture = 27,
+    StopCapture = 28,
+    GetCaptureState = 29,
+    ReadCapture = 30,
+    LoadPattern = 31,
+    StartPattern = 32,
+    StopPattern = 33,
+    GetPatternState = 34,
+    WaitFor = 35,
+    SyncTime = 36,
+    SetPinSafeState = 37,
+    ConfigureFailsafe = 38,
+    GetFailsafeState = 39,
+    SaveConfig = 40,
+    GetSavedConfig = 41,
+    EraseConfig = 42,
+    RestoreDefaults = 43,
+    SetPinName = 44,
+    GetPinNames = 45,
 }
 impl RequestType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -37,6 +1035,47 @@ impl RequestType {
             RequestType::SetPinValue => "SET_PIN_VALUE",
             RequestType::GetPinDirection => "GET_PIN_DIRECTION",
             RequestType::GetPinValue => "GET_PIN_VALUE",
+            RequestType::SetPinPull => "SET_PIN_PULL",
+            RequestType::GetPinPull => "GET_PIN_PULL",
+            RequestType::SetPinDrive => "SET_PIN_DRIVE",
+            RequestType::GetPinDrive => "GET_PIN_DRIVE",
+            RequestType::Batch => "BATCH",
+            RequestType::SetPortValue => "SET_PORT_VALUE",
+            RequestType::SetPortDirection => "SET_PORT_DIRECTION",
+            RequestType::GetPortState => "GET_PORT_STATE",
+            RequestType::SubscribeEdges => "SUBSCRIBE_EDGES",
+            RequestType::SetPinDebounce => "SET_PIN_DEBOUNCE",
+            RequestType::GetPinDebounce => "GET_PIN_DEBOUNCE",
+            RequestType::ConfigureEdgeCounter => "CONFIGURE_EDGE_COUNTER",
+            RequestType::ReadEdgeCounter => "READ_EDGE_COUNTER",
+            RequestType::ResetEdgeCounter => "RESET_EDGE_COUNTER",
+            RequestType::MeasureFrequency => "MEASURE_FREQUENCY",
+            RequestType::SetPinPwm => "SET_PIN_PWM",
+            RequestType::SetPinPwmDuty => "SET_PIN_PWM_DUTY",
+            RequestType::Pulse => "PULSE",
+            RequestType::LoadSequence => "LOAD_SEQUENCE",
+            RequestType::StartSequence => "START_SEQUENCE",
+            RequestType::StopSequence => "STOP_SEQUENCE",
+            RequestType::GetSequenceState => "GET_SEQUENCE_STATE",
+            RequestType::StartCapture => "START_CAPTURE",
+            RequestType::StopCapture => "STOP_CAPTURE",
+            RequestType::GetCaptureState => "GET_CAPTURE_STATE",
+            RequestType::ReadCapture => "READ_CAPTURE",
+            RequestType::LoadPattern => "LOAD_PATTERN",
+            RequestType::StartPattern => "START_PATTERN",
+            RequestType::StopPattern => "STOP_PATTERN",
+            RequestType::GetPatternState => "GET_PATTERN_STATE",
+            RequestType::WaitFor => "WAIT_FOR",
+            RequestType::SyncTime => "SYNC_TIME",
+            RequestType::SetPinSafeState => "SET_PIN_SAFE_STATE",
+            RequestType::ConfigureFailsafe => "CONFIGURE_FAILSAFE",
+            RequestType::GetFailsafeState => "GET_FAILSAFE_STATE",
+            RequestType::SaveConfig => "SAVE_CONFIG",
+            RequestType::GetSavedConfig => "GET_SAVED_CONFIG",
+            RequestType::EraseConfig => "ERASE_CONFIG",
+            RequestType::RestoreDefaults => "RESTORE_DEFAULTS",
+            RequestType::SetPinName => "SET_PIN_NAME",
+            RequestType::GetPinNames => "GET_PIN_NAMES",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -47,10 +1086,53 @@ impl RequestType {
             "SET_PIN_VALUE" => Some(Self::SetPinValue),
             "GET_PIN_DIRECTION" => Some(Self::GetPinDirection),
             "GET_PIN_VALUE" => Some(Self::GetPinValue),
+            "SET_PIN_PULL" => Some(Self::SetPinPull),
+            "GET_PIN_PULL" => Some(Self::GetPinPull),
+            "SET_PIN_DRIVE" => Some(Self::SetPinDrive),
+            "GET_PIN_DRIVE" => Some(Self::GetPinDrive),
+            "BATCH" => Some(Self::Batch),
+            "SET_PORT_VALUE" => Some(Self::SetPortValue),
+            "SET_PORT_DIRECTION" => Some(Self::SetPortDirection),
+            "GET_PORT_STATE" => Some(Self::GetPortState),
+            "SUBSCRIBE_EDGES" => Some(Self::SubscribeEdges),
+            "SET_PIN_DEBOUNCE" => Some(Self::SetPinDebounce),
+            "GET_PIN_DEBOUNCE" => Some(Self::GetPinDebounce),
+            "CONFIGURE_EDGE_COUNTER" => Some(Self::ConfigureEdgeCounter),
+            "READ_EDGE_COUNTER" => Some(Self::ReadEdgeCounter),
+            "RESET_EDGE_COUNTER" => Some(Self::ResetEdgeCounter),
+            "MEASURE_FREQUENCY" => Some(Self::MeasureFrequency),
+            "SET_PIN_PWM" => Some(Self::SetPinPwm),
+            "SET_PIN_PWM_DUTY" => Some(Self::SetPinPwmDuty),
+            "PULSE" => Some(Self::Pulse),
+            "LOAD_SEQUENCE" => Some(Self::LoadSequence),
+            "START_SEQUENCE" => Some(Self::StartSequence),
+            "STOP_SEQUENCE" => Some(Self::StopSequence),
+            "GET_SEQUENCE_STATE" => Some(Self::GetSequenceState),
+            "START_CAPTURE" => Some(Self::StartCapture),
+            "STOP_CAPTURE" => Some(Self::StopCapture),
+            "GET_CAPTURE_STATE" => Some(Self::GetCaptureState),
+            "READ_CAPTURE" => Some(Self::ReadCapture),
+            "LOAD_PATTERN" => Some(Self::LoadPattern),
+            "START_PATTERN" => Some(Self::StartPattern),
+            "STOP_PATTERN" => Some(Self::StopPattern),
+            "GET_PATTERN_STATE" => Some(Self::GetPatternState),
+            "WAIT_FOR" => Some(Self::WaitFor),
+            "SYNC_TIME" => Some(Self::SyncTime),
+            "SET_PIN_SAFE_STATE" => Some(Self::SetPinSafeState),
+            "CONFIGURE_FAILSAFE" => Some(Self::ConfigureFailsafe),
+            "GET_FAILSAFE_STATE" => Some(Self::GetFailsafeState),
+            "SAVE_CONFIG" => Some(Self::SaveConfig),
+            "GET_SAVED_CONFIG" => Some(Self::GetSavedConfig),
+            "ERASE_CONFIG" => Some(Self::EraseConfig),
+            "RESTORE_DEFAULTS" => Some(Self::RestoreDefaults),
+            "SET_PIN_NAME" => Some(Self::SetPinName),
+            "GET_PIN_NAMES" => Some(Self::GetPinNames),
             _ => None,
         }
     }
 }
+/// Protocol v1 value, mixes levels and directions
+/// Replaced by Direction and Level in protocol v2
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum PinValue {
@@ -58,6 +1140,7 @@ pub enum PinValue {
     High = 1,
     Input = 2,
     Output = 3,
+    OpenDrain = 4,
 }
 impl PinValue {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -70,6 +1153,7 @@ impl PinValue {
             PinValue::High => "HIGH",
             PinValue::Input => "INPUT",
             PinValue::Output => "OUTPUT",
+            PinValue::OpenDrain => "OPEN_DRAIN",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -79,6 +1163,327 @@ impl PinValue {
             "HIGH" => Some(Self::High),
             "INPUT" => Some(Self::Input),
             "OUTPUT" => Some(Self::Output),
+            "OPEN_DRAIN" => Some(Self::OpenDrain),
+            _ => None,
+        }
+    }
+}
+/// Pull resistor applied on the pin pad
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PinPull {
+    PullNone = 0,
+    PullUp = 1,
+    PullDown = 2,
+    PullBusKeep = 3,
+}
+impl PinPull {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PinPull::PullNone => "PULL_NONE",
+            PinPull::PullUp => "PULL_UP",
+            PinPull::PullDown => "PULL_DOWN",
+            PinPull::PullBusKeep => "PULL_BUS_KEEP",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PULL_NONE" => Some(Self::PullNone),
+            "PULL_UP" => Some(Self::PullUp),
+            "PULL_DOWN" => Some(Self::PullDown),
+            "PULL_BUS_KEEP" => Some(Self::PullBusKeep),
+            _ => None,
+        }
+    }
+}
+/// Output drive strength of the pin pad
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PinDriveStrength {
+    Drive2ma = 0,
+    Drive4ma = 1,
+    Drive8ma = 2,
+    Drive12ma = 3,
+}
+impl PinDriveStrength {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PinDriveStrength::Drive2ma => "DRIVE_2MA",
+            PinDriveStrength::Drive4ma => "DRIVE_4MA",
+            PinDriveStrength::Drive8ma => "DRIVE_8MA",
+            PinDriveStrength::Drive12ma => "DRIVE_12MA",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DRIVE_2MA" => Some(Self::Drive2ma),
+            "DRIVE_4MA" => Some(Self::Drive4ma),
+            "DRIVE_8MA" => Some(Self::Drive8ma),
+            "DRIVE_12MA" => Some(Self::Drive12ma),
+            _ => None,
+        }
+    }
+}
+/// Output slew rate of the pin pad
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PinSlewRate {
+    SlewSlow = 0,
+    SlewFast = 1,
+}
+impl PinSlewRate {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PinSlewRate::SlewSlow => "SLEW_SLOW",
+            PinSlewRate::SlewFast => "SLEW_FAST",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SLEW_SLOW" => Some(Self::SlewSlow),
+            "SLEW_FAST" => Some(Self::SlewFast),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Direction {
+    Input = 0,
+    Output = 1,
+    OpenDrain = 2,
+    /// Only reported, a pin enters PWM mode with SetPinPwmRequest
+    Pwm = 3,
+    /// Only reported, the pins of a pattern enter this mode while it plays
+    Pattern = 4,
+}
+impl Direction {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Direction::Input => "DIRECTION_INPUT",
+            Direction::Output => "DIRECTION_OUTPUT",
+            Direction::OpenDrain => "DIRECTION_OPEN_DRAIN",
+            Direction::Pwm => "DIRECTION_PWM",
+            Direction::Pattern => "DIRECTION_PATTERN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DIRECTION_INPUT" => Some(Self::Input),
+            "DIRECTION_OUTPUT" => Some(Self::Output),
+            "DIRECTION_OPEN_DRAIN" => Some(Self::OpenDrain),
+            "DIRECTION_PWM" => Some(Self::Pwm),
+            "DIRECTION_PATTERN" => Some(Self::Pattern),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Level {
+    Low = 0,
+    High = 1,
+}
+impl Level {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Level::Low => "LEVEL_LOW",
+            Level::High => "LEVEL_HIGH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "LEVEL_LOW" => Some(Self::Low),
+            "LEVEL_HIGH" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+/// Edges reported by a pin
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Edge {
+    None = 0,
+    Rising = 1,
+    Falling = 2,
+    Both = 3,
+}
+impl Edge {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Edge::None => "EDGE_NONE",
+            Edge::Rising => "EDGE_RISING",
+            Edge::Falling => "EDGE_FALLING",
+            Edge::Both => "EDGE_BOTH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "EDGE_NONE" => Some(Self::None),
+            "EDGE_RISING" => Some(Self::Rising),
+            "EDGE_FALLING" => Some(Self::Falling),
+            "EDGE_BOTH" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CaptureTrigger {
+    /// Trigger on the first sample
+    Now = 0,
+    /// First sample matching the pattern
+    Level = 1,
+    /// First sample matching the pattern after a sample that did not
+    Edge = 2,
+}
+impl CaptureTrigger {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CaptureTrigger::Now => "CAPTURE_TRIGGER_NOW",
+            CaptureTrigger::Level => "CAPTURE_TRIGGER_LEVEL",
+            CaptureTrigger::Edge => "CAPTURE_TRIGGER_EDGE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CAPTURE_TRIGGER_NOW" => Some(Self::Now),
+            "CAPTURE_TRIGGER_LEVEL" => Some(Self::Level),
+            "CAPTURE_TRIGGER_EDGE" => Some(Self::Edge),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CaptureStatus {
+    Idle = 0,
+    WaitingTrigger = 1,
+    Triggered = 2,
+    Done = 3,
+    /// Stopped by the host, or no trigger before 2^32 - 1 words
+    Aborted = 4,
+}
+impl CaptureStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CaptureStatus::Idle => "CAPTURE_STATUS_IDLE",
+            CaptureStatus::WaitingTrigger => "CAPTURE_STATUS_WAITING_TRIGGER",
+            CaptureStatus::Triggered => "CAPTURE_STATUS_TRIGGERED",
+            CaptureStatus::Done => "CAPTURE_STATUS_DONE",
+            CaptureStatus::Aborted => "CAPTURE_STATUS_ABORTED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CAPTURE_STATUS_IDLE" => Some(Self::Idle),
+            "CAPTURE_STATUS_WAITING_TRIGGER" => Some(Self::WaitingTrigger),
+            "CAPTURE_STATUS_TRIGGERED" => Some(Self::Triggered),
+            "CAPTURE_STATUS_DONE" => Some(Self::Done),
+            "CAPTURE_STATUS_ABORTED" => Some(Self::Aborted),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WaitCondition {
+    /// The pins have the expected levels, right away if they already have them
+    Level = 0,
+    /// The pins change into the expected levels, from levels that did not match
+    Edge = 1,
+}
+impl WaitCondition {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WaitCondition::Level => "WAIT_CONDITION_LEVEL",
+            WaitCondition::Edge => "WAIT_CONDITION_EDGE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "WAIT_CONDITION_LEVEL" => Some(Self::Level),
+            "WAIT_CONDITION_EDGE" => Some(Self::Edge),
+            _ => None,
+        }
+    }
+}
+/// State a pin is put in when the failsafe trips
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SafeState {
+    /// The pin is left as it is
+    None = 0,
+    Input = 1,
+    /// Push-pull output at a level
+    Low = 2,
+    High = 3,
+}
+impl SafeState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SafeState::None => "SAFE_STATE_NONE",
+            SafeState::Input => "SAFE_STATE_INPUT",
+            SafeState::Low => "SAFE_STATE_LOW",
+            SafeState::High => "SAFE_STATE_HIGH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SAFE_STATE_NONE" => Some(Self::None),
+            "SAFE_STATE_INPUT" => Some(Self::Input),
+            "SAFE_STATE_LOW" => Some(Self::Low),
+            "SAFE_STATE_HIGH" => Some(Self::High),
             _ => None,
         }
     }
@@ -109,3 +1514,81 @@ impl AnswerType {
         }
     }
 }
+/// Reason of a FAILURE answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ErrorCode {
+    None = 0,
+    /// Pin number out of range or not handled by the device
+    InvalidPin = 1,
+    /// Pin used by the debug UART (firmware built with uart0_debug)
+    PinReserved = 2,
+    /// Operation not allowed with the current pin direction
+    WrongDirection = 3,
+    InvalidValue = 4,
+    UnknownRequestType = 5,
+    /// Frame received but not a valid request
+    DecodeFailure = 6,
+    /// Pin has no direction configured
+    PinNotConfigured = 7,
+    /// Pin cannot do this operation
+    NotSupported = 8,
+    /// Hardware block already used by another pin
+    ResourceBusy = 9,
+    /// Operation stopped by a later request before its end
+    Cancelled = 10,
+    /// Condition not met before the timeout
+    Timeout = 11,
+    /// Flash not written as expected
+    StorageFailure = 12,
+    /// No pin has the name given in the request
+    UnknownPinName = 13,
+    /// Results of the batch too large for the answer
+    AnswerTooLarge = 14,
+}
+impl ErrorCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ErrorCode::None => "ERROR_CODE_NONE",
+            ErrorCode::InvalidPin => "ERROR_CODE_INVALID_PIN",
+            ErrorCode::PinReserved => "ERROR_CODE_PIN_RESERVED",
+            ErrorCode::WrongDirection => "ERROR_CODE_WRONG_DIRECTION",
+            ErrorCode::InvalidValue => "ERROR_CODE_INVALID_VALUE",
+            ErrorCode::UnknownRequestType => "ERROR_CODE_UNKNOWN_REQUEST_TYPE",
+            ErrorCode::DecodeFailure => "ERROR_CODE_DECODE_FAILURE",
+            ErrorCode::PinNotConfigured => "ERROR_CODE_PIN_NOT_CONFIGURED",
+            ErrorCode::NotSupported => "ERROR_CODE_NOT_SUPPORTED",
+            ErrorCode::ResourceBusy => "ERROR_CODE_RESOURCE_BUSY",
+            ErrorCode::Cancelled => "ERROR_CODE_CANCELLED",
+            ErrorCode::Timeout => "ERROR_CODE_TIMEOUT",
+            ErrorCode::StorageFailure => "ERROR_CODE_STORAGE_FAILURE",
+            ErrorCode::UnknownPinName => "ERROR_CODE_UNKNOWN_PIN_NAME",
+            ErrorCode::AnswerTooLarge => "ERROR_CODE_ANSWER_TOO_LARGE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ERROR_CODE_NONE" => Some(Self::None),
+            "ERROR_CODE_INVALID_PIN" => Some(Self::InvalidPin),
+            "ERROR_CODE_PIN_RESERVED" => Some(Self::PinReserved),
+            "ERROR_CODE_WRONG_DIRECTION" => Some(Self::WrongDirection),
+            "ERROR_CODE_INVALID_VALUE" => Some(Self::InvalidValue),
+            "ERROR_CODE_UNKNOWN_REQUEST_TYPE" => Some(Self::UnknownRequestType),
+            "ERROR_CODE_DECODE_FAILURE" => Some(Self::DecodeFailure),
+            "ERROR_CODE_PIN_NOT_CONFIGURED" => Some(Self::PinNotConfigured),
+            "ERROR_CODE_NOT_SUPPORTED" => Some(Self::NotSupported),
+            "ERROR_CODE_RESOURCE_BUSY" => Some(Self::ResourceBusy),
+            "ERROR_CODE_CANCELLED" => Some(Self::Cancelled),
+            "ERROR_CODE_TIMEOUT" => Some(Self::Timeout),
+            "ERROR_CODE_STORAGE_FAILURE" => Some(Self::StorageFailure),
+            "ERROR_CODE_UNKNOWN_PIN_NAME" => Some(Self::UnknownPinName),
+            "ERROR_CODE_ANSWER_TOO_LARGE" => Some(Self::AnswerTooLarge),
+            _ => None,
+        }
+    }
+}
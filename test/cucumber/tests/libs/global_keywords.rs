@@ -1,15 +1,76 @@
 use crate::libs::api_dio::AnswerType;
+use crate::libs::api_dio::ErrorCode;
 use crate::libs::api_dio::PicohaDioAnswer;
+use crate::libs::api_dio::PinDriveStrength;
+use crate::libs::api_dio::PinPull;
+use crate::libs::api_dio::PinSlewRate;
 use crate::libs::api_dio::PinValue;
 
+use crate::libs::api_dio::batch_operation::Operation;
+use crate::libs::api_dio::picoha_dio_event::Event;
+use crate::libs::api_dio::picoha_dio_request_v2::Payload;
+use crate::libs::api_dio::BatchOperation;
+use crate::libs::api_dio::BatchRequest;
+use crate::libs::api_dio::CaptureStatus;
+use crate::libs::api_dio::CaptureTrigger;
+use crate::libs::api_dio::ConfigureEdgeCounterRequest;
+use crate::libs::api_dio::ConfigureFailsafeRequest;
+use crate::libs::api_dio::Direction;
+use crate::libs::api_dio::Edge;
+use crate::libs::api_dio::EraseConfigRequest;
+use crate::libs::api_dio::GetCaptureStateRequest;
+use crate::libs::api_dio::GetFailsafeStateRequest;
+use crate::libs::api_dio::GetPatternStateRequest;
+use crate::libs::api_dio::GetPinDebounceRequest;
+use crate::libs::api_dio::GetPinDirectionRequest;
+use crate::libs::api_dio::GetPinNamesRequest;
+use crate::libs::api_dio::GetPinValueRequest;
+use crate::libs::api_dio::GetPortStateRequest;
+use crate::libs::api_dio::GetSavedConfigRequest;
+use crate::libs::api_dio::GetSequenceStateRequest;
+use crate::libs::api_dio::Level;
+use crate::libs::api_dio::LoadPatternRequest;
+use crate::libs::api_dio::LoadSequenceRequest;
+use crate::libs::api_dio::MeasureFrequencyRequest;
 use crate::libs::api_dio::PicohaDioRequest;
+use crate::libs::api_dio::PicohaDioRequestV2;
+use crate::libs::api_dio::PingRequest;
+use crate::libs::api_dio::PulseRequest;
+use crate::libs::api_dio::ReadCaptureRequest;
+use crate::libs::api_dio::ReadEdgeCounterRequest;
 use crate::libs::api_dio::RequestType;
+use crate::libs::api_dio::ResetEdgeCounterRequest;
+use crate::libs::api_dio::RestoreDefaultsRequest;
+use crate::libs::api_dio::SafeState;
+use crate::libs::api_dio::SaveConfigRequest;
+use crate::libs::api_dio::SequenceStep;
+use crate::libs::api_dio::SetPinDebounceRequest;
+use crate::libs::api_dio::SetPinDirectionRequest;
+use crate::libs::api_dio::SetPinNameRequest;
+use crate::libs::api_dio::SetPinPwmDutyRequest;
+use crate::libs::api_dio::SetPinPwmRequest;
+use crate::libs::api_dio::SetPinSafeStateRequest;
+use crate::libs::api_dio::SetPinValueRequest;
+use crate::libs::api_dio::SetPortDirectionRequest;
+use crate::libs::api_dio::SetPortValueRequest;
+use crate::libs::api_dio::StartCaptureRequest;
+use crate::libs::api_dio::StartPatternRequest;
+use crate::libs::api_dio::StartSequenceRequest;
+use crate::libs::api_dio::StopCaptureRequest;
+use crate::libs::api_dio::StopPatternRequest;
+use crate::libs::api_dio::StopSequenceRequest;
+use crate::libs::api_dio::SubscribeEdgesRequest;
+use crate::libs::api_dio::SyncTimeRequest;
+use crate::libs::api_dio::WaitCondition;
+use crate::libs::api_dio::WaitForRequest;
 
+use cucumber::gherkin::Step;
 use cucumber::{given, then, when};
 use prost::Message;
 use tokio_serial::SerialStream;
 
 use crate::libs::world::PiochaWorld;
+use crate::libs::world::MAX_ANSWER_SIZE;
 use rand::Rng;
 
 // Steps are defined with `given`, `when` and `then` attributes.
@@ -42,14 +103,22 @@ async fn open_connection(world: &mut PiochaWorld) {
         Some(SerialStream::open(&serial_builder).expect("Failed to open serial port"));
 }
 
-#[when("I send a ping command to the device")]
-async fn send_ping(world: &mut PiochaWorld) {
-    let mut request = PicohaDioRequest::default();
-    request.set_type(RequestType::Ping);
+#[when("I close the serial connection")]
+async fn close_connection(world: &mut PiochaWorld) {
+    world.close_connection();
 
-    let answer_buffer = &mut [0u8; 1024];
+    // Let the device see the DTR line drop before the port is opened again
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+}
+
+/// Send a protocol v1 request and store the answer in the world
+///
+async fn send_request(world: &mut PiochaWorld, mut request: PicohaDioRequest) {
+    request.request_id = Some(world.new_request_id());
+
+    let answer_buffer = &mut [0u8; MAX_ANSWER_SIZE];
     let size = world
-        .write_then_read(&request.encode_to_vec(), answer_buffer)
+        .write_then_read(&request.encode_to_vec(), request.request_id, answer_buffer)
         .await
         .unwrap();
 
@@ -60,6 +129,14 @@ async fn send_ping(world: &mut PiochaWorld) {
     world.last_answer = Some(answer);
 }
 
+#[when("I send a ping command to the device")]
+async fn send_ping(world: &mut PiochaWorld) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::Ping);
+
+    send_request(world, request).await;
+}
+
 #[then("I must receive a SUCCESS response from the device")]
 async fn receive_success(world: &mut PiochaWorld) {
     let answer = world.last_answer.as_ref().unwrap();
@@ -79,20 +156,11 @@ async fn i_send_a_set_direction_in_pin_command_to_the_device(
     match direction.as_str() {
         "output" => request.set_value(PinValue::Output),
         "input" => request.set_value(PinValue::Input),
+        "open_drain" => request.set_value(PinValue::OpenDrain),
         _ => panic!("Invalid direction value"),
     }
 
-    let answer_buffer = &mut [0u8; 1024];
-    let size = world
-        .write_then_read(&request.encode_to_vec(), answer_buffer)
-        .await
-        .unwrap();
-
-    // Decode the answer
-    let answer_slice = answer_buffer[..size].as_ref();
-    println!("Received {} bytes -> {:?}", size, answer_slice);
-    let answer = PicohaDioAnswer::decode(answer_slice).unwrap();
-    world.last_answer = Some(answer);
+    send_request(world, request).await;
 }
 
 #[when(expr = "I send a set_value {string} in pin {string} command to the device")]
@@ -108,12 +176,251 @@ async fn i_send_a_set_value_in_pin_command_to_the_device(
     match value.as_str() {
         "high" => request.set_value(PinValue::High),
         "low" => request.set_value(PinValue::Low),
+        // v1 accepts any PinValue here, the device must reject directions
+        "input" => request.set_value(PinValue::Input),
         _ => panic!("Invalid direction value"),
     }
 
-    let answer_buffer = &mut [0u8; 1024];
+    send_request(world, request).await;
+}
+
+#[when(expr = "I send a get_value in pin {string} command to the device")]
+async fn i_send_a_get_value_in_pin_command_to_the_device(world: &mut PiochaWorld, pin: String) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::GetPinValue);
+    request.pin_num = pin.parse().unwrap();
+
+    send_request(world, request).await;
+}
+
+#[when(expr = "I send a get_direction in pin {string} command to the device")]
+async fn i_send_a_get_direction_in_pin_command_to_the_device(world: &mut PiochaWorld, pin: String) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::GetPinDirection);
+    request.pin_num = pin.parse().unwrap();
+
+    send_request(world, request).await;
+}
+
+#[then(expr = "the device must report the value {string}")]
+async fn the_device_must_report_the_value(world: &mut PiochaWorld, value: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+
+    let expected = match value.as_str() {
+        "high" => PinValue::High,
+        "low" => PinValue::Low,
+        "input" => PinValue::Input,
+        "output" => PinValue::Output,
+        "open_drain" => PinValue::OpenDrain,
+        _ => panic!("Invalid value"),
+    };
+    assert_eq!(answer.value, Some(expected as i32));
+}
+
+#[when(expr = "I send a set_pull {string} in pin {string} command to the device")]
+async fn i_send_a_set_pull_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    pull: String,
+    pin: String,
+) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::SetPinPull);
+    request.pin_num = pin.parse().unwrap();
+    request.set_pull(parse_pull(&pull));
+
+    send_request(world, request).await;
+}
+
+#[when(expr = "I send a get_pull in pin {string} command to the device")]
+async fn i_send_a_get_pull_in_pin_command_to_the_device(world: &mut PiochaWorld, pin: String) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::GetPinPull);
+    request.pin_num = pin.parse().unwrap();
+
+    send_request(world, request).await;
+}
+
+#[then(expr = "the device must report the pull {string}")]
+async fn the_device_must_report_the_pull(world: &mut PiochaWorld, pull: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert_eq!(answer.pull, Some(parse_pull(&pull) as i32));
+}
+
+/// Convert a pull name used in the features into the API value
+///
+fn parse_pull(pull: &str) -> PinPull {
+    match pull {
+        "none" => PinPull::PullNone,
+        "up" => PinPull::PullUp,
+        "down" => PinPull::PullDown,
+        "bus_keep" => PinPull::PullBusKeep,
+        _ => panic!("Invalid pull value"),
+    }
+}
+
+#[when(expr = "I send a set_drive {string} {string} in pin {string} command to the device")]
+async fn i_send_a_set_drive_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    strength: String,
+    slew_rate: String,
+    pin: String,
+) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::SetPinDrive);
+    request.pin_num = pin.parse().unwrap();
+    request.set_drive_strength(parse_drive_strength(&strength));
+    request.set_slew_rate(parse_slew_rate(&slew_rate));
+
+    send_request(world, request).await;
+}
+
+#[when(expr = "I send a get_drive in pin {string} command to the device")]
+async fn i_send_a_get_drive_in_pin_command_to_the_device(world: &mut PiochaWorld, pin: String) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::GetPinDrive);
+    request.pin_num = pin.parse().unwrap();
+
+    send_request(world, request).await;
+}
+
+#[then(expr = "the device must report the drive {string} {string}")]
+async fn the_device_must_report_the_drive(
+    world: &mut PiochaWorld,
+    strength: String,
+    slew_rate: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert_eq!(
+        answer.drive_strength,
+        Some(parse_drive_strength(&strength) as i32)
+    );
+    assert_eq!(answer.slew_rate, Some(parse_slew_rate(&slew_rate) as i32));
+}
+
+#[then("I must receive a FAILURE response from the device")]
+async fn receive_failure(world: &mut PiochaWorld) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Failure as i32);
+}
+
+#[then(expr = "the device must report the error {string}")]
+async fn the_device_must_report_the_error(world: &mut PiochaWorld, error: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Failure as i32);
+    assert_eq!(answer.error_code, Some(parse_error_code(&error) as i32));
+}
+
+#[then(expr = "the answer must echo the request {string} on pin {string}")]
+async fn the_answer_must_echo_the_request_on_pin(
+    world: &mut PiochaWorld,
+    r#type: String,
+    pin: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.request_id, Some(world.last_request_id));
+    assert_eq!(
+        answer.request_type,
+        Some(parse_request_type(&r#type) as i32)
+    );
+    assert_eq!(answer.pin_num, Some(pin.parse().unwrap()));
+}
+
+#[then(expr = "the answer must echo the request {string}")]
+async fn the_answer_must_echo_the_request(world: &mut PiochaWorld, r#type: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.request_id, Some(world.last_request_id));
+    assert_eq!(
+        answer.request_type,
+        Some(parse_request_type(&r#type) as i32)
+    );
+    assert_eq!(answer.pin_num, None);
+}
+
+/// Convert a request type name used in the features into the API value
+///
+fn parse_request_type(r#type: &str) -> RequestType {
+    match r#type {
+        "ping" => RequestType::Ping,
+        "set_direction" => RequestType::SetPinDirection,
+        "set_value" => RequestType::SetPinValue,
+        "get_direction" => RequestType::GetPinDirection,
+        "get_value" => RequestType::GetPinValue,
+        "set_pull" => RequestType::SetPinPull,
+        "get_pull" => RequestType::GetPinPull,
+        "set_drive" => RequestType::SetPinDrive,
+        "get_drive" => RequestType::GetPinDrive,
+        "wait_for" => RequestType::WaitFor,
+        "sync_time" => RequestType::SyncTime,
+        "save_config" => RequestType::SaveConfig,
+        "set_pin_name" => RequestType::SetPinName,
+        _ => panic!("Invalid request type value"),
+    }
+}
+
+/// Convert an error name used in the features into the API value
+///
+fn parse_error_code(error: &str) -> ErrorCode {
+    match error {
+        "invalid_pin" => ErrorCode::InvalidPin,
+        "pin_reserved" => ErrorCode::PinReserved,
+        "wrong_direction" => ErrorCode::WrongDirection,
+        "invalid_value" => ErrorCode::InvalidValue,
+        "unknown_request_type" => ErrorCode::UnknownRequestType,
+        "decode_failure" => ErrorCode::DecodeFailure,
+        "pin_not_configured" => ErrorCode::PinNotConfigured,
+        "not_supported" => ErrorCode::NotSupported,
+        "resource_busy" => ErrorCode::ResourceBusy,
+        "cancelled" => ErrorCode::Cancelled,
+        "timeout" => ErrorCode::Timeout,
+        "unknown_pin_name" => ErrorCode::UnknownPinName,
+        "answer_too_large" => ErrorCode::AnswerTooLarge,
+        _ => panic!("Invalid error code value"),
+    }
+}
+
+/// Convert a drive strength name used in the features into the API value
+///
+fn parse_drive_strength(strength: &str) -> PinDriveStrength {
+    match strength {
+        "2mA" => PinDriveStrength::Drive2ma,
+        "4mA" => PinDriveStrength::Drive4ma,
+        "8mA" => PinDriveStrength::Drive8ma,
+        "12mA" => PinDriveStrength::Drive12ma,
+        _ => panic!("Invalid drive strength value"),
+    }
+}
+
+/// Convert a slew rate name used in the features into the API value
+///
+fn parse_slew_rate(slew_rate: &str) -> PinSlewRate {
+    match slew_rate {
+        "slow" => PinSlewRate::SlewSlow,
+        "fast" => PinSlewRate::SlewFast,
+        _ => panic!("Invalid slew rate value"),
+    }
+}
+
+/// Send a protocol v2 request and store the answer in the world
+///
+async fn send_request_v2(world: &mut PiochaWorld, payload: Payload) {
+    send_request_v2_by_name(world, String::new(), payload).await;
+}
+
+/// Send a protocol v2 request targeting a pin by its name, and store the answer in the world
+///
+async fn send_request_v2_by_name(world: &mut PiochaWorld, pin_name: String, payload: Payload) {
+    let request = PicohaDioRequestV2 {
+        request_id: Some(world.new_request_id()),
+        payload: Some(payload),
+        pin_name,
+    };
+
+    let answer_buffer = &mut [0u8; MAX_ANSWER_SIZE];
     let size = world
-        .write_then_read(&request.encode_to_vec(), answer_buffer)
+        .write_then_read(&request.encode_to_vec(), request.request_id, answer_buffer)
         .await
         .unwrap();
 
@@ -124,20 +431,1174 @@ async fn i_send_a_set_value_in_pin_command_to_the_device(
     world.last_answer = Some(answer);
 }
 
+#[when("I send a v2 ping command to the device")]
+async fn send_ping_v2(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::Ping(PingRequest {})).await;
+}
+
+#[when(expr = "I send a v2 set_direction {string} in pin {string} command to the device")]
+async fn i_send_a_v2_set_direction_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    direction: String,
+    pin: String,
+) {
+    let mut request = SetPinDirectionRequest::default();
+    request.pin_num = pin.parse().unwrap();
+    match direction.as_str() {
+        "output" => request.set_direction(Direction::Output),
+        "input" => request.set_direction(Direction::Input),
+        "open_drain" => request.set_direction(Direction::OpenDrain),
+        _ => panic!("Invalid direction value"),
+    }
+    send_request_v2(world, Payload::SetPinDirection(request)).await;
+}
+
+#[when(expr = "I send a v2 set_value {string} in pin {string} command to the device")]
+async fn i_send_a_v2_set_value_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    level: String,
+    pin: String,
+) {
+    let mut request = SetPinValueRequest::default();
+    request.pin_num = pin.parse().unwrap();
+    request.set_level(parse_level(&level));
+    send_request_v2(world, Payload::SetPinValue(request)).await;
+}
+
+#[when(expr = "I send a v2 set_direction {string} in pin named {string} command to the device")]
+async fn i_send_a_v2_set_direction_in_pin_named_command_to_the_device(
+    world: &mut PiochaWorld,
+    direction: String,
+    name: String,
+) {
+    let mut request = SetPinDirectionRequest::default();
+    request.set_direction(parse_direction(&direction));
+    send_request_v2_by_name(world, name, Payload::SetPinDirection(request)).await;
+}
+
+#[when(expr = "I send a v2 set_value {string} in pin named {string} command to the device")]
+async fn i_send_a_v2_set_value_in_pin_named_command_to_the_device(
+    world: &mut PiochaWorld,
+    level: String,
+    name: String,
+) {
+    let mut request = SetPinValueRequest::default();
+    request.set_level(parse_level(&level));
+    send_request_v2_by_name(world, name, Payload::SetPinValue(request)).await;
+}
+
+#[when(expr = "I send a v2 get_value in pin named {string} command to the device")]
+async fn i_send_a_v2_get_value_in_pin_named_command_to_the_device(
+    world: &mut PiochaWorld,
+    name: String,
+) {
+    let request = GetPinValueRequest::default();
+    send_request_v2_by_name(world, name, Payload::GetPinValue(request)).await;
+}
+
+#[when(expr = "I send a v2 get_value in pin {string} command to the device")]
+async fn i_send_a_v2_get_value_in_pin_command_to_the_device(world: &mut PiochaWorld, pin: String) {
+    let request = GetPinValueRequest {
+        pin_num: pin.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::GetPinValue(request)).await;
+}
+
+#[then(expr = "the device must report the level {string}")]
+async fn the_device_must_report_the_level(world: &mut PiochaWorld, level: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert_eq!(answer.level, Some(parse_level(&level) as i32));
+}
+
+/// Parse a port mask written in hexadecimal in the features
+///
+fn parse_mask(mask: &str) -> u32 {
+    u32::from_str_radix(mask.trim_start_matches("0x"), 16).unwrap()
+}
+
+#[when(expr = "I send a set_port_direction mask {string} value {string} command to the device")]
+async fn i_send_a_set_port_direction_command_to_the_device(
+    world: &mut PiochaWorld,
+    mask: String,
+    value: String,
+) {
+    let request = SetPortDirectionRequest {
+        mask: parse_mask(&mask),
+        value: parse_mask(&value),
+    };
+    send_request_v2(world, Payload::SetPortDirection(request)).await;
+}
+
+#[when(expr = "I send a set_port_value mask {string} value {string} command to the device")]
+async fn i_send_a_set_port_value_command_to_the_device(
+    world: &mut PiochaWorld,
+    mask: String,
+    value: String,
+) {
+    let request = SetPortValueRequest {
+        mask: parse_mask(&mask),
+        value: parse_mask(&value),
+    };
+    send_request_v2(world, Payload::SetPortValue(request)).await;
+}
+
+#[when("I send a get_port_state command to the device")]
+async fn i_send_a_get_port_state_command_to_the_device(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::GetPortState(GetPortStateRequest {})).await;
+}
+
+#[then(expr = "the port {string} bits {string} must be {string}")]
+async fn the_port_bits_must_be(world: &mut PiochaWorld, field: String, mask: String, bits: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let state = answer.port_state.as_ref().unwrap();
+    let value = match field.as_str() {
+        "available" => state.available,
+        "input" => state.input,
+        "output" => state.output,
+        "open_drain" => state.open_drain,
+        "input_level" => state.input_level,
+        "output_latch" => state.output_latch,
+        "pwm" => state.pwm,
+        "pattern" => state.pattern,
+        _ => panic!("Invalid port state field"),
+    };
+    assert_eq!(value & parse_mask(&mask), parse_mask(&bits));
+}
+
+/// Convert an edge name used in the features into the API value
+///
+fn parse_edge(edge: &str) -> Edge {
+    match edge {
+        "none" => Edge::None,
+        "rising" => Edge::Rising,
+        "falling" => Edge::Falling,
+        "both" => Edge::Both,
+        _ => panic!("Invalid edge value"),
+    }
+}
+
+#[when(expr = "I subscribe to the {string} edges of pin {string}")]
+async fn i_subscribe_to_the_edges_of_pin(world: &mut PiochaWorld, edge: String, pin: String) {
+    let mut request = SubscribeEdgesRequest::default();
+    request.pin_num = pin.parse().unwrap();
+    request.set_edge(parse_edge(&edge));
+    send_request_v2(world, Payload::SubscribeEdges(request)).await;
+}
+
+#[when(expr = "I send a set_debounce {string} in pin {string} command to the device")]
+async fn i_send_a_set_debounce_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    debounce_us: String,
+    pin: String,
+) {
+    let request = SetPinDebounceRequest {
+        pin_num: pin.parse().unwrap(),
+        debounce_us: debounce_us.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::SetPinDebounce(request)).await;
+}
+
+#[when(expr = "I send a get_debounce in pin {string} command to the device")]
+async fn i_send_a_get_debounce_in_pin_command_to_the_device(world: &mut PiochaWorld, pin: String) {
+    let request = GetPinDebounceRequest {
+        pin_num: pin.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::GetPinDebounce(request)).await;
+}
+
+#[then(expr = "the device must report the debounce {string}")]
+async fn the_device_must_report_the_debounce(world: &mut PiochaWorld, debounce_us: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert_eq!(answer.debounce_us, Some(debounce_us.parse().unwrap()));
+}
+
+#[when(expr = "I count the {string} edges of pin {string}")]
+async fn i_count_the_edges_of_pin(world: &mut PiochaWorld, edge: String, pin: String) {
+    let mut request = ConfigureEdgeCounterRequest::default();
+    request.pin_num = pin.parse().unwrap();
+    request.set_edge(parse_edge(&edge));
+    send_request_v2(world, Payload::ConfigureEdgeCounter(request)).await;
+}
+
+#[when(expr = "I read the edge counter of pin {string}")]
+async fn i_read_the_edge_counter_of_pin(world: &mut PiochaWorld, pin: String) {
+    let request = ReadEdgeCounterRequest {
+        pin_num: pin.parse().unwrap(),
+        clear: false,
+    };
+    send_request_v2(world, Payload::ReadEdgeCounter(request)).await;
+}
+
+#[when(expr = "I read and clear the edge counter of pin {string}")]
+async fn i_read_and_clear_the_edge_counter_of_pin(world: &mut PiochaWorld, pin: String) {
+    let request = ReadEdgeCounterRequest {
+        pin_num: pin.parse().unwrap(),
+        clear: true,
+    };
+    send_request_v2(world, Payload::ReadEdgeCounter(request)).await;
+}
+
+#[when(expr = "I reset the edge counter of pin {string}")]
+async fn i_reset_the_edge_counter_of_pin(world: &mut PiochaWorld, pin: String) {
+    let request = ResetEdgeCounterRequest {
+        pin_num: pin.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::ResetEdgeCounter(request)).await;
+}
+
+#[then(expr = "the device must report the edge count {int}")]
+async fn the_device_must_report_the_edge_count(world: &mut PiochaWorld, count: u32) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert_eq!(answer.edge_count, Some(count));
+}
+
+#[when(expr = "I measure the frequency of pin {string} during {string} us")]
+async fn i_measure_the_frequency_of_pin_during_us(
+    world: &mut PiochaWorld,
+    pin: String,
+    gate_time_us: String,
+) {
+    let request = MeasureFrequencyRequest {
+        pin_num: pin.parse().unwrap(),
+        gate_time_us: gate_time_us.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::MeasureFrequency(request)).await;
+}
+
+#[then(expr = "the measure must count {int} edges with a duty cycle of {string}")]
+async fn the_measure_must_count_edges_with_a_duty_cycle_of(
+    world: &mut PiochaWorld,
+    edge_count: u32,
+    duty_cycle: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let frequency = answer.frequency.as_ref().unwrap();
+    assert_eq!(frequency.edge_count, edge_count);
+    let expected: f64 = duty_cycle.parse().unwrap();
+    assert!(
+        (frequency.duty_cycle - expected).abs() < 0.01,
+        "duty cycle {} instead of {}",
+        frequency.duty_cycle,
+        expected
+    );
+}
+
+#[when(expr = "I send a set_pwm {string} Hz duty {string} in pin {string} command to the device")]
+async fn i_send_a_set_pwm_hz_duty_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    frequency_hz: String,
+    duty_cycle: String,
+    pin: String,
+) {
+    let request = SetPinPwmRequest {
+        pin_num: pin.parse().unwrap(),
+        frequency_hz: frequency_hz.parse().unwrap(),
+        duty_cycle: duty_cycle.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::SetPinPwm(request)).await;
+}
+
+#[when(expr = "I send a set_pwm_duty {string} in pin {string} command to the device")]
+async fn i_send_a_set_pwm_duty_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    duty_cycle: String,
+    pin: String,
+) {
+    let request = SetPinPwmDutyRequest {
+        pin_num: pin.parse().unwrap(),
+        duty_cycle: duty_cycle.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::SetPinPwmDuty(request)).await;
+}
+
+#[then(expr = "the device must report a pwm of {string} Hz with a duty cycle of {string}")]
+async fn the_device_must_report_a_pwm_of_hz_with_a_duty_cycle_of(
+    world: &mut PiochaWorld,
+    frequency_hz: String,
+    duty_cycle: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let pwm = answer.pwm.as_ref().unwrap();
+    let frequency_hz: f64 = frequency_hz.parse().unwrap();
+    let duty_cycle: f64 = duty_cycle.parse().unwrap();
+    assert!(
+        (pwm.frequency_hz - frequency_hz).abs() < frequency_hz * 0.01,
+        "{:?}",
+        pwm
+    );
+    assert!((pwm.duty_cycle - duty_cycle).abs() < 0.01, "{:?}", pwm);
+}
+
+#[then(expr = "the device must report the direction {string}")]
+async fn the_device_must_report_the_direction(world: &mut PiochaWorld, direction: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert_eq!(answer.direction, Some(parse_direction(&direction) as i32));
+}
+
+/// Build a load sequence request from the table of a step
+///
+/// Each row is `| offset_us | mask | levels |`, the masks and levels in hexadecimal
+///
+fn sequence_from_table(step: &Step, append: bool) -> LoadSequenceRequest {
+    let table = step.table.as_ref().expect("Sequence steps table missing");
+    let steps = table
+        .rows
+        .iter()
+        .skip(1)
+        .map(|row| SequenceStep {
+            offset_us: row[0].parse().unwrap(),
+            mask: parse_mask(&row[1]),
+            levels: parse_mask(&row[2]),
+        })
+        .collect();
+    LoadSequenceRequest { steps, append }
+}
+
+#[when("I load the sequence:")]
+async fn i_load_the_sequence(world: &mut PiochaWorld, step: &Step) {
+    let request = sequence_from_table(step, false);
+    send_request_v2(world, Payload::LoadSequence(request)).await;
+}
+
+#[when("I append to the sequence:")]
+async fn i_append_to_the_sequence(world: &mut PiochaWorld, step: &Step) {
+    let request = sequence_from_table(step, true);
+    send_request_v2(world, Payload::LoadSequence(request)).await;
+}
+
+#[when("I start the sequence")]
+async fn i_start_the_sequence(world: &mut PiochaWorld) {
+    let request = StartSequenceRequest { loop_period_us: 0 };
+    send_request_v2(world, Payload::StartSequence(request)).await;
+}
+
+#[when(expr = "I start the sequence looping every {string} us")]
+async fn i_start_the_sequence_looping_every_us(world: &mut PiochaWorld, period_us: String) {
+    let request = StartSequenceRequest {
+        loop_period_us: period_us.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::StartSequence(request)).await;
+}
+
+#[when("I stop the sequence")]
+async fn i_stop_the_sequence(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::StopSequence(StopSequenceRequest {})).await;
+}
+
+#[when("I get the sequence state")]
+async fn i_get_the_sequence_state(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::GetSequenceState(GetSequenceStateRequest {})).await;
+}
+
+#[then(expr = "the sequence must hold {int} steps and be {string}")]
+async fn the_sequence_must_hold_steps_and_be(
+    world: &mut PiochaWorld,
+    step_count: u32,
+    state: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let sequence = answer.sequence.as_ref().unwrap();
+    assert_eq!(sequence.step_count, step_count);
+    assert_eq!(sequence.running, state == "running");
+}
+
+#[then(expr = "the sequence must have completed at least {int} loops")]
+async fn the_sequence_must_have_completed_at_least_loops(world: &mut PiochaWorld, loops: u32) {
+    let answer = world.last_answer.as_ref().unwrap();
+    let sequence = answer.sequence.as_ref().unwrap();
+    assert!(sequence.completed_loops >= loops, "{:?}", sequence);
+}
+
+/// Convert a capture trigger string to a CaptureTrigger
+///
+fn parse_capture_trigger(trigger: &str) -> CaptureTrigger {
+    match trigger {
+        "now" => CaptureTrigger::Now,
+        "level" => CaptureTrigger::Level,
+        "edge" => CaptureTrigger::Edge,
+        _ => panic!("Invalid capture trigger value"),
+    }
+}
+
+/// Convert a capture status string to a CaptureStatus
+///
+fn parse_capture_status(status: &str) -> CaptureStatus {
+    match status {
+        "idle" => CaptureStatus::Idle,
+        "waiting_trigger" => CaptureStatus::WaitingTrigger,
+        "triggered" => CaptureStatus::Triggered,
+        "done" => CaptureStatus::Done,
+        "aborted" => CaptureStatus::Aborted,
+        _ => panic!("Invalid capture status value"),
+    }
+}
+
+/// Build a start capture request from the table of a step
+///
+/// Each row is `| field | value |`, the fields not given keep their default value
+///
+fn capture_from_table(step: &Step) -> StartCaptureRequest {
+    let table = step.table.as_ref().expect("Capture settings table missing");
+    let mut request = StartCaptureRequest::default();
+    for row in table.rows.iter() {
+        let value = row[1].as_str();
+        match row[0].as_str() {
+            "first_pin" => request.first_pin = value.parse().unwrap(),
+            "pin_count" => request.pin_count = value.parse().unwrap(),
+            "sample_rate_hz" => request.sample_rate_hz = value.parse().unwrap(),
+            "pre_trigger_samples" => request.pre_trigger_samples = value.parse().unwrap(),
+            "post_trigger_samples" => request.post_trigger_samples = value.parse().unwrap(),
+            "trigger" => request.set_trigger(parse_capture_trigger(value)),
+            "trigger_mask" => request.trigger_mask = parse_mask(value),
+            "trigger_value" => request.trigger_value = parse_mask(value),
+            _ => panic!("Invalid capture setting"),
+        }
+    }
+    request
+}
+
+#[when("I start a capture with:")]
+async fn i_start_a_capture_with(world: &mut PiochaWorld, step: &Step) {
+    let request = capture_from_table(step);
+    send_request_v2(world, Payload::StartCapture(request)).await;
+}
+
+#[when("I stop the capture")]
+async fn i_stop_the_capture(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::StopCapture(StopCaptureRequest {})).await;
+}
+
+#[when("I get the capture state")]
+async fn i_get_the_capture_state(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::GetCaptureState(GetCaptureStateRequest {})).await;
+}
+
+#[then(expr = "the capture must be {string}")]
+async fn the_capture_must_be(world: &mut PiochaWorld, status: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let capture = answer.capture.as_ref().unwrap();
+    assert_eq!(capture.status, parse_capture_status(&status) as i32);
+}
+
+#[when(expr = "I read the capture from offset {int}")]
+async fn i_read_the_capture_from_offset(world: &mut PiochaWorld, offset: u32) {
+    send_request_v2(world, Payload::ReadCapture(ReadCaptureRequest { offset })).await;
+}
+
+#[when("I download the capture")]
+async fn i_download_the_capture(world: &mut PiochaWorld) {
+    world.capture_data.clear();
+    loop {
+        let offset = world.capture_data.len() as u32;
+        send_request_v2(world, Payload::ReadCapture(ReadCaptureRequest { offset })).await;
+        let answer = world.last_answer.as_ref().unwrap();
+        assert_eq!(answer.r#type, AnswerType::Success as i32);
+        let chunk = answer.capture_chunk.as_ref().unwrap();
+        assert_eq!(chunk.offset, offset);
+        let data_size = chunk.data_size as usize;
+        world.capture_data.extend_from_slice(&chunk.data);
+        if world.capture_data.len() >= data_size {
+            break;
+        }
+    }
+    // Keep the state of the capture to decode the samples
+    i_get_the_capture_state(world).await;
+}
+
+#[then(expr = "the capture must hold {int} samples with the trigger at sample {int}")]
+async fn the_capture_must_hold_samples_with_the_trigger_at_sample(
+    world: &mut PiochaWorld,
+    sample_count: u32,
+    trigger_sample: u32,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    let capture = answer.capture.as_ref().unwrap();
+    assert_eq!(capture.sample_count, sample_count);
+    assert_eq!(capture.trigger_sample, trigger_sample);
+    assert_eq!(world.capture_data.len(), capture.data_size as usize);
+}
+
+#[then(expr = "the captured pin {string} must go from {string} to {string} at the trigger")]
+async fn the_captured_pin_must_go_from_to_at_the_trigger(
+    world: &mut PiochaWorld,
+    pin: String,
+    before: String,
+    after: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    let capture = answer.capture.as_ref().unwrap();
+    let bit = pin.parse::<u32>().unwrap() - capture.first_pin;
+
+    // Sample n of a little-endian word is at bit n * sample_width
+    let level = |sample: u32| {
+        let index = (capture.first_sample + sample) * capture.sample_width + bit;
+        let byte = world.capture_data[(index / 8) as usize];
+        match (byte >> (index % 8)) & 1 {
+            0 => Level::Low,
+            _ => Level::High,
+        }
+    };
+    assert_eq!(level(capture.trigger_sample - 1), parse_level(&before));
+    assert_eq!(level(capture.trigger_sample), parse_level(&after));
+}
+
+/// Build a load pattern request on the pins from first to last
+///
+/// The words are comma-separated 32-bit values in hexadecimal, sent in little-endian
+///
+fn pattern_request(words: &str, first: &str, last: &str, append: bool) -> LoadPatternRequest {
+    let first_pin: u32 = first.parse().unwrap();
+    let last_pin: u32 = last.parse().unwrap();
+    let data = words
+        .split(',')
+        .flat_map(|word| parse_mask(word.trim()).to_le_bytes())
+        .collect();
+    LoadPatternRequest {
+        first_pin,
+        pin_count: last_pin - first_pin + 1,
+        data,
+        append,
+    }
+}
+
+#[when(expr = "I load the pattern {string} on pins {string} to {string}")]
+async fn i_load_the_pattern_on_pins_to(
+    world: &mut PiochaWorld,
+    words: String,
+    first: String,
+    last: String,
+) {
+    let request = pattern_request(&words, &first, &last, false);
+    send_request_v2(world, Payload::LoadPattern(request)).await;
+}
+
+#[when(expr = "I append the pattern {string} on pins {string} to {string}")]
+async fn i_append_the_pattern_on_pins_to(
+    world: &mut PiochaWorld,
+    words: String,
+    first: String,
+    last: String,
+) {
+    let request = pattern_request(&words, &first, &last, true);
+    send_request_v2(world, Payload::LoadPattern(request)).await;
+}
+
+#[when(expr = "I start the pattern at {string} Hz")]
+async fn i_start_the_pattern_at_hz(world: &mut PiochaWorld, rate: String) {
+    let request = StartPatternRequest {
+        sample_rate_hz: rate.parse().unwrap(),
+        looping: false,
+    };
+    send_request_v2(world, Payload::StartPattern(request)).await;
+}
+
+#[when(expr = "I start the pattern looping at {string} Hz")]
+async fn i_start_the_pattern_looping_at_hz(world: &mut PiochaWorld, rate: String) {
+    let request = StartPatternRequest {
+        sample_rate_hz: rate.parse().unwrap(),
+        looping: true,
+    };
+    send_request_v2(world, Payload::StartPattern(request)).await;
+}
+
+#[when("I stop the pattern")]
+async fn i_stop_the_pattern(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::StopPattern(StopPatternRequest {})).await;
+}
+
+#[when("I get the pattern state")]
+async fn i_get_the_pattern_state(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::GetPatternState(GetPatternStateRequest {})).await;
+}
+
+#[then(expr = "the pattern must hold {int} samples and be {string}")]
+async fn the_pattern_must_hold_samples_and_be(
+    world: &mut PiochaWorld,
+    sample_count: u32,
+    state: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let pattern = answer.pattern.as_ref().unwrap();
+    assert_eq!(pattern.sample_count, sample_count);
+    assert_eq!(pattern.running, state == "running");
+}
+
+/// Send a wait request without reading its answer, read later by i_receive_the_wait_answer
+///
+async fn send_wait(world: &mut PiochaWorld, mask: &str, value: &str, edge: bool, timeout_us: &str) {
+    let condition = match edge {
+        true => WaitCondition::Edge,
+        false => WaitCondition::Level,
+    };
+    let request = PicohaDioRequestV2 {
+        request_id: Some(world.new_request_id()),
+        payload: Some(Payload::WaitFor(WaitForRequest {
+            mask: parse_mask(mask),
+            value: parse_mask(value),
+            condition: condition as i32,
+            timeout_us: timeout_us.parse().unwrap(),
+        })),
+        pin_name: String::new(),
+    };
+    world.write_request(&request.encode_to_vec()).await.unwrap();
+    world.wait_request_id = request.request_id;
+}
+
+#[when(expr = "I start waiting for the pins {string} at level {string} within {string} us")]
+async fn i_start_waiting_for_the_pins_at_level_within_us(
+    world: &mut PiochaWorld,
+    mask: String,
+    value: String,
+    timeout_us: String,
+) {
+    send_wait(world, &mask, &value, false, &timeout_us).await;
+}
+
+#[when(expr = "I start waiting for the pins {string} to change to {string} within {string} us")]
+async fn i_start_waiting_for_the_pins_to_change_to_within_us(
+    world: &mut PiochaWorld,
+    mask: String,
+    value: String,
+    timeout_us: String,
+) {
+    send_wait(world, &mask, &value, true, &timeout_us).await;
+}
+
+#[when("I receive the wait answer")]
+async fn i_receive_the_wait_answer(world: &mut PiochaWorld) {
+    let request_id = world.wait_request_id.take().expect("No wait request sent");
+    let answer = world.read_answer(request_id).await.unwrap();
+    world.last_answer = Some(answer);
+}
+
+#[then(expr = "the wait must have lasted between {string} and {string} us")]
+async fn the_wait_must_have_lasted_between_and_us(
+    world: &mut PiochaWorld,
+    min: String,
+    max: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    let wait = answer.wait.as_ref().unwrap();
+    let elapsed_us = wait.elapsed_us;
+    assert!(elapsed_us >= min.parse().unwrap(), "{:?}", wait);
+    assert!(elapsed_us <= max.parse().unwrap(), "{:?}", wait);
+}
+
+#[when("I remember the answer timestamp")]
+async fn i_remember_the_answer_timestamp(world: &mut PiochaWorld) {
+    let answer = world.last_answer.as_ref().unwrap();
+    world.remembered_timestamp_us = Some(answer.timestamp_us.unwrap());
+}
+
+#[then(expr = "the answer timestamp must be at least {string} us after the remembered one")]
+async fn the_answer_timestamp_must_be_at_least_us_after_the_remembered_one(
+    world: &mut PiochaWorld,
+    delay_us: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    let remembered = world.remembered_timestamp_us.unwrap();
+    let timestamp = answer.timestamp_us.unwrap();
+    assert!(
+        timestamp >= remembered + delay_us.parse::<u64>().unwrap(),
+        "{:?}",
+        answer
+    );
+}
+
+/// Host time in microseconds since the UNIX epoch
+///
+fn host_time_us() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_micros() as u64
+}
+
+#[when("I synchronize the time with the device")]
+async fn i_synchronize_the_time_with_the_device(world: &mut PiochaWorld) {
+    let sent_at = host_time_us();
+    let request = SyncTimeRequest {
+        host_time_us: sent_at,
+    };
+    send_request_v2(world, Payload::SyncTime(request)).await;
+    world.sync_host_times_us = Some((sent_at, host_time_us()));
+}
+
+#[then(expr = "the time sync must report a round trip shorter than {string} us")]
+async fn the_time_sync_must_report_a_round_trip_shorter_than_us(
+    world: &mut PiochaWorld,
+    max_us: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let time_sync = answer.time_sync.as_ref().unwrap();
+    let (sent_at, received_at) = world.sync_host_times_us.unwrap();
+    assert_eq!(time_sync.host_time_us, sent_at);
+
+    // The time spent on the device is not part of the round trip
+    let device_time = answer.timestamp_us.unwrap() - time_sync.received_us;
+    let round_trip = (received_at - sent_at) as i64 - device_time as i64;
+    assert!(round_trip >= 0, "{:?}", answer);
+    assert!(round_trip < max_us.parse::<i64>().unwrap(), "{:?}", answer);
+}
+
+/// Convert a safe state name used in the features into the API value
+///
+fn parse_safe_state(state: &str) -> SafeState {
+    match state {
+        "none" => SafeState::None,
+        "input" => SafeState::Input,
+        "low" => SafeState::Low,
+        "high" => SafeState::High,
+        _ => panic!("Invalid safe state value"),
+    }
+}
+
+#[when(expr = "I set the safe state of pin {string} to {string}")]
+async fn i_set_the_safe_state_of_pin_to(world: &mut PiochaWorld, pin: String, state: String) {
+    let mut request = SetPinSafeStateRequest::default();
+    request.pin_num = pin.parse().unwrap();
+    request.set_state(parse_safe_state(&state));
+    send_request_v2(world, Payload::SetPinSafeState(request)).await;
+}
+
+#[when(expr = "I enable the failsafe with a heartbeat timeout of {string} ms")]
+async fn i_enable_the_failsafe_with_a_heartbeat_timeout_of_ms(
+    world: &mut PiochaWorld,
+    timeout_ms: String,
+) {
+    let request = ConfigureFailsafeRequest {
+        heartbeat_timeout_ms: timeout_ms.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::ConfigureFailsafe(request)).await;
+}
+
+#[when("I disable the failsafe")]
+async fn i_disable_the_failsafe(world: &mut PiochaWorld) {
+    let request = ConfigureFailsafeRequest {
+        heartbeat_timeout_ms: 0,
+    };
+    send_request_v2(world, Payload::ConfigureFailsafe(request)).await;
+}
+
+#[when("I get the failsafe state")]
+async fn i_get_the_failsafe_state(world: &mut PiochaWorld) {
+    let request = GetFailsafeStateRequest { clear: false };
+    send_request_v2(world, Payload::GetFailsafeState(request)).await;
+}
+
+#[when("I get and clear the failsafe state")]
+async fn i_get_and_clear_the_failsafe_state(world: &mut PiochaWorld) {
+    let request = GetFailsafeStateRequest { clear: true };
+    send_request_v2(world, Payload::GetFailsafeState(request)).await;
+}
+
+#[then(expr = "the failsafe must be {string}")]
+async fn the_failsafe_must_be(world: &mut PiochaWorld, state: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let failsafe = answer.failsafe.as_ref().unwrap();
+    match state.as_str() {
+        "disabled" => assert!(failsafe.heartbeat_timeout_ms == 0 && !failsafe.tripped),
+        "armed" => assert!(failsafe.heartbeat_timeout_ms != 0 && !failsafe.tripped),
+        "tripped" => assert!(failsafe.tripped),
+        "closed" => assert!(failsafe.session_closed && !failsafe.tripped),
+        _ => panic!("Invalid failsafe state value"),
+    }
+}
+
+#[when("I save the pin configuration")]
+async fn i_save_the_pin_configuration(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::SaveConfig(SaveConfigRequest {})).await;
+}
+
+#[when(expr = "I get the saved configuration of pin {string}")]
+async fn i_get_the_saved_configuration_of_pin(world: &mut PiochaWorld, pin: String) {
+    let request = GetSavedConfigRequest {
+        pin_num: pin.parse().unwrap(),
+    };
+    send_request_v2(world, Payload::GetSavedConfig(request)).await;
+}
+
+#[when("I erase the saved configuration")]
+async fn i_erase_the_saved_configuration(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::EraseConfig(EraseConfigRequest {})).await;
+}
+
+#[when("I restore the factory defaults")]
+async fn i_restore_the_factory_defaults(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::RestoreDefaults(RestoreDefaultsRequest {})).await;
+}
+
+#[then(expr = "the saved configuration must be {string} at level {string}")]
+async fn the_saved_configuration_must_be_at_level(
+    world: &mut PiochaWorld,
+    direction: String,
+    level: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let config = answer.saved_config.as_ref().unwrap();
+    assert!(config.saved);
+    assert_eq!(config.direction, parse_direction(&direction) as i32);
+    assert_eq!(config.level, parse_level(&level) as i32);
+}
+
+#[then("no configuration must be saved")]
+async fn no_configuration_must_be_saved(world: &mut PiochaWorld) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert!(!answer.saved_config.as_ref().unwrap().saved);
+}
+
+#[when(expr = "I name pin {string} {string}")]
+async fn i_name_pin(world: &mut PiochaWorld, pin: String, name: String) {
+    let request = SetPinNameRequest {
+        pin_num: pin.parse().unwrap(),
+        name,
+    };
+    send_request_v2(world, Payload::SetPinName(request)).await;
+}
+
+#[when("I get the pin names")]
+async fn i_get_the_pin_names(world: &mut PiochaWorld) {
+    send_request_v2(world, Payload::GetPinNames(GetPinNamesRequest {})).await;
+}
+
+#[then(expr = "pin {string} must be named {string}")]
+async fn pin_must_be_named(world: &mut PiochaWorld, pin: String, name: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    let pin_num: u32 = pin.parse().unwrap();
+    let pin_name = answer.pin_names.iter().find(|n| n.pin_num == pin_num);
+    assert_eq!(pin_name.map(|n| n.name.as_str()), Some(name.as_str()));
+}
+
+#[then(expr = "no pin must be named {string}")]
+async fn no_pin_must_be_named(world: &mut PiochaWorld, name: String) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.r#type, AnswerType::Success as i32);
+    assert!(answer.pin_names.iter().all(|n| n.name != name));
+}
+
+#[when(expr = "I wait {int} ms")]
+async fn i_wait_ms(_world: &mut PiochaWorld, duration_ms: u64) {
+    tokio::time::sleep(std::time::Duration::from_millis(duration_ms)).await;
+}
+
+/// Send a pulse request and read its answer
+///
+async fn send_pulse(
+    world: &mut PiochaWorld,
+    pin: String,
+    level: String,
+    duration_us: String,
+    repeat_count: u32,
+    period_us: String,
+    wait_completion: bool,
+) {
+    let request = PulseRequest {
+        pin_num: pin.parse().unwrap(),
+        active_level: parse_level(&level) as i32,
+        duration_us: duration_us.parse().unwrap(),
+        repeat_count,
+        period_us: period_us.parse().unwrap(),
+        wait_completion,
+    };
+    send_request_v2(world, Payload::Pulse(request)).await;
+}
+
+#[when(expr = "I send a pulse {string} of {string} us in pin {string} command to the device")]
+async fn i_send_a_pulse_of_us_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    level: String,
+    duration_us: String,
+    pin: String,
+) {
+    send_pulse(world, pin, level, duration_us, 0, "0".to_string(), false).await;
+}
+
+#[when(
+    expr = "I send a pulse {string} of {string} us repeated {int} times every {string} us in pin {string} command to the device"
+)]
+async fn i_send_a_repeated_pulse_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    level: String,
+    duration_us: String,
+    repeat_count: u32,
+    period_us: String,
+    pin: String,
+) {
+    send_pulse(
+        world,
+        pin,
+        level,
+        duration_us,
+        repeat_count,
+        period_us,
+        false,
+    )
+    .await;
+}
+
+#[when(
+    expr = "I send a pulse {string} of {string} us repeated {int} times every {string} us in pin {string} command and wait for its end"
+)]
+async fn i_send_a_repeated_pulse_in_pin_command_and_wait_for_its_end(
+    world: &mut PiochaWorld,
+    level: String,
+    duration_us: String,
+    repeat_count: u32,
+    period_us: String,
+    pin: String,
+) {
+    send_pulse(
+        world,
+        pin,
+        level,
+        duration_us,
+        repeat_count,
+        period_us,
+        true,
+    )
+    .await;
+}
+
+#[then(expr = "I must receive an edge event on pin {string} with level {string}")]
+async fn i_must_receive_an_edge_event_on_pin_with_level(
+    world: &mut PiochaWorld,
+    pin: String,
+    level: String,
+) {
+    let event = world.read_event().await.unwrap();
+    match event.event {
+        Some(Event::Edge(edge)) => {
+            assert_eq!(edge.pin_num, pin.parse::<u32>().unwrap());
+            assert_eq!(edge.level, parse_level(&level) as i32);
+            assert_eq!(edge.lost_events, 0);
+        }
+        other => panic!("Unexpected event {:?}", other),
+    }
+}
+
+/// Build a batch request from the table of a step
+///
+/// Each row is `| operation | pin | value |`, the value is ignored by get operations
+///
+/// The pin is a GPIO number or a pin name
+///
+fn batch_from_table(step: &Step, stop_on_failure: bool) -> BatchRequest {
+    let table = step.table.as_ref().expect("Batch operations table missing");
+    let operations = table
+        .rows
+        .iter()
+        .skip(1)
+        .map(|row| {
+            let (pin_num, pin_name) = match row[1].parse() {
+                Ok(pin_num) => (pin_num, String::new()),
+                Err(_) => (0, row[1].clone()),
+            };
+            let operation = match row[0].as_str() {
+                "set_direction" => {
+                    let mut r = SetPinDirectionRequest::default();
+                    r.pin_num = pin_num;
+                    r.set_direction(parse_direction(&row[2]));
+                    Operation::SetPinDirection(r)
+                }
+                "set_value" => {
+                    let mut r = SetPinValueRequest::default();
+                    r.pin_num = pin_num;
+                    r.set_level(parse_level(&row[2]));
+                    Operation::SetPinValue(r)
+                }
+                "get_direction" => Operation::GetPinDirection(GetPinDirectionRequest { pin_num }),
+                "get_value" => Operation::GetPinValue(GetPinValueRequest { pin_num }),
+                _ => panic!("Invalid batch operation"),
+            };
+            BatchOperation {
+                operation: Some(operation),
+                pin_name,
+            }
+        })
+        .collect();
+
+    BatchRequest {
+        operations,
+        stop_on_failure,
+    }
+}
+
+#[when("I send a batch with the operations:")]
+async fn i_send_a_batch_with_the_operations(world: &mut PiochaWorld, step: &Step) {
+    let batch = batch_from_table(step, false);
+    send_request_v2(world, Payload::Batch(batch)).await;
+}
+
+#[when("I send a batch stopping on failure with the operations:")]
+async fn i_send_a_batch_stopping_on_failure_with_the_operations(
+    world: &mut PiochaWorld,
+    step: &Step,
+) {
+    let batch = batch_from_table(step, true);
+    send_request_v2(world, Payload::Batch(batch)).await;
+}
+
+#[when(expr = "I send a batch of {int} port state reads")]
+async fn i_send_a_batch_of_port_state_reads(world: &mut PiochaWorld, count: usize) {
+    let operation = BatchOperation {
+        operation: Some(Operation::GetPortState(GetPortStateRequest {})),
+        pin_name: String::new(),
+    };
+    let batch = BatchRequest {
+        operations: vec![operation; count],
+        stop_on_failure: false,
+    };
+    send_request_v2(world, Payload::Batch(batch)).await;
+}
+
+#[then(expr = "the batch must report {int} results")]
+async fn the_batch_must_report_results(world: &mut PiochaWorld, count: usize) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert_eq!(answer.batch_results.len(), count);
+}
+
+#[then(expr = "the batch must report fewer than {int} results")]
+async fn the_batch_must_report_fewer_than_results(world: &mut PiochaWorld, count: usize) {
+    let answer = world.last_answer.as_ref().unwrap();
+    assert!(answer.batch_results.len() < count);
+}
+
+#[then(expr = "the batch result {int} must report the level {string}")]
+async fn the_batch_result_must_report_the_level(
+    world: &mut PiochaWorld,
+    index: usize,
+    level: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    let result = &answer.batch_results[index - 1];
+    assert_eq!(result.r#type, AnswerType::Success as i32);
+    assert_eq!(result.level, Some(parse_level(&level) as i32));
+}
+
+#[then(expr = "the batch result {int} must report the error {string}")]
+async fn the_batch_result_must_report_the_error(
+    world: &mut PiochaWorld,
+    index: usize,
+    error: String,
+) {
+    let answer = world.last_answer.as_ref().unwrap();
+    let result = &answer.batch_results[index - 1];
+    assert_eq!(result.r#type, AnswerType::Failure as i32);
+    assert_eq!(result.error_code, Some(parse_error_code(&error) as i32));
+}
+
+/// Convert a direction name used in the features into the API value
+///
+fn parse_direction(direction: &str) -> Direction {
+    match direction {
+        "output" => Direction::Output,
+        "input" => Direction::Input,
+        "open_drain" => Direction::OpenDrain,
+        "pwm" => Direction::Pwm,
+        "pattern" => Direction::Pattern,
+        _ => panic!("Invalid direction value"),
+    }
+}
+
+/// Convert a level name used in the features into the API value
+///
+fn parse_level(level: &str) -> Level {
+    match level {
+        "high" => Level::High,
+        "low" => Level::Low,
+        _ => panic!("Invalid level value"),
+    }
+}
+
 #[given(expr = "I send a corrupted data to the device")]
 async fn i_send_a_corrupted_data_to_the_device(world: &mut PiochaWorld) {
-    let mut data = [0u8; 20];
+    let mut data = [0u8; 21];
     let mut rng = rand::thread_rng();
 
-    for i in 0..data.len() {
-        data[i] = rng.gen();
+    // Random bytes without SLIP control bytes, so the device sees a single frame
+    for i in 0..data.len() - 1 {
+        data[i] = loop {
+            let b: u8 = rng.gen();
+            if b != 0xC0 && b != 0xDB {
+                break b;
+            }
+        };
     }
+    data[data.len() - 1] = 0xC0;
 
-    let _size = world.just_write(&data).await.unwrap();
+    // The device answers every frame, whatever it contains
+    let answer_buffer = &mut [0u8; MAX_ANSWER_SIZE];
+    let size = world
+        .write_frame_then_read(&data, answer_buffer)
+        .await
+        .unwrap();
+    let answer = PicohaDioAnswer::decode(answer_buffer[..size].as_ref()).unwrap();
+    world.last_answer = Some(answer);
+}
+
+/// Send a raw protobuf payload and store the answer in the world
+///
+/// The payload carries no request id, so the first answer is taken
+///
+async fn send_raw_request(world: &mut PiochaWorld, payload: &[u8]) {
+    let answer_buffer = &mut [0u8; MAX_ANSWER_SIZE];
+    let size = world
+        .write_then_read(payload, None, answer_buffer)
+        .await
+        .unwrap();
+
+    // Decode the answer
+    let answer_slice = answer_buffer[..size].as_ref();
+    println!("Received {} bytes -> {:?}", size, answer_slice);
+    let answer = PicohaDioAnswer::decode(answer_slice).unwrap();
+    world.last_answer = Some(answer);
+}
+
+#[when(expr = "I send the bytes {string} to the device")]
+async fn i_send_the_bytes_to_the_device(world: &mut PiochaWorld, bytes: String) {
+    let payload: Vec<u8> = bytes
+        .split_whitespace()
+        .map(|b| u8::from_str_radix(b, 16).unwrap())
+        .collect();
+    send_raw_request(world, &payload).await;
+}
+
+#[when(expr = "I send a request of raw type {string} to the device")]
+async fn i_send_a_request_of_raw_type_to_the_device(world: &mut PiochaWorld, r#type: String) {
+    let mut request = PicohaDioRequest::default();
+    request.r#type = r#type.parse().unwrap();
+    send_request(world, request).await;
+}
+
+#[when(expr = "I send a set_value of raw value {string} in pin {string} command to the device")]
+async fn i_send_a_set_value_of_raw_value_in_pin_command_to_the_device(
+    world: &mut PiochaWorld,
+    value: String,
+    pin: String,
+) {
+    let mut request = PicohaDioRequest::default();
+    request.set_type(RequestType::SetPinValue);
+    request.pin_num = pin.parse().unwrap();
+    request.value = value.parse().unwrap();
+    send_request(world, request).await;
 }
 
 #[when(expr = "I wait for 2 seconds")]
 async fn wait_2_sec(world: &mut PiochaWorld) {
     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
 }
-
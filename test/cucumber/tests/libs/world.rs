@@ -1,6 +1,8 @@
+use std::collections::VecDeque;
 use std::fmt::Debug;
 
 use super::api_dio::PicohaDioAnswer;
+use super::api_dio::PicohaDioEvent;
 use super::connectors::SerialSettings;
 use super::connectors::UsbSettings;
 
@@ -8,13 +10,16 @@ use super::connectors::UsbSettings;
 // use super::api_dio::RequestType;
 
 use cucumber::World;
-// use prost::Message;
+use prost::Message;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 use tokio::time::timeout;
 use tokio_serial::SerialStream;
 use tracing;
 
+/// Largest answer of the device, MAX_ANSWER_SIZE in the firmware
+pub const MAX_ANSWER_SIZE: usize = 1024;
+
 // `World` is your shared, likely mutable state.
 // Cucumber constructs it via `Default::default()` for each scenario.
 #[derive(World)]
@@ -27,9 +32,30 @@ pub struct PiochaWorld {
     // Keep track of number of data in the buffer
     pub in_buf_size: usize,
 
-    decode_buffer: serial_line_ip::DecoderBuffer<512>,
+    decode_buffer: serial_line_ip::DecoderBuffer<MAX_ANSWER_SIZE>,
 
     pub last_answer: Option<PicohaDioAnswer>,
+
+    // Id of the last request sent
+    pub last_request_id: u32,
+
+    // Events received while waiting for answers, oldest first
+    pub events: VecDeque<PicohaDioEvent>,
+
+    // Data of the last capture downloaded
+    pub capture_data: Vec<u8>,
+
+    // Answers to other requests received while waiting for an answer, oldest first
+    pub other_answers: VecDeque<PicohaDioAnswer>,
+
+    // Id of the last wait request, answered later
+    pub wait_request_id: Option<u32>,
+
+    // Device time of an answer, to compare with a later one
+    pub remembered_timestamp_us: Option<u64>,
+
+    // Host times when the last sync time request was sent and answered
+    pub sync_host_times_us: Option<(u64, u64)>,
 }
 
 impl Debug for PiochaWorld {
@@ -42,6 +68,13 @@ impl Debug for PiochaWorld {
             .field("in_buf_size", &self.in_buf_size)
             // .field("decode_buffer", &self.decode_buffer)
             .field("last_answer", &self.last_answer)
+            .field("last_request_id", &self.last_request_id)
+            .field("events", &self.events)
+            .field("capture_data", &self.capture_data)
+            .field("other_answers", &self.other_answers)
+            .field("wait_request_id", &self.wait_request_id)
+            .field("remembered_timestamp_us", &self.remembered_timestamp_us)
+            .field("sync_host_times_us", &self.sync_host_times_us)
             .finish()
     }
 }
@@ -62,25 +95,94 @@ impl PiochaWorld {
         Ok(())
     }
 
+    /// Encode a command in a frame and write it without waiting for its answer
+    ///
+    pub async fn write_request(&mut self, command: &[u8]) -> Result<(), String> {
+        let mut encoded_command = [0u8; 1024];
+        let mut slip_encoder = serial_line_ip::Encoder::new();
+        let mut totals = slip_encoder
+            .encode(command, &mut encoded_command)
+            .map_err(|e| format!("Unable to encode command: {:?}", e))?;
+        totals += slip_encoder
+            .finish(&mut encoded_command[totals.written..])
+            .map_err(|e| format!("Unable to finsh command encoding: {:?}", e))?;
+        self.just_write(&encoded_command[..totals.written]).await
+    }
+
+    /// Wait for the answer of a request written earlier
+    ///
+    /// The answer may already have been received while waiting for another one
+    ///
+    pub async fn read_answer(&mut self, request_id: u32) -> Result<PicohaDioAnswer, String> {
+        let received = self
+            .other_answers
+            .iter()
+            .position(|answer| answer.request_id == Some(request_id));
+        if let Some(index) = received {
+            return Ok(self.other_answers.remove(index).unwrap());
+        }
+
+        let response = &mut [0u8; MAX_ANSWER_SIZE];
+        let size = match self.serial_settings.read_timeout {
+            Some(timeout_value) => timeout(
+                timeout_value,
+                self.__write_frame_then_read(&[], Some(request_id), response),
+            )
+            .await
+            .map_err(|e| format!("Timeout reading {:?}", e))??,
+            None => {
+                self.__write_frame_then_read(&[], Some(request_id), response)
+                    .await?
+            }
+        };
+        PicohaDioAnswer::decode(&response[..size]).map_err(|e| format!("{:?}", e))
+    }
+
+    /// Close the serial port, which drops the DTR line and ends the session on the device
+    ///
+    /// Data received and not processed yet belongs to the closed session and is dropped
+    ///
+    pub fn close_connection(&mut self) {
+        self.serial_stream = None;
+        self.in_buf_size = 0;
+        self.decode_buffer.reset();
+        self.events.clear();
+        self.other_answers.clear();
+    }
+
+    /// Get a new id to identify a request
+    ///
+    pub fn new_request_id(&mut self) -> u32 {
+        self.last_request_id = self.last_request_id.wrapping_add(1);
+        self.last_request_id
+    }
+
     /// Lock the connector to write a command then wait for the answers
     ///
+    /// With a request id, answers to other requests (late answers of a timed out request
+    /// for example) are skipped until the one carrying this id is received.
+    ///
     pub async fn write_then_read(
         &mut self,
         command: &[u8],
+        request_id: Option<u32>,
         response: &mut [u8],
     ) -> Result<usize, String> {
         match self.serial_settings.read_timeout {
             // If the timeout is set, use it
             Some(timeout_value) => {
-                return Ok(
-                    timeout(timeout_value, self.__write_then_read(command, response))
-                        .await
-                        .map_err(|e| format!("Timeout reading {:?}", e))??,
-                );
+                return Ok(timeout(
+                    timeout_value,
+                    self.__write_then_read(command, request_id, response),
+                )
+                .await
+                .map_err(|e| format!("Timeout reading {:?}", e))??);
             }
             // Else good luck !
             None => {
-                return Ok(self.__write_then_read(command, response).await?);
+                return Ok(self
+                    .__write_then_read(command, request_id, response)
+                    .await?);
             }
         }
     }
@@ -91,6 +193,7 @@ impl PiochaWorld {
     async fn __write_then_read(
         &mut self,
         command: &[u8],
+        request_id: Option<u32>,
         response: &mut [u8],
     ) -> Result<usize, String> {
         // Prepare encoding
@@ -109,12 +212,46 @@ impl PiochaWorld {
             .finish(&mut encoded_command[totals.written..])
             .map_err(|e| format!("Unable to finsh command encoding: {:?}", e))?;
 
+        self.__write_frame_then_read(&encoded_command[..totals.written], request_id, response)
+            .await
+    }
+
+    /// Write data that is already SLIP framed then wait for the answer
+    ///
+    /// Used to send frames that the regular encoding would never produce
+    ///
+    pub async fn write_frame_then_read(
+        &mut self,
+        frame: &[u8],
+        response: &mut [u8],
+    ) -> Result<usize, String> {
+        match self.serial_settings.read_timeout {
+            Some(timeout_value) => {
+                return Ok(timeout(
+                    timeout_value,
+                    self.__write_frame_then_read(frame, None, response),
+                )
+                .await
+                .map_err(|e| format!("Timeout reading {:?}", e))??);
+            }
+            None => {
+                return Ok(self.__write_frame_then_read(frame, None, response).await?);
+            }
+        }
+    }
+
+    async fn __write_frame_then_read(
+        &mut self,
+        frame: &[u8],
+        request_id: Option<u32>,
+        response: &mut [u8],
+    ) -> Result<usize, String> {
         // Send the command
         let _ = self
             .serial_stream
             .as_mut()
             .ok_or_else(|| format!("No serial stream"))?
-            .write(&encoded_command[..totals.written])
+            .write(frame)
             .await
             .map_err(|e| format!("Unable to write on serial stream: {}", e));
 
@@ -131,26 +268,142 @@ impl PiochaWorld {
                 .await
                 .map_err(|e| format!("Unable to read on serial stream {:?}", e))?;
 
-            let data = &chunk_buffer[..read_size];
-            match self.decode_buffer.feed(data) {
-                core::prelude::v1::Ok((_nb_bytes_processed, found_trame_complete)) => {
-                    if found_trame_complete {
-                        // let trame = self.decode_buffer.slice();
-                        // let request = try_to_decode_api_request(trame).unwrap();
-                        // print_debug_message!("+ process request: {:?}", request);
-                        // request_processor.process_request(&mut serial, request);
+            // A chunk may hold several answers, the whole chunk is decoded so the frames
+            // after the answer are kept and a partial frame is completed by the next read
+            let mut data = &chunk_buffer[..read_size];
+            let mut answer_size = None;
+            while !data.is_empty() {
+                match self.decode_buffer.feed(data) {
+                    core::prelude::v1::Ok((nb_bytes_processed, found_trame_complete)) => {
+                        if !found_trame_complete {
+                            break;
+                        }
+                        data = &data[nb_bytes_processed..];
+                        match answer_size {
+                            Some(_) => self.keep_frame(),
+                            None => answer_size = self.take_answer(request_id, response),
+                        }
+                    }
+                    _ => {
+                        self.decode_buffer.reset();
+                        break;
+                    }
+                }
+            }
+            if let Some(trame_size) = answer_size {
+                return Ok(trame_size);
+            }
+        }
+    }
+
+    /// Wait for the next event pushed by the device
+    ///
+    pub async fn read_event(&mut self) -> Result<PicohaDioEvent, String> {
+        match self.serial_settings.read_timeout {
+            Some(timeout_value) => {
+                return Ok(timeout(timeout_value, self.__read_event())
+                    .await
+                    .map_err(|e| format!("Timeout reading {:?}", e))??);
+            }
+            None => {
+                return Ok(self.__read_event().await?);
+            }
+        }
+    }
+
+    async fn __read_event(&mut self) -> Result<PicohaDioEvent, String> {
+        // Events may already have been received along with answers
+        while self.events.is_empty() {
+            let mut chunk_buffer = [0u8; 512];
+
+            // Read a chunck
+            let read_size = self
+                .serial_stream
+                .as_mut()
+                .ok_or_else(|| format!("No serial stream"))?
+                .read(&mut chunk_buffer)
+                .await
+                .map_err(|e| format!("Unable to read on serial stream {:?}", e))?;
+
+            // Keep every event of the chunk, answers are not expected here
+            let mut data = &chunk_buffer[..read_size];
+            while !data.is_empty() {
+                match self.decode_buffer.feed(data) {
+                    core::prelude::v1::Ok((nb_bytes_processed, found_trame_complete)) => {
+                        if !found_trame_complete {
+                            break;
+                        }
+                        data = &data[nb_bytes_processed..];
+                        if !self.take_event() {
+                            tracing::warn!("Skipping answer: {:?}", self.decode_buffer.slice());
+                        }
+                        self.decode_buffer.reset();
+                    }
+                    _ => {
+                        self.decode_buffer.reset();
                         break;
                     }
                 }
-                _ => {}
             }
         }
+        Ok(self.events.pop_front().unwrap())
+    }
+
+    /// Keep the decoded frame if it is an event, answers never set the event tags
+    ///
+    fn take_event(&mut self) -> bool {
+        match PicohaDioEvent::decode(self.decode_buffer.slice()) {
+            Ok(event) if event.event.is_some() => {
+                tracing::info!("Received event: {:?}", event);
+                self.events.push_back(event);
+                true
+            }
+            _ => false,
+        }
+    }
 
-        let trame_size = self.decode_buffer.slice().len();
-        response[..trame_size].copy_from_slice(self.decode_buffer.slice());
+    /// Copy the decoded frame into the response if it answers the expected request
+    ///
+    /// Events are kept aside for read_event
+    ///
+    fn take_answer(&mut self, request_id: Option<u32>, response: &mut [u8]) -> Option<usize> {
+        if self.take_event() {
+            self.decode_buffer.reset();
+            return None;
+        }
+
+        let trame = self.decode_buffer.slice();
+        let expected = match request_id {
+            Some(id) => PicohaDioAnswer::decode(trame)
+                .map(|answer| answer.request_id == Some(id))
+                .unwrap_or(false),
+            None => true,
+        };
+        if !expected {
+            self.keep_frame();
+            return None;
+        }
 
+        let trame_size = trame.len();
+        response[..trame_size].copy_from_slice(trame);
+        self.decode_buffer.reset();
+        Some(trame_size)
+    }
+
+    /// Keep the decoded frame aside, an event for read_event or an answer for read_answer
+    ///
+    fn keep_frame(&mut self) {
+        if !self.take_event() {
+            let trame = self.decode_buffer.slice();
+            if let Ok(answer) = PicohaDioAnswer::decode(trame) {
+                // Kept for read_answer, late answers of timed out requests are never read
+                tracing::info!("Keeping answer to another request: {:?}", answer);
+                self.other_answers.push_back(answer);
+            } else {
+                tracing::warn!("Skipping answer to another request: {:?}", trame);
+            }
+        }
         self.decode_buffer.reset();
-        Ok(trame_size)
     }
 }
 
@@ -171,6 +424,13 @@ impl std::default::Default for PiochaWorld {
             in_buf_size: 0,
             last_answer: None,
             decode_buffer: serial_line_ip::DecoderBuffer::new(),
+            last_request_id: 0,
+            events: VecDeque::new(),
+            capture_data: Vec::new(),
+            other_answers: VecDeque::new(),
+            wait_request_id: None,
+            remembered_timestamp_us: None,
+            sync_host_times_us: None,
         }
     }
 }
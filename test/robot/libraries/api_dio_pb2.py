@@ -24,21 +24,171 @@ _sym_db = _symbol_database.Default()
 
 
 
-DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x11src/api_dio.proto\"Y\n\x10PicohaDioRequest\x12\x1a\n\x04type\x18\x01 \x01(\x0e\x32\x0c.RequestType\x12\x0f\n\x07pin_num\x18\x02 \x01(\r\x12\x18\n\x05value\x18\x03 \x01(\x0e\x32\t.PinValue\"\x83\x01\n\x0fPicohaDioAnswer\x12\x19\n\x04type\x18\x01 \x01(\x0e\x32\x0b.AnswerType\x12\x1d\n\x05value\x18\x02 \x01(\x0e\x32\t.PinValueH\x00\x88\x01\x01\x12\x1a\n\rerror_message\x18\x03 \x01(\tH\x01\x88\x01\x01\x42\x08\n\x06_valueB\x10\n\x0e_error_message*k\n\x0bRequestType\x12\x08\n\x04PING\x10\x00\x12\x15\n\x11SET_PIN_DIRECTION\x10\x01\x12\x11\n\rSET_PIN_VALUE\x10\x02\x12\x15\n\x11GET_PIN_DIRECTION\x10\x03\x12\x11\n\rGET_PIN_VALUE\x10\x04*4\n\x08PinValue\x12\x07\n\x03LOW\x10\x00\x12\x08\n\x04HIGH\x10\x01\x12\t\n\x05INPUT\x10\x02\x12\n\n\x06OUTPUT\x10\x03*&\n\nAnswerType\x12\x0b\n\x07SUCCESS\x10\x00\x12\x0b\n\x07\x46\x41ILURE\x10\x01\x62\x06proto3')
+DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x11src/api_dio.proto\"\xe5\x01\n\x10PicohaDioRequest\x12\x1a\n\x04type\x18\x01 \x01(\x0e\x32\x0c.RequestType\x12\x0f\n\x07pin_num\x18\x02 \x01(\r\x12\x18\n\x05value\x18\x03 \x01(\x0e\x32\t.PinValue\x12\x16\n\x04pull\x18\x04 \x01(\x0e\x32\x08.PinPull\x12)\n\x0e\x64rive_strength\x18\x05 \x01(\x0e\x32\x11.PinDriveStrength\x12\x1f\n\tslew_rate\x18\x06 \x01(\x0e\x32\x0c.PinSlewRate\x12\x17\n\nrequest_id\x18\x07 \x01(\rH\x00\x88\x01\x01\x42\r\n\x0b_request_id\"\r\n\x0bPingRequest\"H\n\x16SetPinDirectionRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x1d\n\tdirection\x18\x02 \x01(\x0e\x32\n.Direction\"<\n\x12SetPinValueRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x15\n\x05level\x18\x02 \x01(\x0e\x32\x06.Level\")\n\x16GetPinDirectionRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\"%\n\x12GetPinValueRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\"<\n\x11SetPinPullRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x16\n\x04pull\x18\x02 \x01(\x0e\x32\x08.PinPull\"$\n\x11GetPinPullRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\"q\n\x12SetPinDriveRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12)\n\x0e\x64rive_strength\x18\x02 \x01(\x0e\x32\x11.PinDriveStrength\x12\x1f\n\tslew_rate\x18\x03 \x01(\x0e\x32\x0c.PinSlewRate\"%\n\x12GetPinDriveRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\"2\n\x13SetPortValueRequest\x12\x0c\n\x04mask\x18\x01 \x01(\r\x12\r\n\x05value\x18\x02 \x01(\r\"6\n\x17SetPortDirectionRequest\x12\x0c\n\x04mask\x18\x01 \x01(\r\x12\r\n\x05value\x18\x02 \x01(\r\"\x15\n\x13GetPortStateRequest\"\x9a\x01\n\tPortState\x12\x11\n\tavailable\x18\x01 \x01(\r\x12\r\n\x05input\x18\x02 \x01(\r\x12\x0e\n\x06output\x18\x03 \x01(\r\x12\x12\n\nopen_drain\x18\x04 \x01(\r\x12\x13\n\x0binput_level\x18\x05 \x01(\r\x12\x14\n\x0coutput_latch\x18\x06 \x01(\r\x12\x0b\n\x03pwm\x18\x07 \x01(\r\x12\x0f\n\x07pattern\x18\x08 \x01(\r\"=\n\x15SubscribeEdgesRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x13\n\x04\x65\x64ge\x18\x02 \x01(\x0e\x32\x05.Edge\"=\n\x15SetPinDebounceRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x13\n\x0b\x64\x65\x62ounce_us\x18\x02 \x01(\r\"(\n\x15GetPinDebounceRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\"C\n\x1b\x43onfigureEdgeCounterRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x13\n\x04\x65\x64ge\x18\x02 \x01(\x0e\x32\x05.Edge\"8\n\x16ReadEdgeCounterRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\r\n\x05\x63lear\x18\x02 \x01(\x08\"*\n\x17ResetEdgeCounterRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\"@\n\x17MeasureFrequencyRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x14\n\x0cgate_time_us\x18\x02 \x01(\r\"g\n\x14\x46requencyMeasurement\x12\x12\n\nedge_count\x18\x01 \x01(\r\x12\x14\n\x0c\x66requency_hz\x18\x02 \x01(\x01\x12\x11\n\tperiod_us\x18\x03 \x01(\x01\x12\x12\n\nduty_cycle\x18\x04 \x01(\x01\"M\n\x10SetPinPwmRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x14\n\x0c\x66requency_hz\x18\x02 \x01(\r\x12\x12\n\nduty_cycle\x18\x03 \x01(\x01\";\n\x14SetPinPwmDutyRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x12\n\nduty_cycle\x18\x02 \x01(\x01\"5\n\tPwmOutput\x12\x14\n\x0c\x66requency_hz\x18\x01 \x01(\x01\x12\x12\n\nduty_cycle\x18\x02 \x01(\x01\"\x94\x01\n\x0cPulseRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x1c\n\x0c\x61\x63tive_level\x18\x02 \x01(\x0e\x32\x06.Level\x12\x13\n\x0b\x64uration_us\x18\x03 \x01(\r\x12\x14\n\x0crepeat_count\x18\x04 \x01(\r\x12\x11\n\tperiod_us\x18\x05 \x01(\r\x12\x17\n\x0fwait_completion\x18\x06 \x01(\x08\"?\n\x0cSequenceStep\x12\x11\n\toffset_us\x18\x01 \x01(\r\x12\x0c\n\x04mask\x18\x02 \x01(\r\x12\x0e\n\x06levels\x18\x03 \x01(\r\"C\n\x13LoadSequenceRequest\x12\x1c\n\x05steps\x18\x01 \x03(\x0b\x32\r.SequenceStep\x12\x0e\n\x06\x61ppend\x18\x02 \x01(\x08\".\n\x14StartSequenceRequest\x12\x16\n\x0eloop_period_us\x18\x01 \x01(\r\"\x15\n\x13StopSequenceRequest\"\x19\n\x17GetSequenceStateRequest\"M\n\rSequenceState\x12\x12\n\nstep_count\x18\x01 \x01(\r\x12\x0f\n\x07running\x18\x02 \x01(\x08\x12\x17\n\x0f\x63ompleted_loops\x18\x03 \x01(\r\"\xdd\x01\n\x13StartCaptureRequest\x12\x11\n\tfirst_pin\x18\x01 \x01(\r\x12\x11\n\tpin_count\x18\x02 \x01(\r\x12\x16\n\x0esample_rate_hz\x18\x03 \x01(\r\x12\x1b\n\x13pre_trigger_samples\x18\x04 \x01(\r\x12\x1c\n\x14post_trigger_samples\x18\x05 \x01(\r\x12 \n\x07trigger\x18\x06 \x01(\x0e\x32\x0f.CaptureTrigger\x12\x14\n\x0ctrigger_mask\x18\x07 \x01(\r\x12\x15\n\rtrigger_value\x18\x08 \x01(\r\"\x14\n\x12StopCaptureRequest\"\x18\n\x16GetCaptureStateRequest\"$\n\x12ReadCaptureRequest\x12\x0e\n\x06offset\x18\x01 \x01(\r\"\xf2\x01\n\x0c\x43\x61ptureState\x12\x1e\n\x06status\x18\x01 \x01(\x0e\x32\x0e.CaptureStatus\x12\x11\n\tfirst_pin\x18\x02 \x01(\r\x12\x11\n\tpin_count\x18\x03 \x01(\r\x12\x14\n\x0csample_width\x18\x04 \x01(\r\x12\x16\n\x0esample_rate_hz\x18\x05 \x01(\x01\x12\x14\n\x0c\x66irst_sample\x18\x06 \x01(\r\x12\x14\n\x0csample_count\x18\x07 \x01(\r\x12\x16\n\x0etrigger_sample\x18\x08 \x01(\r\x12\x11\n\tdata_size\x18\t \x01(\r\x12\x17\n\x0fsamples_skipped\x18\n \x01(\x08\"?\n\x0c\x43\x61ptureChunk\x12\x0e\n\x06offset\x18\x01 \x01(\r\x12\x0c\n\x04\x64\x61ta\x18\x02 \x01(\x0c\x12\x11\n\tdata_size\x18\x03 \x01(\r\"X\n\x12LoadPatternRequest\x12\x11\n\tfirst_pin\x18\x01 \x01(\r\x12\x11\n\tpin_count\x18\x02 \x01(\r\x12\x0c\n\x04\x64\x61ta\x18\x03 \x01(\x0c\x12\x0e\n\x06\x61ppend\x18\x04 \x01(\x08\">\n\x13StartPatternRequest\x12\x16\n\x0esample_rate_hz\x18\x01 \x01(\r\x12\x0f\n\x07looping\x18\x02 \x01(\x08\"\x14\n\x12StopPatternRequest\"\x18\n\x16GetPatternStateRequest\"\x9a\x01\n\x0cPatternState\x12\x11\n\tfirst_pin\x18\x01 \x01(\r\x12\x11\n\tpin_count\x18\x02 \x01(\r\x12\x14\n\x0csample_width\x18\x03 \x01(\r\x12\x14\n\x0csample_count\x18\x04 \x01(\r\x12\x0f\n\x07running\x18\x05 \x01(\x08\x12\x0f\n\x07looping\x18\x06 \x01(\x08\x12\x16\n\x0esample_rate_hz\x18\x07 \x01(\x01\"d\n\x0eWaitForRequest\x12\x0c\n\x04mask\x18\x01 \x01(\r\x12\r\n\x05value\x18\x02 \x01(\r\x12!\n\tcondition\x18\x03 \x01(\x0e\x32\x0e.WaitCondition\x12\x12\n\ntimeout_us\x18\x04 \x01(\r\"0\n\nWaitResult\x12\x12\n\nelapsed_us\x18\x01 \x01(\r\x12\x0e\n\x06levels\x18\x02 \x01(\r\"\'\n\x0fSyncTimeRequest\x12\x14\n\x0chost_time_us\x18\x01 \x01(\x04\"5\n\x08TimeSync\x12\x14\n\x0chost_time_us\x18\x01 \x01(\x04\x12\x13\n\x0breceived_us\x18\x02 \x01(\x04\"D\n\x16SetPinSafeStateRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x19\n\x05state\x18\x02 \x01(\x0e\x32\n.SafeState\"8\n\x18\x43onfigureFailsafeRequest\x12\x1c\n\x14heartbeat_timeout_ms\x18\x01 \x01(\r\"(\n\x17GetFailsafeStateRequest\x12\r\n\x05\x63lear\x18\x01 \x01(\x08\"\x9e\x01\n\rFailsafeState\x12\x1c\n\x14heartbeat_timeout_ms\x18\x01 \x01(\r\x12\x0f\n\x07tripped\x18\x02 \x01(\x08\x12\x15\n\rtripped_at_us\x18\x03 \x01(\x04\x12\x11\n\tsafe_pins\x18\x04 \x01(\r\x12\x16\n\x0esession_closed\x18\x05 \x01(\x08\x12\x1c\n\x14session_closed_at_us\x18\x06 \x01(\x04\"\x13\n\x11SaveConfigRequest\"(\n\x15GetSavedConfigRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\"\x14\n\x12\x45raseConfigRequest\"\x18\n\x16RestoreDefaultsRequest\"\xea\x01\n\x0eSavedPinConfig\x12\r\n\x05saved\x18\x01 \x01(\x08\x12\x0f\n\x07version\x18\x02 \x01(\r\x12\x1d\n\tdirection\x18\x03 \x01(\x0e\x32\n.Direction\x12\x15\n\x05level\x18\x04 \x01(\x0e\x32\x06.Level\x12\x16\n\x04pull\x18\x05 \x01(\x0e\x32\x08.PinPull\x12)\n\x0e\x64rive_strength\x18\x06 \x01(\x0e\x32\x11.PinDriveStrength\x12\x1f\n\tslew_rate\x18\x07 \x01(\x0e\x32\x0c.PinSlewRate\x12\x1e\n\nsafe_state\x18\x08 \x01(\x0e\x32\n.SafeState\"2\n\x11SetPinNameRequest\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x0c\n\x04name\x18\x02 \x01(\t\"\x14\n\x12GetPinNamesRequest\"(\n\x07PinName\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x0c\n\x04name\x18\x02 \x01(\t\"\xc2\x0f\n\x0e\x42\x61tchOperation\x12\x10\n\x08pin_name\x18\x01 \x01(\t\x12\x34\n\x11set_pin_direction\x18\x11 \x01(\x0b\x32\x17.SetPinDirectionRequestH\x00\x12,\n\rset_pin_value\x18\x12 \x01(\x0b\x32\x13.SetPinValueRequestH\x00\x12\x34\n\x11get_pin_direction\x18\x13 \x01(\x0b\x32\x17.GetPinDirectionRequestH\x00\x12,\n\rget_pin_value\x18\x14 \x01(\x0b\x32\x13.GetPinValueRequestH\x00\x12*\n\x0cset_pin_pull\x18\x15 \x01(\x0b\x32\x12.SetPinPullRequestH\x00\x12*\n\x0cget_pin_pull\x18\x16 \x01(\x0b\x32\x12.GetPinPullRequestH\x00\x12,\n\rset_pin_drive\x18\x17 \x01(\x0b\x32\x13.SetPinDriveRequestH\x00\x12,\n\rget_pin_drive\x18\x18 \x01(\x0b\x32\x13.GetPinDriveRequestH\x00\x12.\n\x0eset_port_value\x18\x1a \x01(\x0b\x32\x14.SetPortValueRequestH\x00\x12\x36\n\x12set_port_direction\x18\x1b \x01(\x0b\x32\x18.SetPortDirectionRequestH\x00\x12.\n\x0eget_port_state\x18\x1c \x01(\x0b\x32\x14.GetPortStateRequestH\x00\x12\x31\n\x0fsubscribe_edges\x18\x1d \x01(\x0b\x32\x16.SubscribeEdgesRequestH\x00\x12\x32\n\x10set_pin_debounce\x18\x1e \x01(\x0b\x32\x16.SetPinDebounceRequestH\x00\x12\x32\n\x10get_pin_debounce\x18\x1f \x01(\x0b\x32\x16.GetPinDebounceRequestH\x00\x12>\n\x16\x63onfigure_edge_counter\x18  \x01(\x0b\x32\x1c.ConfigureEdgeCounterRequestH\x00\x12\x34\n\x11read_edge_counter\x18! \x01(\x0b\x32\x17.ReadEdgeCounterRequestH\x00\x12\x36\n\x12reset_edge_counter\x18\" \x01(\x0b\x32\x18.ResetEdgeCounterRequestH\x00\x12(\n\x0bset_pin_pwm\x18$ \x01(\x0b\x32\x11.SetPinPwmRequestH\x00\x12\x31\n\x10set_pin_pwm_duty\x18% \x01(\x0b\x32\x15.SetPinPwmDutyRequestH\x00\x12\x1e\n\x05pulse\x18& \x01(\x0b\x32\r.PulseRequestH\x00\x12-\n\rload_sequence\x18\' \x01(\x0b\x32\x14.LoadSequenceRequestH\x00\x12/\n\x0estart_sequence\x18( \x01(\x0b\x32\x15.StartSequenceRequestH\x00\x12-\n\rstop_sequence\x18) \x01(\x0b\x32\x14.StopSequenceRequestH\x00\x12\x36\n\x12get_sequence_state\x18* \x01(\x0b\x32\x18.GetSequenceStateRequestH\x00\x12-\n\rstart_capture\x18+ \x01(\x0b\x32\x14.StartCaptureRequestH\x00\x12+\n\x0cstop_capture\x18, \x01(\x0b\x32\x13.StopCaptureRequestH\x00\x12\x34\n\x11get_capture_state\x18- \x01(\x0b\x32\x17.GetCaptureStateRequestH\x00\x12+\n\x0cload_pattern\x18/ \x01(\x0b\x32\x13.LoadPatternRequestH\x00\x12-\n\rstart_pattern\x18\x30 \x01(\x0b\x32\x14.StartPatternRequestH\x00\x12+\n\x0cstop_pattern\x18\x31 \x01(\x0b\x32\x13.StopPatternRequestH\x00\x12\x34\n\x11get_pattern_state\x18\x32 \x01(\x0b\x32\x17.GetPatternStateRequestH\x00\x12\x35\n\x12set_pin_safe_state\x18\x35 \x01(\x0b\x32\x17.SetPinSafeStateRequestH\x00\x12\x37\n\x12\x63onfigure_failsafe\x18\x36 \x01(\x0b\x32\x19.ConfigureFailsafeRequestH\x00\x12\x36\n\x12get_failsafe_state\x18\x37 \x01(\x0b\x32\x18.GetFailsafeStateRequestH\x00\x12)\n\x0bsave_config\x18\x38 \x01(\x0b\x32\x12.SaveConfigRequestH\x00\x12\x32\n\x10get_saved_config\x18\x39 \x01(\x0b\x32\x16.GetSavedConfigRequestH\x00\x12+\n\x0c\x65rase_config\x18: \x01(\x0b\x32\x13.EraseConfigRequestH\x00\x12\x33\n\x10restore_defaults\x18; \x01(\x0b\x32\x17.RestoreDefaultsRequestH\x00\x12*\n\x0cset_pin_name\x18< \x01(\x0b\x32\x12.SetPinNameRequestH\x00\x42\x0b\n\toperationJ\x04\x08#\x10$J\x04\x08.\x10/\"L\n\x0c\x42\x61tchRequest\x12#\n\noperations\x18\x01 \x03(\x0b\x32\x0f.BatchOperation\x12\x17\n\x0fstop_on_failure\x18\x02 \x01(\x08\"\xfc\x11\n\x12PicohaDioRequestV2\x12\x17\n\nrequest_id\x18\x07 \x01(\rH\x01\x88\x01\x01\x12\x10\n\x08pin_name\x18\x08 \x01(\t\x12\x1c\n\x04ping\x18\x10 \x01(\x0b\x32\x0c.PingRequestH\x00\x12\x34\n\x11set_pin_direction\x18\x11 \x01(\x0b\x32\x17.SetPinDirectionRequestH\x00\x12,\n\rset_pin_value\x18\x12 \x01(\x0b\x32\x13.SetPinValueRequestH\x00\x12\x34\n\x11get_pin_direction\x18\x13 \x01(\x0b\x32\x17.GetPinDirectionRequestH\x00\x12,\n\rget_pin_value\x18\x14 \x01(\x0b\x32\x13.GetPinValueRequestH\x00\x12*\n\x0cset_pin_pull\x18\x15 \x01(\x0b\x32\x12.SetPinPullRequestH\x00\x12*\n\x0cget_pin_pull\x18\x16 \x01(\x0b\x32\x12.GetPinPullRequestH\x00\x12,\n\rset_pin_drive\x18\x17 \x01(\x0b\x32\x13.SetPinDriveRequestH\x00\x12,\n\rget_pin_drive\x18\x18 \x01(\x0b\x32\x13.GetPinDriveRequestH\x00\x12\x1e\n\x05\x62\x61tch\x18\x19 \x01(\x0b\x32\r.BatchRequestH\x00\x12.\n\x0eset_port_value\x18\x1a \x01(\x0b\x32\x14.SetPortValueRequestH\x00\x12\x36\n\x12set_port_direction\x18\x1b \x01(\x0b\x32\x18.SetPortDirectionRequestH\x00\x12.\n\x0eget_port_state\x18\x1c \x01(\x0b\x32\x14.GetPortStateRequestH\x00\x12\x31\n\x0fsubscribe_edges\x18\x1d \x01(\x0b\x32\x16.SubscribeEdgesRequestH\x00\x12\x32\n\x10set_pin_debounce\x18\x1e \x01(\x0b\x32\x16.SetPinDebounceRequestH\x00\x12\x32\n\x10get_pin_debounce\x18\x1f \x01(\x0b\x32\x16.GetPinDebounceRequestH\x00\x12>\n\x16\x63onfigure_edge_counter\x18  \x01(\x0b\x32\x1c.ConfigureEdgeCounterRequestH\x00\x12\x34\n\x11read_edge_counter\x18! \x01(\x0b\x32\x17.ReadEdgeCounterRequestH\x00\x12\x36\n\x12reset_edge_counter\x18\" \x01(\x0b\x32\x18.ResetEdgeCounterRequestH\x00\x12\x35\n\x11measure_frequency\x18# \x01(\x0b\x32\x18.MeasureFrequencyRequestH\x00\x12(\n\x0bset_pin_pwm\x18$ \x01(\x0b\x32\x11.SetPinPwmRequestH\x00\x12\x31\n\x10set_pin_pwm_duty\x18% \x01(\x0b\x32\x15.SetPinPwmDutyRequestH\x00\x12\x1e\n\x05pulse\x18& \x01(\x0b\x32\r.PulseRequestH\x00\x12-\n\rload_sequence\x18\' \x01(\x0b\x32\x14.LoadSequenceRequestH\x00\x12/\n\x0estart_sequence\x18( \x01(\x0b\x32\x15.StartSequenceRequestH\x00\x12-\n\rstop_sequence\x18) \x01(\x0b\x32\x14.StopSequenceRequestH\x00\x12\x36\n\x12get_sequence_state\x18* \x01(\x0b\x32\x18.GetSequenceStateRequestH\x00\x12-\n\rstart_capture\x18+ \x01(\x0b\x32\x14.StartCaptureRequestH\x00\x12+\n\x0cstop_capture\x18, \x01(\x0b\x32\x13.StopCaptureRequestH\x00\x12\x34\n\x11get_capture_state\x18- \x01(\x0b\x32\x17.GetCaptureStateRequestH\x00\x12+\n\x0cread_capture\x18. \x01(\x0b\x32\x13.ReadCaptureRequestH\x00\x12+\n\x0cload_pattern\x18/ \x01(\x0b\x32\x13.LoadPatternRequestH\x00\x12-\n\rstart_pattern\x18\x30 \x01(\x0b\x32\x14.StartPatternRequestH\x00\x12+\n\x0cstop_pattern\x18\x31 \x01(\x0b\x32\x13.StopPatternRequestH\x00\x12\x34\n\x11get_pattern_state\x18\x32 \x01(\x0b\x32\x17.GetPatternStateRequestH\x00\x12#\n\x08wait_for\x18\x33 \x01(\x0b\x32\x0f.WaitForRequestH\x00\x12%\n\tsync_time\x18\x34 \x01(\x0b\x32\x10.SyncTimeRequestH\x00\x12\x35\n\x12set_pin_safe_state\x18\x35 \x01(\x0b\x32\x17.SetPinSafeStateRequestH\x00\x12\x37\n\x12\x63onfigure_failsafe\x18\x36 \x01(\x0b\x32\x19.ConfigureFailsafeRequestH\x00\x12\x36\n\x12get_failsafe_state\x18\x37 \x01(\x0b\x32\x18.GetFailsafeStateRequestH\x00\x12)\n\x0bsave_config\x18\x38 \x01(\x0b\x32\x12.SaveConfigRequestH\x00\x12\x32\n\x10get_saved_config\x18\x39 \x01(\x0b\x32\x16.GetSavedConfigRequestH\x00\x12+\n\x0c\x65rase_config\x18: \x01(\x0b\x32\x13.EraseConfigRequestH\x00\x12\x33\n\x10restore_defaults\x18; \x01(\x0b\x32\x17.RestoreDefaultsRequestH\x00\x12*\n\x0cset_pin_name\x18< \x01(\x0b\x32\x12.SetPinNameRequestH\x00\x12,\n\rget_pin_names\x18= \x01(\x0b\x32\x13.GetPinNamesRequestH\x00\x42\t\n\x07payloadB\r\n\x0b_request_id\"\xaf\x07\n\x14\x42\x61tchOperationResult\x12\x19\n\x04type\x18\x01 \x01(\x0e\x32\x0b.AnswerType\x12#\n\nerror_code\x18\x02 \x01(\x0e\x32\n.ErrorCodeH\x00\x88\x01\x01\x12\"\n\tdirection\x18\x03 \x01(\x0e\x32\n.DirectionH\x01\x88\x01\x01\x12\x1a\n\x05level\x18\x04 \x01(\x0e\x32\x06.LevelH\x02\x88\x01\x01\x12\x1b\n\x04pull\x18\x05 \x01(\x0e\x32\x08.PinPullH\x03\x88\x01\x01\x12.\n\x0e\x64rive_strength\x18\x06 \x01(\x0e\x32\x11.PinDriveStrengthH\x04\x88\x01\x01\x12$\n\tslew_rate\x18\x07 \x01(\x0e\x32\x0c.PinSlewRateH\x05\x88\x01\x01\x12#\n\nport_state\x18\x08 \x01(\x0b\x32\n.PortStateH\x06\x88\x01\x01\x12\x18\n\x0b\x64\x65\x62ounce_us\x18\t \x01(\rH\x07\x88\x01\x01\x12\x17\n\nedge_count\x18\n \x01(\rH\x08\x88\x01\x01\x12 \n\x0c\x63ounted_edge\x18\x0b \x01(\x0e\x32\x05.EdgeH\t\x88\x01\x01\x12-\n\tfrequency\x18\x0c \x01(\x0b\x32\x15.FrequencyMeasurementH\n\x88\x01\x01\x12\x1c\n\x03pwm\x18\r \x01(\x0b\x32\n.PwmOutputH\x0b\x88\x01\x01\x12%\n\x08sequence\x18\x0e \x01(\x0b\x32\x0e.SequenceStateH\x0c\x88\x01\x01\x12#\n\x07\x63\x61pture\x18\x0f \x01(\x0b\x32\r.CaptureStateH\r\x88\x01\x01\x12#\n\x07pattern\x18\x11 \x01(\x0b\x32\r.PatternStateH\x0e\x88\x01\x01\x12\x19\n\x0ctimestamp_us\x18\x12 \x01(\x04H\x0f\x88\x01\x01\x12%\n\x08\x66\x61ilsafe\x18\x13 \x01(\x0b\x32\x0e.FailsafeStateH\x10\x88\x01\x01\x12*\n\x0csaved_config\x18\x14 \x01(\x0b\x32\x0f.SavedPinConfigH\x11\x88\x01\x01\x42\r\n\x0b_error_codeB\x0c\n\n_directionB\x08\n\x06_levelB\x07\n\x05_pullB\x11\n\x0f_drive_strengthB\x0c\n\n_slew_rateB\r\n\x0b_port_stateB\x0e\n\x0c_debounce_usB\r\n\x0b_edge_countB\x0f\n\r_counted_edgeB\x0c\n\n_frequencyB\x06\n\x04_pwmB\x0b\n\t_sequenceB\n\n\x08_captureB\n\n\x08_patternB\x0f\n\r_timestamp_usB\x0b\n\t_failsafeB\x0f\n\r_saved_configJ\x04\x08\x10\x10\x11\"\xe1\n\n\x0fPicohaDioAnswer\x12\x19\n\x04type\x18\x01 \x01(\x0e\x32\x0b.AnswerType\x12\x1d\n\x05value\x18\x02 \x01(\x0e\x32\t.PinValueH\x00\x88\x01\x01\x12\x1a\n\rerror_message\x18\x03 \x01(\tH\x01\x88\x01\x01\x12\x1b\n\x04pull\x18\x04 \x01(\x0e\x32\x08.PinPullH\x02\x88\x01\x01\x12.\n\x0e\x64rive_strength\x18\x05 \x01(\x0e\x32\x11.PinDriveStrengthH\x03\x88\x01\x01\x12$\n\tslew_rate\x18\x06 \x01(\x0e\x32\x0c.PinSlewRateH\x04\x88\x01\x01\x12\"\n\tdirection\x18\x07 \x01(\x0e\x32\n.DirectionH\x05\x88\x01\x01\x12\x1a\n\x05level\x18\x08 \x01(\x0e\x32\x06.LevelH\x06\x88\x01\x01\x12#\n\nerror_code\x18\t \x01(\x0e\x32\n.ErrorCodeH\x07\x88\x01\x01\x12\x17\n\nrequest_id\x18\n \x01(\rH\x08\x88\x01\x01\x12\'\n\x0crequest_type\x18\x0b \x01(\x0e\x32\x0c.RequestTypeH\t\x88\x01\x01\x12\x14\n\x07pin_num\x18\x0c \x01(\rH\n\x88\x01\x01\x12,\n\rbatch_results\x18\r \x03(\x0b\x32\x15.BatchOperationResult\x12#\n\nport_state\x18\x0e \x01(\x0b\x32\n.PortStateH\x0b\x88\x01\x01\x12\x18\n\x0b\x64\x65\x62ounce_us\x18\x0f \x01(\rH\x0c\x88\x01\x01\x12\x17\n\nedge_count\x18\x10 \x01(\rH\r\x88\x01\x01\x12 \n\x0c\x63ounted_edge\x18\x11 \x01(\x0e\x32\x05.EdgeH\x0e\x88\x01\x01\x12-\n\tfrequency\x18\x12 \x01(\x0b\x32\x15.FrequencyMeasurementH\x0f\x88\x01\x01\x12\x1c\n\x03pwm\x18\x13 \x01(\x0b\x32\n.PwmOutputH\x10\x88\x01\x01\x12%\n\x08sequence\x18\x14 \x01(\x0b\x32\x0e.SequenceStateH\x11\x88\x01\x01\x12#\n\x07\x63\x61pture\x18\x15 \x01(\x0b\x32\r.CaptureStateH\x12\x88\x01\x01\x12)\n\rcapture_chunk\x18\x16 \x01(\x0b\x32\r.CaptureChunkH\x13\x88\x01\x01\x12#\n\x07pattern\x18\x17 \x01(\x0b\x32\r.PatternStateH\x14\x88\x01\x01\x12\x1e\n\x04wait\x18\x18 \x01(\x0b\x32\x0b.WaitResultH\x15\x88\x01\x01\x12\x19\n\x0ctimestamp_us\x18\x19 \x01(\x04H\x16\x88\x01\x01\x12!\n\ttime_sync\x18\x1a \x01(\x0b\x32\t.TimeSyncH\x17\x88\x01\x01\x12%\n\x08\x66\x61ilsafe\x18\x1b \x01(\x0b\x32\x0e.FailsafeStateH\x18\x88\x01\x01\x12*\n\x0csaved_config\x18\x1c \x01(\x0b\x32\x0f.SavedPinConfigH\x19\x88\x01\x01\x12\x1b\n\tpin_names\x18\x1d \x03(\x0b\x32\x08.PinNameB\x08\n\x06_valueB\x10\n\x0e_error_messageB\x07\n\x05_pullB\x11\n\x0f_drive_strengthB\x0c\n\n_slew_rateB\x0c\n\n_directionB\x08\n\x06_levelB\r\n\x0b_error_codeB\r\n\x0b_request_idB\x0f\n\r_request_typeB\n\n\x08_pin_numB\r\n\x0b_port_stateB\x0e\n\x0c_debounce_usB\r\n\x0b_edge_countB\x0f\n\r_counted_edgeB\x0c\n\n_frequencyB\x06\n\x04_pwmB\x0b\n\t_sequenceB\n\n\x08_captureB\x10\n\x0e_capture_chunkB\n\n\x08_patternB\x07\n\x05_waitB\x0f\n\r_timestamp_usB\x0c\n\n_time_syncB\x0b\n\t_failsafeB\x0f\n\r_saved_config\"^\n\tEdgeEvent\x12\x0f\n\x07pin_num\x18\x01 \x01(\r\x12\x15\n\x05level\x18\x02 \x01(\x0e\x32\x06.Level\x12\x14\n\x0ctimestamp_us\x18\x03 \x01(\x04\x12\x13\n\x0blost_events\x18\x04 \x01(\r\"5\n\x0ePicohaDioEvent\x12\x1a\n\x04\x65\x64ge\x18  \x01(\x0b\x32\n.EdgeEventH\x00\x42\x07\n\x05\x65vent*\x9c\x07\n\x0bRequestType\x12\x08\n\x04PING\x10\x00\x12\x15\n\x11SET_PIN_DIRECTION\x10\x01\x12\x11\n\rSET_PIN_VALUE\x10\x02\x12\x15\n\x11GET_PIN_DIRECTION\x10\x03\x12\x11\n\rGET_PIN_VALUE\x10\x04\x12\x10\n\x0cSET_PIN_PULL\x10\x05\x12\x10\n\x0cGET_PIN_PULL\x10\x06\x12\x11\n\rSET_PIN_DRIVE\x10\x07\x12\x11\n\rGET_PIN_DRIVE\x10\x08\x12\t\n\x05\x42\x41TCH\x10\t\x12\x12\n\x0eSET_PORT_VALUE\x10\n\x12\x16\n\x12SET_PORT_DIRECTION\x10\x0b\x12\x12\n\x0eGET_PORT_STATE\x10\x0c\x12\x13\n\x0fSUBSCRIBE_EDGES\x10\r\x12\x14\n\x10SET_PIN_DEBOUNCE\x10\x0e\x12\x14\n\x10GET_PIN_DEBOUNCE\x10\x0f\x12\x1a\n\x16\x43ONFIGURE_EDGE_COUNTER\x10\x10\x12\x15\n\x11READ_EDGE_COUNTER\x10\x11\x12\x16\n\x12RESET_EDGE_COUNTER\x10\x12\x12\x15\n\x11MEASURE_FREQUENCY\x10\x13\x12\x0f\n\x0bSET_PIN_PWM\x10\x14\x12\x14\n\x10SET_PIN_PWM_DUTY\x10\x15\x12\t\n\x05PULSE\x10\x16\x12\x11\n\rLOAD_SEQUENCE\x10\x17\x12\x12\n\x0eSTART_SEQUENCE\x10\x18\x12\x11\n\rSTOP_SEQUENCE\x10\x19\x12\x16\n\x12GET_SEQUENCE_STATE\x10\x1a\x12\x11\n\rSTART_CAPTURE\x10\x1b\x12\x10\n\x0cSTOP_CAPTURE\x10\x1c\x12\x15\n\x11GET_CAPTURE_STATE\x10\x1d\x12\x10\n\x0cREAD_CAPTURE\x10\x1e\x12\x10\n\x0cLOAD_PATTERN\x10\x1f\x12\x11\n\rSTART_PATTERN\x10 \x12\x10\n\x0cSTOP_PATTERN\x10!\x12\x15\n\x11GET_PATTERN_STATE\x10\"\x12\x0c\n\x08WAIT_FOR\x10#\x12\r\n\tSYNC_TIME\x10$\x12\x16\n\x12SET_PIN_SAFE_STATE\x10%\x12\x16\n\x12\x43ONFIGURE_FAILSAFE\x10&\x12\x16\n\x12GET_FAILSAFE_STATE\x10\'\x12\x0f\n\x0bSAVE_CONFIG\x10(\x12\x14\n\x10GET_SAVED_CONFIG\x10)\x12\x10\n\x0c\x45RASE_CONFIG\x10*\x12\x14\n\x10RESTORE_DEFAULTS\x10+\x12\x10\n\x0cSET_PIN_NAME\x10,\x12\x11\n\rGET_PIN_NAMES\x10-*D\n\x08PinValue\x12\x07\n\x03LOW\x10\x00\x12\x08\n\x04HIGH\x10\x01\x12\t\n\x05INPUT\x10\x02\x12\n\n\x06OUTPUT\x10\x03\x12\x0e\n\nOPEN_DRAIN\x10\x04*G\n\x07PinPull\x12\r\n\tPULL_NONE\x10\x00\x12\x0b\n\x07PULL_UP\x10\x01\x12\r\n\tPULL_DOWN\x10\x02\x12\x11\n\rPULL_BUS_KEEP\x10\x03*O\n\x10PinDriveStrength\x12\r\n\tDRIVE_2MA\x10\x00\x12\r\n\tDRIVE_4MA\x10\x01\x12\r\n\tDRIVE_8MA\x10\x02\x12\x0e\n\nDRIVE_12MA\x10\x03*+\n\x0bPinSlewRate\x12\r\n\tSLEW_SLOW\x10\x00\x12\r\n\tSLEW_FAST\x10\x01*z\n\tDirection\x12\x13\n\x0f\x44IRECTION_INPUT\x10\x00\x12\x14\n\x10\x44IRECTION_OUTPUT\x10\x01\x12\x18\n\x14\x44IRECTION_OPEN_DRAIN\x10\x02\x12\x11\n\rDIRECTION_PWM\x10\x03\x12\x15\n\x11\x44IRECTION_PATTERN\x10\x04*&\n\x05Level\x12\r\n\tLEVEL_LOW\x10\x00\x12\x0e\n\nLEVEL_HIGH\x10\x01*G\n\x04\x45\x64ge\x12\r\n\tEDGE_NONE\x10\x00\x12\x0f\n\x0b\x45\x44GE_RISING\x10\x01\x12\x10\n\x0c\x45\x44GE_FALLING\x10\x02\x12\r\n\tEDGE_BOTH\x10\x03*^\n\x0e\x43\x61ptureTrigger\x12\x17\n\x13\x43\x41PTURE_TRIGGER_NOW\x10\x00\x12\x19\n\x15\x43\x41PTURE_TRIGGER_LEVEL\x10\x01\x12\x18\n\x14\x43\x41PTURE_TRIGGER_EDGE\x10\x02*\x9f\x01\n\rCaptureStatus\x12\x17\n\x13\x43\x41PTURE_STATUS_IDLE\x10\x00\x12\"\n\x1e\x43\x41PTURE_STATUS_WAITING_TRIGGER\x10\x01\x12\x1c\n\x18\x43\x41PTURE_STATUS_TRIGGERED\x10\x02\x12\x17\n\x13\x43\x41PTURE_STATUS_DONE\x10\x03\x12\x1a\n\x16\x43\x41PTURE_STATUS_ABORTED\x10\x04*B\n\rWaitCondition\x12\x18\n\x14WAIT_CONDITION_LEVEL\x10\x00\x12\x17\n\x13WAIT_CONDITION_EDGE\x10\x01*_\n\tSafeState\x12\x13\n\x0fSAFE_STATE_NONE\x10\x00\x12\x14\n\x10SAFE_STATE_INPUT\x10\x01\x12\x12\n\x0eSAFE_STATE_LOW\x10\x02\x12\x13\n\x0fSAFE_STATE_HIGH\x10\x03*&\n\nAnswerType\x12\x0b\n\x07SUCCESS\x10\x00\x12\x0b\n\x07\x46\x41ILURE\x10\x01*\xce\x03\n\tErrorCode\x12\x13\n\x0f\x45RROR_CODE_NONE\x10\x00\x12\x1a\n\x16\x45RROR_CODE_INVALID_PIN\x10\x01\x12\x1b\n\x17\x45RROR_CODE_PIN_RESERVED\x10\x02\x12\x1e\n\x1a\x45RROR_CODE_WRONG_DIRECTION\x10\x03\x12\x1c\n\x18\x45RROR_CODE_INVALID_VALUE\x10\x04\x12#\n\x1f\x45RROR_CODE_UNKNOWN_REQUEST_TYPE\x10\x05\x12\x1d\n\x19\x45RROR_CODE_DECODE_FAILURE\x10\x06\x12!\n\x1d\x45RROR_CODE_PIN_NOT_CONFIGURED\x10\x07\x12\x1c\n\x18\x45RROR_CODE_NOT_SUPPORTED\x10\x08\x12\x1c\n\x18\x45RROR_CODE_RESOURCE_BUSY\x10\t\x12\x18\n\x14\x45RROR_CODE_CANCELLED\x10\n\x12\x16\n\x12\x45RROR_CODE_TIMEOUT\x10\x0b\x12\x1e\n\x1a\x45RROR_CODE_STORAGE_FAILURE\x10\x0c\x12\x1f\n\x1b\x45RROR_CODE_UNKNOWN_PIN_NAME\x10\r\x12\x1f\n\x1b\x45RROR_CODE_ANSWER_TOO_LARGE\x10\x0e\x62\x06proto3')
 
 _globals = globals()
 _builder.BuildMessageAndEnumDescriptors(DESCRIPTOR, _globals)
 _builder.BuildTopDescriptorsAndMessages(DESCRIPTOR, 'src.api_dio_pb2', _globals)
 if not _descriptor._USE_C_DESCRIPTORS:
   DESCRIPTOR._loaded_options = None
-  _globals['_REQUESTTYPE']._serialized_start=246
-  _globals['_REQUESTTYPE']._serialized_end=353
-  _globals['_PINVALUE']._serialized_start=355
-  _globals['_PINVALUE']._serialized_end=407
-  _globals['_ANSWERTYPE']._serialized_start=409
-  _globals['_ANSWERTYPE']._serialized_end=447
-  _globals['_PICOHADIOREQUEST']._serialized_start=21
-  _globals['_PICOHADIOREQUEST']._serialized_end=110
-  _globals['_PICOHADIOANSWER']._serialized_start=113
-  _globals['_PICOHADIOANSWER']._serialized_end=244
+  _globals['_REQUESTTYPE']._serialized_start=11065
+  _globals['_REQUESTTYPE']._serialized_end=11989
+  _globals['_PINVALUE']._serialized_start=11991
+  _globals['_PINVALUE']._serialized_end=12059
+  _globals['_PINPULL']._serialized_start=12061
+  _globals['_PINPULL']._serialized_end=12132
+  _globals['_PINDRIVESTRENGTH']._serialized_start=12134
+  _globals['_PINDRIVESTRENGTH']._serialized_end=12213
+  _globals['_PINSLEWRATE']._serialized_start=12215
+  _globals['_PINSLEWRATE']._serialized_end=12258
+  _globals['_DIRECTION']._serialized_start=12260
+  _globals['_DIRECTION']._serialized_end=12382
+  _globals['_LEVEL']._serialized_start=12384
+  _globals['_LEVEL']._serialized_end=12422
+  _globals['_EDGE']._serialized_start=12424
+  _globals['_EDGE']._serialized_end=12495
+  _globals['_CAPTURETRIGGER']._serialized_start=12497
+  _globals['_CAPTURETRIGGER']._serialized_end=12591
+  _globals['_CAPTURESTATUS']._serialized_start=12594
+  _globals['_CAPTURESTATUS']._serialized_end=12753
+  _globals['_WAITCONDITION']._serialized_start=12755
+  _globals['_WAITCONDITION']._serialized_end=12821
+  _globals['_SAFESTATE']._serialized_start=12823
+  _globals['_SAFESTATE']._serialized_end=12918
+  _globals['_ANSWERTYPE']._serialized_start=12920
+  _globals['_ANSWERTYPE']._serialized_end=12958
+  _globals['_ERRORCODE']._serialized_start=12961
+  _globals['_ERRORCODE']._serialized_end=13423
+  _globals['_PICOHADIOREQUEST']._serialized_start=22
+  _globals['_PICOHADIOREQUEST']._serialized_end=251
+  _globals['_PINGREQUEST']._serialized_start=253
+  _globals['_PINGREQUEST']._serialized_end=266
+  _globals['_SETPINDIRECTIONREQUEST']._serialized_start=268
+  _globals['_SETPINDIRECTIONREQUEST']._serialized_end=340
+  _globals['_SETPINVALUEREQUEST']._serialized_start=342
+  _globals['_SETPINVALUEREQUEST']._serialized_end=402
+  _globals['_GETPINDIRECTIONREQUEST']._serialized_start=404
+  _globals['_GETPINDIRECTIONREQUEST']._serialized_end=445
+  _globals['_GETPINVALUEREQUEST']._serialized_start=447
+  _globals['_GETPINVALUEREQUEST']._serialized_end=484
+  _globals['_SETPINPULLREQUEST']._serialized_start=486
+  _globals['_SETPINPULLREQUEST']._serialized_end=546
+  _globals['_GETPINPULLREQUEST']._serialized_start=548
+  _globals['_GETPINPULLREQUEST']._serialized_end=584
+  _globals['_SETPINDRIVEREQUEST']._serialized_start=586
+  _globals['_SETPINDRIVEREQUEST']._serialized_end=699
+  _globals['_GETPINDRIVEREQUEST']._serialized_start=701
+  _globals['_GETPINDRIVEREQUEST']._serialized_end=738
+  _globals['_SETPORTVALUEREQUEST']._serialized_start=740
+  _globals['_SETPORTVALUEREQUEST']._serialized_end=790
+  _globals['_SETPORTDIRECTIONREQUEST']._serialized_start=792
+  _globals['_SETPORTDIRECTIONREQUEST']._serialized_end=846
+  _globals['_GETPORTSTATEREQUEST']._serialized_start=848
+  _globals['_GETPORTSTATEREQUEST']._serialized_end=869
+  _globals['_PORTSTATE']._serialized_start=872
+  _globals['_PORTSTATE']._serialized_end=1026
+  _globals['_SUBSCRIBEEDGESREQUEST']._serialized_start=1028
+  _globals['_SUBSCRIBEEDGESREQUEST']._serialized_end=1089
+  _globals['_SETPINDEBOUNCEREQUEST']._serialized_start=1091
+  _globals['_SETPINDEBOUNCEREQUEST']._serialized_end=1152
+  _globals['_GETPINDEBOUNCEREQUEST']._serialized_start=1154
+  _globals['_GETPINDEBOUNCEREQUEST']._serialized_end=1194
+  _globals['_CONFIGUREEDGECOUNTERREQUEST']._serialized_start=1196
+  _globals['_CONFIGUREEDGECOUNTERREQUEST']._serialized_end=1263
+  _globals['_READEDGECOUNTERREQUEST']._serialized_start=1265
+  _globals['_READEDGECOUNTERREQUEST']._serialized_end=1321
+  _globals['_RESETEDGECOUNTERREQUEST']._serialized_start=1323
+  _globals['_RESETEDGECOUNTERREQUEST']._serialized_end=1365
+  _globals['_MEASUREFREQUENCYREQUEST']._serialized_start=1367
+  _globals['_MEASUREFREQUENCYREQUEST']._serialized_end=1431
+  _globals['_FREQUENCYMEASUREMENT']._serialized_start=1433
+  _globals['_FREQUENCYMEASUREMENT']._serialized_end=1536
+  _globals['_SETPINPWMREQUEST']._serialized_start=1538
+  _globals['_SETPINPWMREQUEST']._serialized_end=1615
+  _globals['_SETPINPWMDUTYREQUEST']._serialized_start=1617
+  _globals['_SETPINPWMDUTYREQUEST']._serialized_end=1676
+  _globals['_PWMOUTPUT']._serialized_start=1678
+  _globals['_PWMOUTPUT']._serialized_end=1731
+  _globals['_PULSEREQUEST']._serialized_start=1734
+  _globals['_PULSEREQUEST']._serialized_end=1882
+  _globals['_SEQUENCESTEP']._serialized_start=1884
+  _globals['_SEQUENCESTEP']._serialized_end=1947
+  _globals['_LOADSEQUENCEREQUEST']._serialized_start=1949
+  _globals['_LOADSEQUENCEREQUEST']._serialized_end=2016
+  _globals['_STARTSEQUENCEREQUEST']._serialized_start=2018
+  _globals['_STARTSEQUENCEREQUEST']._serialized_end=2064
+  _globals['_STOPSEQUENCEREQUEST']._serialized_start=2066
+  _globals['_STOPSEQUENCEREQUEST']._serialized_end=2087
+  _globals['_GETSEQUENCESTATEREQUEST']._serialized_start=2089
+  _globals['_GETSEQUENCESTATEREQUEST']._serialized_end=2114
+  _globals['_SEQUENCESTATE']._serialized_start=2116
+  _globals['_SEQUENCESTATE']._serialized_end=2193
+  _globals['_STARTCAPTUREREQUEST']._serialized_start=2196
+  _globals['_STARTCAPTUREREQUEST']._serialized_end=2417
+  _globals['_STOPCAPTUREREQUEST']._serialized_start=2419
+  _globals['_STOPCAPTUREREQUEST']._serialized_end=2439
+  _globals['_GETCAPTURESTATEREQUEST']._serialized_start=2441
+  _globals['_GETCAPTURESTATEREQUEST']._serialized_end=2465
+  _globals['_READCAPTUREREQUEST']._serialized_start=2467
+  _globals['_READCAPTUREREQUEST']._serialized_end=2503
+  _globals['_CAPTURESTATE']._serialized_start=2506
+  _globals['_CAPTURESTATE']._serialized_end=2748
+  _globals['_CAPTURECHUNK']._serialized_start=2750
+  _globals['_CAPTURECHUNK']._serialized_end=2813
+  _globals['_LOADPATTERNREQUEST']._serialized_start=2815
+  _globals['_LOADPATTERNREQUEST']._serialized_end=2903
+  _globals['_STARTPATTERNREQUEST']._serialized_start=2905
+  _globals['_STARTPATTERNREQUEST']._serialized_end=2967
+  _globals['_STOPPATTERNREQUEST']._serialized_start=2969
+  _globals['_STOPPATTERNREQUEST']._serialized_end=2989
+  _globals['_GETPATTERNSTATEREQUEST']._serialized_start=2991
+  _globals['_GETPATTERNSTATEREQUEST']._serialized_end=3015
+  _globals['_PATTERNSTATE']._serialized_start=3018
+  _globals['_PATTERNSTATE']._serialized_end=3172
+  _globals['_WAITFORREQUEST']._serialized_start=3174
+  _globals['_WAITFORREQUEST']._serialized_end=3274
+  _globals['_WAITRESULT']._serialized_start=3276
+  _globals['_WAITRESULT']._serialized_end=3324
+  _globals['_SYNCTIMEREQUEST']._serialized_start=3326
+  _globals['_SYNCTIMEREQUEST']._serialized_end=3365
+  _globals['_TIMESYNC']._serialized_start=3367
+  _globals['_TIMESYNC']._serialized_end=3420
+  _globals['_SETPINSAFESTATEREQUEST']._serialized_start=3422
+  _globals['_SETPINSAFESTATEREQUEST']._serialized_end=3490
+  _globals['_CONFIGUREFAILSAFEREQUEST']._serialized_start=3492
+  _globals['_CONFIGUREFAILSAFEREQUEST']._serialized_end=3548
+  _globals['_GETFAILSAFESTATEREQUEST']._serialized_start=3550
+  _globals['_GETFAILSAFESTATEREQUEST']._serialized_end=3590
+  _globals['_FAILSAFESTATE']._serialized_start=3593
+  _globals['_FAILSAFESTATE']._serialized_end=3751
+  _globals['_SAVECONFIGREQUEST']._serialized_start=3753
+  _globals['_SAVECONFIGREQUEST']._serialized_end=3772
+  _globals['_GETSAVEDCONFIGREQUEST']._serialized_start=3774
+  _globals['_GETSAVEDCONFIGREQUEST']._serialized_end=3814
+  _globals['_ERASECONFIGREQUEST']._serialized_start=3816
+  _globals['_ERASECONFIGREQUEST']._serialized_end=3836
+  _globals['_RESTOREDEFAULTSREQUEST']._serialized_start=3838
+  _globals['_RESTOREDEFAULTSREQUEST']._serialized_end=3862
+  _globals['_SAVEDPINCONFIG']._serialized_start=3865
+  _globals['_SAVEDPINCONFIG']._serialized_end=4099
+  _globals['_SETPINNAMEREQUEST']._serialized_start=4101
+  _globals['_SETPINNAMEREQUEST']._serialized_end=4151
+  _globals['_GETPINNAMESREQUEST']._serialized_start=4153
+  _globals['_GETPINNAMESREQUEST']._serialized_end=4173
+  _globals['_PINNAME']._serialized_start=4175
+  _globals['_PINNAME']._serialized_end=4215
+  _globals['_BATCHOPERATION']._serialized_start=4218
+  _globals['_BATCHOPERATION']._serialized_end=6204
+  _globals['_BATCHREQUEST']._serialized_start=6206
+  _globals['_BATCHREQUEST']._serialized_end=6282
+  _globals['_PICOHADIOREQUESTV2']._serialized_start=6285
+  _globals['_PICOHADIOREQUESTV2']._serialized_end=8585
+  _globals['_BATCHOPERATIONRESULT']._serialized_start=8588
+  _globals['_BATCHOPERATIONRESULT']._serialized_end=9531
+  _globals['_PICOHADIOANSWER']._serialized_start=9534
+  _globals['_PICOHADIOANSWER']._serialized_end=10911
+  _globals['_EDGEEVENT']._serialized_start=10913
+  _globals['_EDGEEVENT']._serialized_end=11007
+  _globals['_PICOHADIOEVENT']._serialized_start=11009
+  _globals['_PICOHADIOEVENT']._serialized_end=11062
 # @@protoc_insertion_point(module_scope)